@@ -33,30 +33,139 @@ pub enum Operation {
         /// Alias name to remove
         alias: String,
     },
+    /// Move aliases to another tracked alias file
+    Move {
+        /// Alias file to move them to, one of the tracked files
+        #[arg(long, value_name = "ALIAS_FILE_PATH", help = "Alias file to move them to, one of the tracked files")]
+        to: PathBuf,
+        /// Aliases to move
+        #[arg(required = true)]
+        aliases: Vec<String>,
+    },
     /// List all aliases
-    List,
-    /// Change an existing alias to a new alias and/or command
+    List {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table, help = "Output format")]
+        format: OutputFormat,
+    },
+    /// Rename an alias and/or change its command, in the file it is defined in
     Change {
-        /// Old alias name
+        /// Alias to change
         old_alias: String,
-        /// New alias name
-        new_alias: String,
+        /// New alias name (default: keep the name)
+        new_alias: Option<String>,
+        /// New command for the alias, same as -c
+        #[arg(conflicts_with = "command")]
+        new_command: Option<String>,
         /// New command for the alias
-        command: String,
+        #[arg(short = 'c', long, help = "New command for the alias")]
+        command: Option<String>,
+        /// Edit the current command interactively (the default when neither a new name nor a command is given)
+        #[arg(short = 'e', long, conflicts_with_all = ["command", "new_command"], help = "Edit the current command interactively")]
+        edit: bool,
     },
     /// Get intelligent alias suggestions based on command history
     GetSuggestions {
         /// Number of suggestions to display
         #[arg(short = 'n', long, help = "Number of suggestions to display")]
         num: Option<usize>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table, help = "Output format")]
+        format: OutputFormat,
+    },
+    /// Add the top alias of the top suggestions in one go
+    Accept {
+        /// Number of suggestions to accept
+        #[arg(short = 'n', long, default_value_t = 5, help = "Number of suggestions to accept")]
+        num: usize,
+        /// Only accept suggestions whose command contains this text
+        #[arg(long, help = "Only accept suggestions whose command contains this text")]
+        filter: Option<String>,
+        /// Show what would be written without changing any file
+        #[arg(long, help = "Show what would be written without changing any file")]
+        dry_run: bool,
+        /// Ask before adding each alias, with the option to rename it
+        #[arg(short = 'i', long, help = "Ask before adding each alias, with the option to rename it")]
+        interactive: bool,
+    },
+    /// Fuzzy search alias names and commands in every tracked alias file
+    Search {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table, help = "Output format")]
+        format: OutputFormat,
+        /// What to look for, e.g. gcb or checkout
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        query: Vec<String>,
+    },
+    /// Show where an alias is defined, and any definitions or commands it shadows
+    Which {
+        /// Alias name to look up
+        alias: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table, help = "Output format")]
+        format: OutputFormat,
+    },
+    /// Expand the aliases in a command line the way the shell would
+    Expand {
+        /// Command line to expand (quote it to keep && and | away from your shell)
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+    },
+    /// Explain how a command's score and alias suggestions came about
+    Explain {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table, help = "Output format")]
+        format: OutputFormat,
+        /// Command to explain, as recorded (e.g. git add .)
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+    },
+    /// Show usage statistics from the recorded history
+    Stats {
+        /// Number of days to cover, ending today
+        #[arg(short = 'd', long, default_value_t = 30, help = "Number of days to cover, ending today")]
+        days: usize,
+        /// Deprecated, use --format json
+        #[arg(long, hide = true)]
+        json: bool,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table, help = "Output format")]
+        format: OutputFormat,
     },
     /// Delete alias suggestions for a specific alias
     DeleteSuggestion {
         /// Alias name to delete suggestions for
         alias: String,
     },
+    /// List, restore, block or snooze commands kept out of the suggestions
+    Ignored {
+        #[command(subcommand)]
+        action: IgnoredAction,
+    },
     /// Launch the interactive terminal user interface (TUI)
     Tui,
+    /// Read new commands from a shell history file (for shells without a preexec hook)
+    SyncHistory {
+        /// History file to read (default: $HISTFILE or the current shell's history file)
+        histfile: Option<PathBuf>,
+        /// Also sync this file automatically every time alman runs
+        #[arg(long, help = "Also sync this file automatically every time alman runs")]
+        track: bool,
+        /// Stop syncing this file automatically
+        #[arg(long, conflicts_with = "track", help = "Stop syncing this file automatically")]
+        untrack: bool,
+    },
+    /// Import command history from an atuin, zsh-histdb or McFly database
+    Import {
+        #[arg(value_enum, help = "History tool to import from")]
+        source: ImportSource,
+        /// Path to the history database (default: the tool's usual location)
+        #[arg(short = 'p', long, help = "Path to the history database")]
+        path: Option<PathBuf>,
+        /// Also import commands recorded on other machines
+        #[arg(long, help = "Also import commands recorded on other machines")]
+        all_hosts: bool,
+    },
     #[command(hide = true)]
     Init {
         #[arg(value_enum, help = "Shell type to initialize (bash, zsh, fish, posix)")]
@@ -64,6 +173,75 @@ pub enum Operation {
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum IgnoredAction {
+    /// List deleted and snoozed commands and block rules
+    List {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table, help = "Output format")]
+        format: OutputFormat,
+    },
+    /// Suggest a deleted or snoozed command again, or drop a block rule
+    Restore {
+        /// Command or block rule pattern, as listed
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        entry: Vec<String>,
+    },
+    /// Never suggest commands matching a pattern
+    Block {
+        /// Glob pattern over the whole command (e.g. 'cd *')
+        pattern: String,
+        /// Treat the pattern as a regular expression instead
+        #[arg(long, help = "Treat the pattern as a regular expression instead")]
+        regex: bool,
+    },
+    /// Hide a command from the suggestions for a while
+    Snooze {
+        /// How long, e.g. 12h, 3d or 2w
+        #[arg(long = "for", value_name = "DURATION", default_value = "7d", help = "How long, e.g. 12h, 3d or 2w")]
+        duration: String,
+        /// Command to snooze, as recorded
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+    },
+}
+
+impl Operation {
+    // The --format asked for, for commands that have one
+    pub fn output_format(&self) -> Option<OutputFormat> {
+        match self {
+            Operation::List { format }
+            | Operation::GetSuggestions { format, .. }
+            | Operation::Explain { format, .. }
+            | Operation::Search { format, .. }
+            | Operation::Which { format, .. }
+            | Operation::Ignored { action: IgnoredAction::List { format } } => Some(*format),
+            Operation::Stats { json: true, .. } => Some(OutputFormat::Json),
+            Operation::Stats { format, .. } => Some(*format),
+            _ => None,
+        }
+    }
+}
+
+// For the read commands. Everything but `table` is meant for scripts, see the README for
+// the fields each command prints.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Csv,
+    Tsv,
+    Plain,
+}
+
+#[derive(ValueEnum, Clone, Debug)]
+pub enum ImportSource {
+    Atuin,
+    ZshHistdb,
+    Mcfly,
+}
+
 #[derive(ValueEnum, Clone, Debug)]
 pub enum InitShell {
     Bash,
@@ -1,2 +1,3 @@
 pub mod arg_handler;
 pub mod cli_data;
+pub mod output;
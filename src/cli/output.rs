@@ -0,0 +1,358 @@
+// What the read commands print for `--format json|csv|tsv|plain`. Scripts and editors read
+// this, so field names and column order are documented in the README: new fields go at the
+// end, existing ones don't change.
+use crate::cli::cli_data::OutputFormat;
+use crate::ops::alias_lookup::{print_search, print_which, SearchHit, WhichReport};
+use crate::ops::alias_suggestions::PriorityBreakdown;
+use crate::ops::explain::{print_explanation, Explanation};
+use crate::ops::get_suggestions::CommandWithAlias;
+use crate::ops::ignored::{IgnoredEntry, IgnoredKind};
+use crate::ops::usage_stats::{print_report, StatsReport, WEEKDAYS};
+use crate::database::database_structs::BlockKind;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct AliasRecord {
+    pub alias: String,
+    pub command: String,
+    pub file: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SuggestionRecord {
+    pub rank: usize,
+    pub command: String,
+    pub score: i32,
+    pub frequency: i32,
+    // Unix timestamps, first_used is null for commands recorded before it was tracked
+    pub last_used: i64,
+    pub first_used: Option<i64>,
+    pub daily_savings: f64,
+    // Best first
+    pub aliases: Vec<AliasOption>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AliasOption {
+    pub alias: String,
+    pub reason: String,
+    pub priority: i32,
+    pub priority_breakdown: PriorityBreakdown,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IgnoredRecord {
+    // deleted, aliased, glob, regex or snoozed
+    pub kind: &'static str,
+    pub text: String,
+    pub until: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchRecord {
+    pub alias: String,
+    pub command: String,
+    pub file: String,
+    pub line: usize,
+    pub score: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DefinitionRecord {
+    pub alias: String,
+    pub command: String,
+    pub file: String,
+    pub line: usize,
+    // Only the last definition is, the shell overwrites the others
+    pub active: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WhichRecord {
+    pub alias: String,
+    pub definitions: Vec<DefinitionRecord>,
+    pub shadows: Option<String>,
+}
+
+pub fn print_aliases(format: OutputFormat, aliases: &[AliasRecord]) {
+    match format {
+        OutputFormat::Json => print_json(aliases),
+        OutputFormat::Csv | OutputFormat::Tsv => print_rows(
+            format,
+            &["alias", "command", "file"],
+            aliases.iter().map(|a| vec![a.alias.clone(), a.command.clone(), a.file.clone()]).collect(),
+        ),
+        OutputFormat::Plain | OutputFormat::Table => {
+            for alias in aliases {
+                println!("{} = {}", alias.alias, alias.command);
+            }
+        }
+    }
+}
+
+pub fn suggestion_records(list: &[CommandWithAlias]) -> Vec<SuggestionRecord> {
+    list.iter()
+        .enumerate()
+        .map(|(i, suggestion)| SuggestionRecord {
+            rank: i + 1,
+            command: suggestion.command.command_text.clone(),
+            score: suggestion.command.score,
+            frequency: suggestion.command.frequency,
+            last_used: suggestion.command.last_access_time,
+            first_used: Some(suggestion.command.first_access_time).filter(|t| *t > 0),
+            daily_savings: suggestion.daily_savings,
+            aliases: suggestion
+                .alias_suggestions
+                .iter()
+                .zip(&suggestion.priorities)
+                .map(|(alias, priority)| AliasOption {
+                    alias: alias.alias.clone(),
+                    reason: alias.reason.clone(),
+                    priority: priority.total(),
+                    priority_breakdown: priority.clone(),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+pub fn print_suggestions(format: OutputFormat, list: &[CommandWithAlias]) {
+    let records = suggestion_records(list);
+    match format {
+        OutputFormat::Json => print_json(&records),
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            // One row per alias, the command's columns repeated
+            let mut rows = Vec::new();
+            for record in &records {
+                for (i, option) in record.aliases.iter().enumerate() {
+                    rows.push(vec![
+                        record.rank.to_string(),
+                        record.command.clone(),
+                        record.score.to_string(),
+                        record.frequency.to_string(),
+                        record.last_used.to_string(),
+                        record.first_used.map(|t| t.to_string()).unwrap_or_default(),
+                        format!("{:.1}", record.daily_savings),
+                        (i + 1).to_string(),
+                        option.alias.clone(),
+                        option.priority.to_string(),
+                        option.priority_breakdown.category.to_string(),
+                        option.reason.clone(),
+                    ]);
+                }
+            }
+            let header = [
+                "rank", "command", "score", "frequency", "last_used", "first_used", "daily_savings",
+                "alias_rank", "alias", "priority", "category", "reason",
+            ];
+            print_rows(format, &header, rows);
+        }
+        OutputFormat::Plain | OutputFormat::Table => {
+            for record in &records {
+                let aliases: Vec<&str> = record.aliases.iter().map(|a| a.alias.as_str()).collect();
+                println!("{} -> {}", record.command, aliases.join(", "));
+            }
+        }
+    }
+}
+
+pub fn print_explanation_as(format: OutputFormat, explanation: &Explanation) {
+    match format {
+        OutputFormat::Json => print_json(explanation),
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let row = |section: &str, key: &str, value: String| vec![section.to_string(), key.to_string(), value];
+            let mut rows = vec![
+                row("command", "text", explanation.command.command_text.clone()),
+                row("command", "frequency", explanation.command.frequency.to_string()),
+                row("command", "last_used", explanation.command.last_access_time.to_string()),
+                row("score", "scorer", explanation.scorer.to_string()),
+                row("score", "total", explanation.score.to_string()),
+                row("score", "rank", explanation.rank.map(|r| r.to_string()).unwrap_or_default()),
+                row("score", "ranked_commands", explanation.total_commands.to_string()),
+            ];
+            rows.extend(explanation.components.iter().map(|c| row("component", &c.label, c.value.clone())));
+            rows.extend(explanation.prefixes.iter().map(|(text, freq)| row("prefix", text, freq.to_string())));
+            rows.extend(explanation.extensions.iter().map(|(text, freq)| row("extension", text, freq.to_string())));
+            if let Some(template) = &explanation.template {
+                rows.push(row("template", "text", template.clone()));
+            }
+            for ranked in &explanation.suggestions {
+                let alias = &ranked.suggestion.alias;
+                rows.push(row("alias_priority", alias, ranked.priority.total().to_string()));
+                rows.push(row("alias_reason", alias, ranked.suggestion.reason.clone()));
+                rows.push(row("alias_daily_savings", alias, format!("{:.1}", ranked.savings.daily_keystrokes)));
+            }
+            print_rows(format, &["section", "key", "value"], rows);
+        }
+        OutputFormat::Plain | OutputFormat::Table => print_explanation(explanation),
+    }
+}
+
+pub fn print_stats(format: OutputFormat, report: &StatsReport) {
+    match format {
+        OutputFormat::Json => print_json(report),
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let row = |section: &str, key: &str, value: String| vec![section.to_string(), key.to_string(), value];
+            let mut rows = vec![
+                row("summary", "from", report.from.clone()),
+                row("summary", "to", report.to.clone()),
+                row("summary", "total_commands", report.total_commands.to_string()),
+                row("summary", "active_days", report.active_days.to_string()),
+                row("summary", "average_per_active_day", format!("{:.2}", report.average_per_active_day)),
+                row("summary", "alias_runs", report.alias_runs.to_string()),
+                row("summary", "full_runs_with_alias", report.full_runs_with_alias.to_string()),
+                row("summary", "alias_usage_ratio", format!("{:.4}", report.alias_usage_ratio)),
+                row("summary", "keystrokes_saved", report.keystrokes_saved.to_string()),
+                row("summary", "keystrokes_saved_all_time", report.keystrokes_saved_all_time.to_string()),
+            ];
+            rows.extend(report.top_tools.iter().map(|t| row("tool", &t.tool, t.count.to_string())));
+            rows.extend(report.top_aliases.iter().map(|a| row("alias", &a.alias, a.count.to_string())));
+            for day in &report.days {
+                rows.push(row("day_commands", &day.day, day.commands.to_string()));
+                rows.push(row("day_new_commands", &day.day, day.new_commands.to_string()));
+                rows.push(row("day_keystrokes_saved", &day.day, day.keystrokes_saved.to_string()));
+            }
+            rows.extend(report.hours.iter().enumerate().map(|(hour, count)| row("hour", &format!("{:02}", hour), count.to_string())));
+            rows.extend(WEEKDAYS.iter().zip(report.weekdays.iter()).map(|(day, count)| row("weekday", day, count.to_string())));
+            for week in &report.new_commands_per_week {
+                rows.push(row("week_new_commands", &week.week, week.new_commands.to_string()));
+                rows.push(row("week_cumulative", &week.week, week.cumulative.to_string()));
+            }
+            print_rows(format, &["section", "key", "value"], rows);
+        }
+        OutputFormat::Plain | OutputFormat::Table => print_report(report),
+    }
+}
+
+pub fn print_ignored(format: OutputFormat, entries: &[IgnoredEntry]) {
+    let records: Vec<IgnoredRecord> = entries
+        .iter()
+        .map(|entry| {
+            let (kind, until) = match entry.kind {
+                IgnoredKind::Deleted => ("deleted", None),
+                IgnoredKind::Aliased(_) => ("aliased", None),
+                IgnoredKind::Blocked(BlockKind::Glob) => ("glob", None),
+                IgnoredKind::Blocked(BlockKind::Regex) => ("regex", None),
+                IgnoredKind::Snoozed(until) => ("snoozed", Some(until)),
+            };
+            IgnoredRecord { kind, text: entry.text.clone(), until }
+        })
+        .collect();
+    match format {
+        OutputFormat::Json => print_json(&records),
+        OutputFormat::Csv | OutputFormat::Tsv => print_rows(
+            format,
+            &["kind", "text", "until"],
+            records
+                .iter()
+                .map(|r| vec![r.kind.to_string(), r.text.clone(), r.until.map(|t| t.to_string()).unwrap_or_default()])
+                .collect(),
+        ),
+        OutputFormat::Plain | OutputFormat::Table => {
+            for entry in entries {
+                println!("{}\t{}", entry.label(), entry.text);
+            }
+        }
+    }
+}
+
+pub fn print_search_hits(format: OutputFormat, hits: &[SearchHit]) {
+    let records: Vec<SearchRecord> = hits
+        .iter()
+        .map(|hit| SearchRecord {
+            alias: hit.definition.alias.clone(),
+            command: hit.definition.command.clone(),
+            file: hit.definition.file.clone(),
+            line: hit.definition.line,
+            score: hit.score,
+        })
+        .collect();
+    match format {
+        OutputFormat::Json => print_json(&records),
+        OutputFormat::Csv | OutputFormat::Tsv => print_rows(
+            format,
+            &["alias", "command", "file", "line", "score"],
+            records
+                .iter()
+                .map(|r| vec![r.alias.clone(), r.command.clone(), r.file.clone(), r.line.to_string(), r.score.to_string()])
+                .collect(),
+        ),
+        OutputFormat::Plain => {
+            for record in &records {
+                println!("{} = {}", record.alias, record.command);
+            }
+        }
+        OutputFormat::Table => print_search(hits),
+    }
+}
+
+pub fn print_which_as(format: OutputFormat, report: &WhichReport) {
+    let last = report.definitions.len().saturating_sub(1);
+    let record = WhichRecord {
+        alias: report.alias.clone(),
+        definitions: report
+            .definitions
+            .iter()
+            .enumerate()
+            .map(|(i, d)| DefinitionRecord {
+                alias: d.alias.clone(),
+                command: d.command.clone(),
+                file: d.file.clone(),
+                line: d.line,
+                active: i == last,
+            })
+            .collect(),
+        shadows: report.shadows.clone(),
+    };
+    match format {
+        OutputFormat::Json => print_json(&record),
+        OutputFormat::Csv | OutputFormat::Tsv => print_rows(
+            format,
+            &["alias", "command", "file", "line", "active"],
+            record
+                .definitions
+                .iter()
+                .map(|d| vec![d.alias.clone(), d.command.clone(), d.file.clone(), d.line.to_string(), d.active.to_string()])
+                .collect(),
+        ),
+        // Just what the name runs, for `$(alman which gs --format plain)`
+        OutputFormat::Plain => {
+            if let Some(active) = report.definitions.last() {
+                println!("{}", active.command);
+            }
+        }
+        OutputFormat::Table => print_which(report),
+    }
+}
+
+fn print_json<T: Serialize + ?Sized>(value: &T) {
+    match serde_json::to_string_pretty(value) {
+        Ok(json) => println!("{}", json),
+        Err(e) => eprintln!("Failed to serialize output: {}", e),
+    }
+}
+
+// csv follows RFC 4180 quoting; tsv has no quoting, so tabs, newlines and backslashes in a
+// field are escaped as \t, \n and \\
+fn print_rows(format: OutputFormat, header: &[&str], rows: Vec<Vec<String>>) {
+    let (separator, escape): (&str, fn(&str) -> String) = match format {
+        OutputFormat::Tsv => ("\t", tsv_field),
+        _ => (",", csv_field),
+    };
+    println!("{}", header.iter().map(|h| escape(h)).collect::<Vec<_>>().join(separator));
+    for row in rows {
+        println!("{}", row.iter().map(|f| escape(f)).collect::<Vec<_>>().join(separator));
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn tsv_field(field: &str) -> String {
+    field.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n").replace('\r', "\\r")
+}
@@ -0,0 +1,191 @@
+// Commands as a tree of words, so `git`, `git commit` and `git commit -m` share one branch.
+// The database only stores what was actually run; how much each level is used is summed up
+// from the runs below it when the tree is built.
+use super::database_structs::{Command, Database, DeletedCommands};
+use super::scoring::Scorer;
+use crate::ops::ignored::IgnoreMatcher;
+use crate::ops::normalize_command::{alias_target, is_template};
+use crate::ops::parse_command::parse_command_line;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Default)]
+pub struct CommandNode {
+    pub text: String,
+    // Runs of exactly this command
+    pub own_runs: i32,
+    // Runs of this command and of everything it is a prefix of
+    pub frequency: i32,
+    pub last_access_time: i64,
+    // 0 if any run below is from before first use was tracked
+    pub first_access_time: i64,
+    pub decays: u32,
+    pub children: BTreeMap<String, CommandNode>,
+}
+
+impl CommandNode {
+    pub fn to_command(&self) -> Command {
+        let mut command = Command::new_at(self.text.clone(), self.last_access_time);
+        command.frequency = self.frequency;
+        command.first_access_time = self.first_access_time;
+        command.decays = self.decays;
+        command
+    }
+
+    fn add(&mut self, command: &Command) {
+        self.first_access_time = if self.frequency == 0 {
+            command.first_access_time
+        } else if self.first_access_time == 0 || command.first_access_time == 0 {
+            0
+        } else {
+            self.first_access_time.min(command.first_access_time)
+        };
+        self.frequency += command.frequency;
+        self.last_access_time = self.last_access_time.max(command.last_access_time);
+        self.decays = self.decays.max(command.decays);
+    }
+
+    // Every node below this one, depth first
+    pub fn descendants(&self) -> Vec<&CommandNode> {
+        let mut nodes = Vec::new();
+        for child in self.children.values() {
+            nodes.push(child);
+            nodes.extend(child.descendants());
+        }
+        nodes
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CommandTree {
+    root: CommandNode,
+    // Pipelines, templates and sequences, which don't nest
+    standalone: BTreeMap<String, Command>,
+}
+
+impl CommandTree {
+    pub fn build(db: &Database) -> Self {
+        let mut tree = Self::default();
+        for command in db.reverse_command_map.values() {
+            match simple_command_words(&command.command_text) {
+                Some(words) => tree.insert(&words, command),
+                None => {
+                    tree.standalone.insert(command.command_text.clone(), command.clone());
+                }
+            }
+        }
+        tree
+    }
+
+    fn insert(&mut self, words: &[String], command: &Command) {
+        let mut node = &mut self.root;
+        for (i, word) in words.iter().enumerate() {
+            node = node.children.entry(word.clone()).or_insert_with(|| CommandNode {
+                text: words[..=i].join(" "),
+                ..Default::default()
+            });
+            node.add(command);
+        }
+        node.own_runs += command.frequency;
+    }
+
+    pub fn node(&self, command_text: &str) -> Option<&CommandNode> {
+        let words = simple_command_words(command_text)?;
+        let mut node = &self.root;
+        for word in &words {
+            node = node.children.get(word)?;
+        }
+        Some(node)
+    }
+
+    // The nodes from the first word down to `command_text`, the command itself excluded
+    pub fn ancestors(&self, command_text: &str) -> Vec<&CommandNode> {
+        let words = simple_command_words(command_text).unwrap_or_default();
+        let mut nodes = Vec::new();
+        let mut node = &self.root;
+        for word in words.iter().take(words.len().saturating_sub(1)) {
+            match node.children.get(word) {
+                Some(child) => {
+                    nodes.push(child);
+                    node = child;
+                }
+                None => break,
+            }
+        }
+        nodes
+    }
+
+    // Usage of `command_text`, whether it was run as is or only as part of longer commands
+    pub fn lookup(&self, command_text: &str) -> Option<Command> {
+        if let Some(command) = self.standalone.get(command_text) {
+            return Some(command.clone());
+        }
+        self.node(command_text).filter(|node| node.frequency > 0).map(|node| node.to_command())
+    }
+
+    // Along every branch that ends in a command that was run, the level `scorer` values most,
+    // so `git commit -m` is suggested instead of both `git` and `git commit`. Sorted by score.
+    pub fn select(&self, scorer: &dyn Scorer, now: i64, deleted_commands: &DeletedCommands) -> Vec<Command> {
+        let ignored = IgnoreMatcher::new(deleted_commands, now);
+        // Same rule as for storing commands: short single words aren't worth an alias
+        let single_short_word = |text: &str| !text.contains(' ') && text.len() <= 5;
+        let eligible = |text: &str| !single_short_word(text) && !ignored.is_ignored(text);
+
+        let mut selected: BTreeMap<String, Command> = BTreeMap::new();
+        let mut stack: Vec<(&CommandNode, Option<(i32, &CommandNode)>)> =
+            self.root.children.values().map(|child| (child, None)).collect();
+        while let Some((node, best_above)) = stack.pop() {
+            let mut best = best_above;
+            if eligible(&node.text) {
+                let score = scorer.score(&node.to_command(), now);
+                // On a tie the longer command wins, it saves more per run
+                if best.map(|(best_score, _)| score >= best_score).unwrap_or(true) {
+                    best = Some((score, node));
+                }
+            }
+            if node.own_runs > 0 {
+                if let Some((score, best_node)) = best {
+                    selected.entry(best_node.text.clone()).or_insert_with(|| {
+                        let mut command = best_node.to_command();
+                        command.score = score;
+                        command
+                    });
+                }
+            }
+            stack.extend(node.children.values().map(|child| (child, best)));
+        }
+
+        let mut commands: Vec<Command> = selected.into_values().collect();
+        commands.extend(
+            self.standalone
+                .values()
+                // `cd <path>` would be aliased as plain `cd`
+                .filter(|command| {
+                    !single_short_word(&alias_target(&command.command_text)) && !ignored.is_ignored(&command.command_text)
+                })
+                .map(|command| {
+                    let mut scored = command.clone();
+                    scored.score = scorer.score(command, now);
+                    scored
+                }),
+        );
+        commands.sort();
+        commands
+    }
+}
+
+// The words of a plain command like `git commit -m "msg"`. None for pipelines, sequences and
+// templates, which are kept as they are instead of being split into levels.
+pub fn simple_command_words(command_text: &str) -> Option<Vec<String>> {
+    if is_template(command_text) {
+        return None;
+    }
+    let mut pipelines = parse_command_line(command_text).ok()?;
+    if pipelines.len() != 1 || pipelines[0].commands.len() != 1 {
+        return None;
+    }
+    let words = pipelines.remove(0).commands.remove(0).words;
+    if words.is_empty() {
+        return None;
+    }
+    Some(words)
+}
@@ -1,114 +1,110 @@
 
 // use core::time;
 // use std::collections::BTreeSet;
-use std::time::{SystemTime, UNIX_EPOCH};
-use super::database_structs::{Command, Database, DeletedCommands};
+use super::command_tree::{simple_command_words, CommandTree};
+use super::database_structs::{Command, Database, DeletedCommands, DB_FORMAT_VERSION};
+use super::scoring::{now, BucketScorer, Scorer};
 
+// The score stored on each record is only bookkeeping for score_reset, it always uses the
+// bucket scorer. Rankings are computed at query time with the configured scorer.
 fn get_score(command: &Command) -> i32 {
-    let now = SystemTime::now();
-    let current_time: i64 = now.duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
-    let time_difference: i64 = current_time - command.last_access_time;
-    let mult: f64;
-    if time_difference <= 3600 {
-        mult=4.0;
-    }
-    else if time_difference <= 86400 {
-        mult=2.0;
-    }
-    else if time_difference <= 604800 {
-        mult=0.5;
-    }
-    else {
-        mult=0.25;
-    }
-    let length = command.length as f64;
-    let frequency = command.frequency as f64;
-
-    (mult * length.powf(3.0 / 5.0) * frequency) as i32
-    
-    
+    BucketScorer.score(command, now())
 }
 
 impl Database {
-    pub fn add_command(&mut self, command_str: String, deleted_commands: &DeletedCommands) {
-        if !deleted_commands.deleted_commands.contains(&command_str) {
-            if let Some(existing_command) = self.reverse_command_map.get(&command_str) {
-                let mut updated_command = existing_command.clone();
-                self.total_score -= updated_command.score as i64;
-                self.total_num_commands -= 1;
-                updated_command.add();
-
-                self.command_list.remove(existing_command);
-                self.reverse_command_map.remove(&command_str);
-
-                self.command_list.insert(updated_command.clone());
-                self.reverse_command_map.insert(command_str.clone(), updated_command);
-
-                self.total_num_commands += 1;
-                self.total_score += self.reverse_command_map.get(&command_str).unwrap().score as i64;
-
-            } else {
-                let new_command: Command = Command::new(command_str.clone());
-                if new_command.length <= 5 && new_command.number_of_words == 1 {
-                    return; // Ignore commands that are too short and single-word
-                }
-                self.command_list.insert(new_command.clone());
-                self.reverse_command_map.insert(command_str.clone(), new_command);
-                self.total_num_commands += 1;
-                self.total_score += self.reverse_command_map.get(&command_str).unwrap().score as i64;
-            }
-            let threshold: i64 = 10000; // Much higher threshold
-            if self.total_score > threshold {
-                self.score_reset();
+    // timestamp is when the command ran, which is in the past when reading back a history file.
+    // Deleted and aliased commands are recorded too, they're left out when suggestions are picked.
+    pub fn add_command_at(&mut self, command_str: String, timestamp: i64) {
+        if let Some(existing_command) = self.reverse_command_map.get(&command_str) {
+            let mut updated_command = existing_command.clone();
+            self.total_score -= updated_command.score as i64;
+            self.total_num_commands -= 1;
+            updated_command.add_at(timestamp);
+
+            self.command_list.remove(existing_command);
+            self.reverse_command_map.remove(&command_str);
+
+            self.command_list.insert(updated_command.clone());
+            self.reverse_command_map.insert(command_str.clone(), updated_command);
+
+            self.total_num_commands += 1;
+            self.total_score += self.reverse_command_map.get(&command_str).unwrap().score as i64;
+
+        } else {
+            let new_command: Command = Command::new_at(command_str.clone(), timestamp);
+            if new_command.length <= 5 && new_command.number_of_words == 1 {
+                return; // Ignore commands that are too short and single-word
             }
-            
+            self.command_list.insert(new_command.clone());
+            self.reverse_command_map.insert(command_str.clone(), new_command);
+            self.total_num_commands += 1;
+            self.total_score += self.reverse_command_map.get(&command_str).unwrap().score as i64;
+        }
+        let threshold: i64 = 10000; // Much higher threshold
+        if self.total_score > threshold {
+            self.score_reset();
         }
     }
 
-    pub fn remove_command(&mut self, command_str: &String, deleted_commands: &mut DeletedCommands) {
-        // first check if already exists in DeletedCommands
-        if !deleted_commands.deleted_commands.contains(command_str) {
-            deleted_commands
-                .deleted_commands
-                .insert(command_str.clone());
-            // then remove from command list, first get command from reverse map
-            let command_to_remove = self.reverse_command_map.get(command_str);
-            // if not null, remove it fromm both
-            if let Some(cmd) = command_to_remove {
-                self.command_list.remove(cmd);
-                self.total_num_commands -= 1;
-                self.total_score -= cmd.score as i64;
-                self.reverse_command_map.remove(command_str);
+    // Top `n` commands ranked by `scorer` as of now, with `score` set to that ranking score. Of
+    // `git`, `git commit` and `git commit -m` only the level worth the most is included.
+    pub fn get_top_commands(&self, n: Option<usize>, scorer: &dyn Scorer, deleted_commands: &DeletedCommands) -> Vec<Command> {
+        let n = n.unwrap_or(5);
+        let mut commands = CommandTree::build(self).select(scorer, now(), deleted_commands);
+        commands.truncate(n);
+        commands
+    }
+
+    // Older databases recorded every run once for the full command and once for each of its
+    // prefixes, so a record's own runs are what's left after taking off the runs recorded further
+    // down its branch. Those are added up from the deepest level, passing through prefixes that
+    // have no record (deleted, decayed away or too short to keep). Score resets rounded every
+    // record on its own, which can leave a little less than nothing; records with nothing left
+    // were only ever prefixes.
+    pub fn drop_prefix_records(&mut self) {
+        use std::collections::HashMap;
+
+        // Every prefix by length, with what its record counted if it has one
+        let mut levels: Vec<HashMap<String, Option<i32>>> = Vec::new();
+        for command in self.reverse_command_map.values() {
+            if let Some(words) = simple_command_words(&command.command_text) {
+                if levels.len() < words.len() {
+                    levels.resize_with(words.len(), Default::default);
+                }
+                levels[words.len() - 1].insert(words.join(" "), Some(command.frequency));
+            }
+        }
+        // Runs recorded below each prefix, by the closest records down each branch
+        let mut below: HashMap<String, i32> = HashMap::new();
+        for depth in (1..levels.len()).rev() {
+            let level = std::mem::take(&mut levels[depth]);
+            for (prefix, recorded) in level {
+                let runs = recorded.unwrap_or_else(|| below.get(&prefix).copied().unwrap_or(0));
+                let parent = prefix.rsplit_once(' ').map(|(parent, _)| parent.to_string()).unwrap_or_default();
+                levels[depth - 1].entry(parent.clone()).or_insert(None);
+                *below.entry(parent).or_insert(0) += runs;
             }
         }
-    }
 
-    pub fn update_db(&mut self) {
-    // Update each command in place
-    let keys: Vec<String> = self.reverse_command_map.keys().cloned().collect();
-    for key in keys {
-        if let Some(command) = self.reverse_command_map.get_mut(&key) {
-            self.total_score -= command.score as i64;
-            command.update();
+        let old_map = std::mem::take(&mut self.reverse_command_map);
+        self.command_list.clear();
+        self.total_num_commands = 0;
+        self.total_score = 0;
+        for (text, mut command) in old_map {
+            if let Some(words) = simple_command_words(&text) {
+                command.frequency -= below.get(&words.join(" ")).copied().unwrap_or(0);
+                if command.frequency < 1 {
+                    continue;
+                }
+                command.score = get_score(&command);
+            }
+            self.total_num_commands += 1;
             self.total_score += command.score as i64;
-
-            // Remove and re-insert in command_list to update ordering if needed
-            self.command_list.remove(command);
             self.command_list.insert(command.clone());
+            self.reverse_command_map.insert(text, command);
         }
+        self.format_version = DB_FORMAT_VERSION;
     }
-    let threshold: i64 = 10000; // Much higher threshold
-    if self.total_score > threshold {
-        self.score_reset();
-    }
-}
-
-    pub fn get_top_commands(&mut self, n: Option<usize>) -> Vec<&Command> {
-        let n = n.unwrap_or(5);
-        self.command_list.iter().take(n).collect()
-    }
-
-
 
     pub fn score_reset(&mut self){
         //iterate through the set & map and reduce the freq of each string by 50% and delete the strings with 0 freq
@@ -117,8 +113,9 @@ impl Database {
         let mut sc:i64=0;
         for (key, value) in self.reverse_command_map.iter_mut() {
             value.frequency = (value.frequency as f32 * 0.5).round() as i32; // Reduce by 50% instead of 90%
+            value.decays += 1;
             self.total_score -= value.score as i64;
-            value.score = get_score(&value);
+            value.score = get_score(value);
             sc+= value.score as i64;
             if value.frequency < 1 {
                 self.total_num_commands-=1;
@@ -136,6 +133,7 @@ impl Database {
 
         for mut cmd in old_set {
             cmd.frequency = (cmd.frequency as f32 * 0.5).round() as i32; // Reduce by 50% instead of 90%
+            cmd.decays += 1;
             cmd.score=get_score(&cmd); 
             if cmd.frequency>0 {self.command_list.insert(cmd);}
         }
@@ -145,14 +143,34 @@ impl Database {
 
 }
 
+impl DeletedCommands {
+    pub fn delete(&mut self, command_str: &str) {
+        // The record is kept (and stops counting) so `alman ignored restore` can bring it back
+        self.deleted_commands.insert(command_str.to_string());
+        self.aliased.remove(command_str);
+    }
+
+    // Hidden for having an alias. One that was deleted already stays an explicit deletion.
+    pub fn hide_aliased(&mut self, command_str: &str) {
+        if self.deleted_commands.insert(command_str.to_string()) {
+            self.aliased.insert(command_str.to_string());
+        }
+    }
+
+    // Suggested again once its alias is gone, if that's the only reason it was hidden
+    pub fn unhide_aliased(&mut self, command_str: &str) {
+        if self.aliased.remove(command_str) {
+            self.deleted_commands.remove(command_str);
+        }
+    }
+}
+
 impl Command {
-    pub fn new(command_text: String) -> Self {
+    pub fn new_at(command_text: String, last_access_time: i64) -> Self {
         let length: i16 = command_text.split_whitespace().map(|s| s.len()).sum::<usize>() as i16;
         //map transforms the strings into their lengths
         let number_of_words: i8 = command_text.split_whitespace().count() as i8;
         let frequency: i32 = 1;
-        let now = SystemTime::now();
-        let last_access_time: i64 = now.duration_since(UNIX_EPOCH).unwrap().as_secs() as i64; // or set as needed
 
         // Create a temporary Command to calculate the score
         let temp_command = Command {
@@ -162,6 +180,8 @@ impl Command {
             score: 0, // placeholder
             command_text: command_text.clone(),
             number_of_words,
+            decays: 0,
+            first_access_time: last_access_time,
         };
 
         let score = get_score(&temp_command);
@@ -173,17 +193,105 @@ impl Command {
             score,
             command_text,
             number_of_words,
+            decays: 0,
+            first_access_time: last_access_time,
         }
     }
-    pub fn add(&mut self) {
+    pub fn add_at(&mut self, timestamp: i64) {
         // here we update the last_access time, frequency, and score
-        let now = SystemTime::now();
-        self.last_access_time = now.duration_since(UNIX_EPOCH).unwrap().as_secs() as i64; // placeholder
+        // history can be replayed out of order, so never move last_access_time backwards
+        self.last_access_time = self.last_access_time.max(timestamp);
+        if self.first_access_time > 0 {
+            self.first_access_time = self.first_access_time.min(timestamp);
+        }
         self.frequency += 1;
         self.score = get_score(self);
     }
-    pub fn update (&mut self) {
-        // just update with time and score
-        self.score = get_score(self);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeSet, HashMap};
+
+    // A version 0 database, where every run was also counted under each word prefix
+    fn old_format(records: &[(&str, i32)]) -> Database {
+        let reverse_command_map: HashMap<String, Command> = records
+            .iter()
+            .map(|(text, frequency)| {
+                let command = Command {
+                    score: 0,
+                    last_access_time: now(),
+                    frequency: *frequency,
+                    length: text.len() as i16,
+                    command_text: text.to_string(),
+                    number_of_words: text.split_whitespace().count() as i8,
+                    decays: 0,
+                    first_access_time: 0,
+                };
+                (text.to_string(), command)
+            })
+            .collect();
+        Database {
+            command_list: reverse_command_map.values().cloned().collect::<BTreeSet<_>>(),
+            total_num_commands: records.len() as i32,
+            total_score: 0,
+            reverse_command_map,
+            format_version: 0,
+        }
+    }
+
+    fn frequency(db: &Database, text: &str) -> Option<i32> {
+        db.reverse_command_map.get(text).map(|c| c.frequency)
+    }
+
+    #[test]
+    fn drop_prefix_records_keeps_own_runs() {
+        let mut db = old_format(&[
+            ("git commit", 17),
+            ("git commit -m x", 6),
+            ("git commit --amend", 1),
+            ("git push", 4),
+            ("git push origin", 3),
+            ("git push origin main", 3),
+            // Rounded by a score reset to less than its children add up to
+            ("cargo build", 2),
+            ("cargo build --release", 1),
+            ("cargo build -p a", 1),
+            ("cargo build -p b", 1),
+        ]);
+        db.drop_prefix_records();
+
+        assert_eq!(frequency(&db, "git commit"), Some(10));
+        assert_eq!(frequency(&db, "git commit -m x"), Some(6));
+        assert_eq!(frequency(&db, "git commit --amend"), Some(1));
+        assert_eq!(frequency(&db, "git push"), Some(1));
+        // Its runs were all `git push origin main`
+        assert_eq!(frequency(&db, "git push origin"), None);
+        assert_eq!(frequency(&db, "git push origin main"), Some(3));
+        assert_eq!(frequency(&db, "cargo build"), None);
+        assert_eq!(frequency(&db, "cargo build -p a"), Some(1));
+
+        assert_eq!(db.format_version, DB_FORMAT_VERSION);
+        assert_eq!(db.total_num_commands as usize, db.reverse_command_map.len());
+        assert_eq!(db.command_list.len(), db.reverse_command_map.len());
+    }
+
+    #[test]
+    fn unhiding_an_aliased_command_keeps_explicit_deletions() {
+        let mut deleted = DeletedCommands::default();
+        deleted.delete("git status");
+        deleted.hide_aliased("git status");
+        deleted.hide_aliased("git log");
+        deleted.unhide_aliased("git status");
+        deleted.unhide_aliased("git log");
+        assert!(deleted.deleted_commands.contains("git status"));
+        assert!(!deleted.deleted_commands.contains("git log"));
+
+        // Deleting an aliased command makes it an explicit deletion
+        deleted.hide_aliased("git push");
+        deleted.delete("git push");
+        deleted.unhide_aliased("git push");
+        assert!(deleted.deleted_commands.contains("git push"));
     }
 }
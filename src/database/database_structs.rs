@@ -1,41 +1,163 @@
-use std::collections:: {BTreeSet, HashMap};
-use serde::{Serialize, Deserialize};
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Database {
-    pub command_list: BTreeSet<Command>,
-    pub reverse_command_map: HashMap<String, Command>,
-    pub total_num_commands: i32,
-    pub total_score: i64,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct DeletedCommands {
-    pub deleted_commands: BTreeSet<String>,
-}
-
-#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
-pub struct Command {
-    pub score: i32,
-    pub last_access_time: i64,
-    pub frequency: i32,
-    pub length: i16,
-    pub command_text: String,
-    pub number_of_words: i8,
-}
-
-impl Ord for Command {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        // Sort by score descending
-        match other.score.cmp(&self.score) {
-            std::cmp::Ordering::Equal => self.command_text.cmp(&other.command_text), // tie-breaker
-            ord => ord,
-        }
-    }
-}
-
-impl PartialOrd for Command {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
+use std::collections:: {BTreeMap, BTreeSet, HashMap};
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Database {
+    pub command_list: BTreeSet<Command>,
+    pub reverse_command_map: HashMap<String, Command>,
+    pub total_num_commands: i32,
+    pub total_score: i64,
+    // 0 for databases that stored every word prefix of a command as its own record
+    #[serde(default)]
+    pub format_version: u32,
+}
+
+// Since version 1 only commands that were actually run are stored, see command_tree.rs
+pub const DB_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DeletedCommands {
+    pub deleted_commands: BTreeSet<String>,
+    // The deleted commands that were only hidden because an alias was added for them, so
+    // changing the alias can bring them back. Explicit deletions are never in here.
+    #[serde(default)]
+    pub aliased: BTreeSet<String>,
+    // Patterns no suggestion may match, see ops/ignored.rs
+    #[serde(default)]
+    pub block_rules: Vec<BlockRule>,
+    // Command -> unix time it comes back
+    #[serde(default)]
+    pub snoozed: BTreeMap<String, i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockRule {
+    pub kind: BlockKind,
+    pub pattern: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlockKind {
+    Glob,
+    Regex,
+}
+
+// Per-file read position for history files tailed by `alman sync-history`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct HistorySyncState {
+    pub files: BTreeMap<String, HistoryFileState>,
+    // Per history database, the newest row already imported by `alman import`
+    #[serde(default)]
+    pub imports: BTreeMap<String, ImportCursor>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ImportCursor {
+    pub last_timestamp: i64,
+    pub last_id: String,
+    // When alman started recording on its own, taken from the database at the first import.
+    // Rows from then on were already counted by the shell hook or a history file sync.
+    #[serde(default)]
+    pub recorded_from: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct HistoryFileState {
+    pub inode: u64,
+    pub offset: u64,
+    // Last line read before `offset`, used to find our place again after the shell rewrites the file
+    pub last_line: String,
+}
+
+// Command ordering per shell session, used to find sequences worth a combo alias
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SequenceStats {
+    pub sessions: BTreeMap<String, Vec<SequenceStep>>,
+    // "git add -A && git commit && git push" -> how often it was run in that order
+    pub ngrams: HashMap<String, NgramStats>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SequenceStep {
+    pub timestamp: i64,
+    pub command: String,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct NgramStats {
+    pub count: i32,
+    pub last_seen: i64,
+}
+
+// One entry per local calendar day, kept small so years of history stay cheap to load
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UsageHistory {
+    pub days: BTreeMap<String, DayUsage>, // keyed by YYYY-MM-DD
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct DayUsage {
+    pub commands: u32,
+    pub tools: HashMap<String, u32>,
+    pub hours: [u32; 24],
+    // Command lines run for the first time
+    pub new_commands: u32,
+    // Lines that started with one of the user's aliases
+    pub alias_runs: u32,
+    // Lines typed out in full although an alias for them exists
+    pub full_runs_with_alias: u32,
+    // Characters not typed thanks to aliases
+    #[serde(default)]
+    pub keystrokes_saved: u64,
+    // Runs of each alias, by name
+    #[serde(default)]
+    pub aliases: HashMap<String, u32>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Command {
+    pub score: i32,
+    pub last_access_time: i64,
+    pub frequency: i32,
+    pub length: i16,
+    pub command_text: String,
+    pub number_of_words: i8,
+    // How many times score_reset has halved the frequency
+    #[serde(default)]
+    pub decays: u32,
+    // 0 for records from before this was tracked
+    #[serde(default)]
+    pub first_access_time: i64,
+}
+
+impl Ord for Command {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Sort by score descending
+        match other.score.cmp(&self.score) {
+            std::cmp::Ordering::Equal => self.command_text.cmp(&other.command_text), // tie-breaker
+            ord => ord,
+        }
+    }
+}
+
+impl PartialOrd for Command {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Names that are already taken on this machine, rebuilt when anything it was read from changes
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CommandIndex {
+    pub path: String,
+    pub shells: Vec<String>,
+    // Modification time in nanoseconds of every PATH directory and rc file read, 0 if missing
+    pub sources: BTreeMap<String, i64>,
+    pub executables: BTreeSet<String>,
+    // Alias name -> rc file it is defined in
+    pub shell_aliases: BTreeMap<String, String>,
+    // Function name -> rc file it is defined in
+    pub functions: BTreeMap<String, String>,
+    pub git_aliases: BTreeSet<String>,
+}
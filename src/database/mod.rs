@@ -1,4 +1,5 @@
-pub mod database_structs;
-pub mod database_ops;
-pub mod scoring;
+pub mod database_structs;
+pub mod database_ops;
+pub mod command_tree;
+pub mod scoring;
 pub mod persistence;
\ No newline at end of file
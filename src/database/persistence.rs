@@ -1,15 +1,31 @@
 use std::fs;
 use std::path::Path;
 use serde_json;
-use super::database_structs::{Database, DeletedCommands};
+use super::database_structs::{CommandIndex, Database, DB_FORMAT_VERSION, DeletedCommands, HistorySyncState, SequenceStats, UsageHistory};
+use super::scoring::ScoringConfig;
+use crate::ops::suggestion_templates::SuggestionTemplate;
 
 pub const DB_FILE: &str = "command_database.json";
 pub const DELETED_COMMANDS_FILE: &str = "deleted_commands.json";
 pub const CONFIG_FILE: &str = "config.json";
+pub const HISTORY_SYNC_FILE: &str = "history_sync.json";
+pub const SEQUENCES_FILE: &str = "sequences.json";
+pub const USAGE_HISTORY_FILE: &str = "usage_history.json";
+pub const COMMAND_INDEX_FILE: &str = "command_index.json";
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct AppConfig {
     pub alias_file_paths: Vec<String>,
+    // History files that get tailed every time alman runs (for shells without a preexec hook)
+    #[serde(default)]
+    pub history_file_paths: Vec<String>,
+    #[serde(default)]
+    pub scoring: ScoringConfig,
+    #[serde(default)]
+    pub suggestion_templates: Vec<SuggestionTemplate>,
+    // Shells the alias files are sourced by (bash, zsh, fish, sh), $SHELL when empty
+    #[serde(default)]
+    pub target_shells: Vec<String>,
 }
 
 pub fn save_config(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>> {
@@ -47,11 +63,15 @@ pub fn load_database(file_path: &str) -> Result<Database, Box<dyn std::error::Er
             reverse_command_map: std::collections::HashMap::new(),
             total_num_commands: 0,
             total_score: 0,
+            format_version: DB_FORMAT_VERSION,
         });
     }
     
     let content = fs::read_to_string(file_path)?;
-    let db: Database = serde_json::from_str(&content)?;
+    let mut db: Database = serde_json::from_str(&content)?;
+    if db.format_version < DB_FORMAT_VERSION {
+        db.drop_prefix_records();
+    }
     Ok(db)
 }
 
@@ -64,9 +84,7 @@ pub fn save_deleted_commands(deleted_commands: &DeletedCommands, file_path: &str
 pub fn load_deleted_commands(file_path: &str) -> Result<DeletedCommands, Box<dyn std::error::Error>> {
     if !Path::new(file_path).exists() {
         // Return empty deleted commands if file doesn't exist
-        return Ok(DeletedCommands {
-            deleted_commands: std::collections::BTreeSet::new(),
-        });
+        return Ok(DeletedCommands::default());
     }
     
     let content = fs::read_to_string(file_path)?;
@@ -74,6 +92,55 @@ pub fn load_deleted_commands(file_path: &str) -> Result<DeletedCommands, Box<dyn
     Ok(deleted_commands)
 }
 
+pub fn save_history_sync_state(state: &HistorySyncState, file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string_pretty(state)?;
+    fs::write(file_path, json)?;
+    Ok(())
+}
+
+pub fn load_history_sync_state(file_path: &str) -> Result<HistorySyncState, Box<dyn std::error::Error>> {
+    if !Path::new(file_path).exists() {
+        // Nothing synced yet
+        return Ok(HistorySyncState::default());
+    }
+
+    let content = fs::read_to_string(file_path)?;
+    let state: HistorySyncState = serde_json::from_str(&content)?;
+    Ok(state)
+}
+
+pub fn save_sequence_stats(stats: &SequenceStats, file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string(stats)?;
+    fs::write(file_path, json)?;
+    Ok(())
+}
+
+pub fn load_sequence_stats(file_path: &str) -> Result<SequenceStats, Box<dyn std::error::Error>> {
+    if !Path::new(file_path).exists() {
+        return Ok(SequenceStats::default());
+    }
+
+    let content = fs::read_to_string(file_path)?;
+    let stats: SequenceStats = serde_json::from_str(&content)?;
+    Ok(stats)
+}
+
+pub fn save_usage_history(history: &UsageHistory, file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string(history)?;
+    fs::write(file_path, json)?;
+    Ok(())
+}
+
+pub fn load_usage_history(file_path: &str) -> Result<UsageHistory, Box<dyn std::error::Error>> {
+    if !Path::new(file_path).exists() {
+        return Ok(UsageHistory::default());
+    }
+
+    let content = fs::read_to_string(file_path)?;
+    let history: UsageHistory = serde_json::from_str(&content)?;
+    Ok(history)
+}
+
 pub fn get_database_path() -> String {
     let home_dir = dirs::home_dir().unwrap_or_else(|| std::path::PathBuf::from("."));
     home_dir.join(".alman").join(DB_FILE).to_string_lossy().to_string()
@@ -84,6 +151,48 @@ pub fn get_deleted_commands_path() -> String {
     home_dir.join(".alman").join(DELETED_COMMANDS_FILE).to_string_lossy().to_string()
 }
 
+pub fn get_history_sync_path() -> String {
+    let home_dir = dirs::home_dir().unwrap_or_else(|| std::path::PathBuf::from("."));
+    home_dir.join(".alman").join(HISTORY_SYNC_FILE).to_string_lossy().to_string()
+}
+
+pub fn get_sequences_path() -> String {
+    let home_dir = dirs::home_dir().unwrap_or_else(|| std::path::PathBuf::from("."));
+    home_dir.join(".alman").join(SEQUENCES_FILE).to_string_lossy().to_string()
+}
+
+pub fn get_usage_history_path() -> String {
+    let home_dir = dirs::home_dir().unwrap_or_else(|| std::path::PathBuf::from("."));
+    home_dir.join(".alman").join(USAGE_HISTORY_FILE).to_string_lossy().to_string()
+}
+
+pub fn save_command_index(index: &CommandIndex, file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string(index)?;
+    fs::write(file_path, json)?;
+    Ok(())
+}
+
+pub fn load_command_index(file_path: &str) -> Result<CommandIndex, Box<dyn std::error::Error>> {
+    if !Path::new(file_path).exists() {
+        return Ok(CommandIndex::default());
+    }
+
+    let content = fs::read_to_string(file_path)?;
+    let index: CommandIndex = serde_json::from_str(&content)?;
+    Ok(index)
+}
+
+pub fn get_command_index_path() -> String {
+    let home_dir = dirs::home_dir().unwrap_or_else(|| std::path::PathBuf::from("."));
+    home_dir.join(".alman").join(COMMAND_INDEX_FILE).to_string_lossy().to_string()
+}
+
+// User tool rule packs that extend or replace the bundled ones
+pub fn get_rules_dir() -> String {
+    let home_dir = dirs::home_dir().unwrap_or_else(|| std::path::PathBuf::from("."));
+    home_dir.join(".alman").join("rules").to_string_lossy().to_string()
+}
+
 pub fn ensure_data_directory() -> Result<(), Box<dyn std::error::Error>> {
     let home_dir = dirs::home_dir().unwrap_or_else(|| std::path::PathBuf::from("."));
     let data_dir = home_dir.join(".alman");
@@ -0,0 +1,206 @@
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+use super::database_structs::Command;
+
+// Scores decide which commands are worth an alias. They are computed when the database is
+// queried, so a command nobody ran for a month sinks without anything being rewritten.
+pub trait Scorer {
+    fn name(&self) -> &'static str;
+    fn score(&self, command: &Command, now: i64) -> i32;
+    // The factors that went into `score`, for `alman explain`
+    fn explain(&self, command: &Command, now: i64) -> Vec<ScoreComponent>;
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoreComponent {
+    pub label: String,
+    pub value: String,
+}
+
+impl ScoreComponent {
+    fn new(label: &str, value: String) -> Self {
+        Self { label: label.to_string(), value }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScoringMethod {
+    Bucket,
+    Decay,
+    Keystrokes,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoringConfig {
+    #[serde(default = "default_method")]
+    pub method: ScoringMethod,
+    // Used by the decay and keystrokes scorers
+    #[serde(default = "default_half_life_hours")]
+    pub half_life_hours: f64,
+}
+
+fn default_method() -> ScoringMethod {
+    ScoringMethod::Bucket
+}
+
+fn default_half_life_hours() -> f64 {
+    72.0
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            method: default_method(),
+            half_life_hours: default_half_life_hours(),
+        }
+    }
+}
+
+// The original scorer: four recency buckets times length^0.6 * frequency
+pub struct BucketScorer;
+
+impl BucketScorer {
+    fn bucket(command: &Command, now: i64) -> (f64, &'static str) {
+        let time_difference = now - command.last_access_time;
+        if time_difference <= 3600 {
+            (4.0, "used in the last hour")
+        } else if time_difference <= 86400 {
+            (2.0, "used in the last day")
+        } else if time_difference <= 604800 {
+            (0.5, "used in the last week")
+        } else {
+            (0.25, "not used for over a week")
+        }
+    }
+}
+
+impl Scorer for BucketScorer {
+    fn name(&self) -> &'static str {
+        "bucket"
+    }
+
+    fn score(&self, command: &Command, now: i64) -> i32 {
+        let (mult, _) = Self::bucket(command, now);
+        (mult * length_factor(command) * command.frequency as f64) as i32
+    }
+
+    fn explain(&self, command: &Command, now: i64) -> Vec<ScoreComponent> {
+        let (mult, bucket) = Self::bucket(command, now);
+        vec![
+            ScoreComponent::new("Recency multiplier", format!("x{} ({})", mult, bucket)),
+            ScoreComponent::new("Length factor", format!("{}^0.6 = {:.2}", command.length, length_factor(command))),
+            ScoreComponent::new("Frequency", command.frequency.to_string()),
+            ScoreComponent::new(
+                "Score",
+                format!("{} x {:.2} x {} = {}", mult, length_factor(command), command.frequency, self.score(command, now)),
+            ),
+        ]
+    }
+}
+
+// Like the bucket scorer but the recency weight halves smoothly every `half_life_secs`,
+// so scores don't jump when a command crosses a bucket edge
+pub struct DecayScorer {
+    pub half_life_secs: f64,
+}
+
+impl Scorer for DecayScorer {
+    fn name(&self) -> &'static str {
+        "decay"
+    }
+
+    fn score(&self, command: &Command, now: i64) -> i32 {
+        // Same top weight as the bucket scorer so both produce numbers of the same size
+        (4.0 * decay_weight(command, now, self.half_life_secs) * length_factor(command) * command.frequency as f64) as i32
+    }
+
+    fn explain(&self, command: &Command, now: i64) -> Vec<ScoreComponent> {
+        let weight = decay_weight(command, now, self.half_life_secs);
+        vec![
+            ScoreComponent::new("Recency multiplier", format!("4 x {:.3} ({})", weight, half_life_text(command, now, self.half_life_secs))),
+            ScoreComponent::new("Length factor", format!("{}^0.6 = {:.2}", command.length, length_factor(command))),
+            ScoreComponent::new("Frequency", command.frequency.to_string()),
+            ScoreComponent::new(
+                "Score",
+                format!("{:.2} x {:.2} x {} = {}", 4.0 * weight, length_factor(command), command.frequency, self.score(command, now)),
+            ),
+        ]
+    }
+}
+
+// Ranks by how many characters an alias would save: what is typed on each run minus a
+// typical alias, times how often it is run, with the same smooth recency decay
+pub struct KeystrokesScorer {
+    pub half_life_secs: f64,
+}
+
+// Most suggested aliases are two to four characters
+const TYPICAL_ALIAS_LENGTH: i64 = 3;
+
+impl Scorer for KeystrokesScorer {
+    fn name(&self) -> &'static str {
+        "keystrokes"
+    }
+
+    fn score(&self, command: &Command, now: i64) -> i32 {
+        let saved_per_run = (typed_length(command) - TYPICAL_ALIAS_LENGTH).max(0) as f64;
+        let frequency = command.frequency as f64;
+        (saved_per_run * frequency * decay_weight(command, now, self.half_life_secs)) as i32
+    }
+
+    fn explain(&self, command: &Command, now: i64) -> Vec<ScoreComponent> {
+        let saved_per_run = (typed_length(command) - TYPICAL_ALIAS_LENGTH).max(0);
+        let weight = decay_weight(command, now, self.half_life_secs);
+        vec![
+            ScoreComponent::new(
+                "Keystrokes saved per run",
+                format!("{} typed - {} for an alias = {}", typed_length(command), TYPICAL_ALIAS_LENGTH, saved_per_run),
+            ),
+            ScoreComponent::new("Frequency", command.frequency.to_string()),
+            ScoreComponent::new("Recency multiplier", format!("{:.3} ({})", weight, half_life_text(command, now, self.half_life_secs))),
+            ScoreComponent::new(
+                "Score",
+                format!("{} x {} x {:.3} = {}", saved_per_run, command.frequency, weight, self.score(command, now)),
+            ),
+        ]
+    }
+}
+
+// Characters typed to run the command, spaces included
+pub fn typed_length(command: &Command) -> i64 {
+    command.length as i64 + (command.number_of_words as i64 - 1).max(0)
+}
+
+fn length_factor(command: &Command) -> f64 {
+    (command.length as f64).powf(3.0 / 5.0)
+}
+
+fn half_life_text(command: &Command, now: i64, half_life_secs: f64) -> String {
+    let age_hours = (now - command.last_access_time).max(0) as f64 / 3600.0;
+    format!("last used {:.1}h ago, half-life {:.0}h", age_hours, half_life_secs / 3600.0)
+}
+
+fn decay_weight(command: &Command, now: i64, half_life_secs: f64) -> f64 {
+    let age = (now - command.last_access_time).max(0) as f64;
+    0.5f64.powf(age / half_life_secs.max(1.0))
+}
+
+pub fn scorer_for(config: &ScoringConfig) -> Box<dyn Scorer> {
+    let half_life_secs = config.half_life_hours * 3600.0;
+    match config.method {
+        ScoringMethod::Bucket => Box::new(BucketScorer),
+        ScoringMethod::Decay => Box::new(DecayScorer { half_life_secs }),
+        ScoringMethod::Keystrokes => Box::new(KeystrokesScorer { half_life_secs }),
+    }
+}
+
+// The scorer picked in ~/.alman/config.json, the bucket scorer if none is set
+pub fn configured_scorer() -> Box<dyn Scorer> {
+    let config = super::persistence::load_config().map(|config| config.scoring).unwrap_or_default();
+    scorer_for(&config)
+}
+
+pub fn now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64
+}
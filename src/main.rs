@@ -5,17 +5,19 @@ mod tui;
 mod shell;
 
 use cli::arg_handler::parse_args;
-use cli::cli_data::Operation;
+use cli::cli_data::{Operation, OutputFormat};
+use cli::output;
 use database::database_structs::{Database, DeletedCommands};
 use database::persistence::{
     ensure_data_directory, get_database_path, get_deleted_commands_path, load_database,
-    load_deleted_commands, save_database, save_deleted_commands, load_config, save_config, AppConfig
+    load_deleted_commands, save_database, save_deleted_commands, load_config, save_config
 };
 use ops::add_alias::add_alias;
 use ops::delete_suggestion::delete_suggestion;
 use ops::get_suggestions;
 use ops::insert_command::insert_command;
 use ops::remove_alias::remove_alias;
+use ops::history_sync::{default_history_file, sync_history_files};
 use shell::{ShellOpts, render_shell_init};
 use std::env;
 use tui::run_tui;
@@ -25,6 +27,7 @@ use std::path::PathBuf;
 use std::fs;
 use std::path::Path;
 use std::os::unix::fs::PermissionsExt;
+use std::io::IsTerminal;
 
 fn to_absolute_path(path: &str) -> String {
     let pb = PathBuf::from(path);
@@ -64,7 +67,7 @@ fn main() {
         // Load config and get default alias file path
         let default_path = {
             if let Some(cfg) = crate::database::persistence::load_config() {
-                cfg.alias_file_paths.first().map(|p| to_absolute_path(p)).unwrap_or_else(|| crate::database::persistence::get_default_alias_file_path())
+                cfg.alias_file_paths.first().map(|p| to_absolute_path(p)).unwrap_or_else(crate::database::persistence::get_default_alias_file_path)
             } else {
                 crate::database::persistence::get_default_alias_file_path()
             }
@@ -110,8 +113,8 @@ fn main() {
 
     // If only --alias-file-path is provided (no subcommand), update config and exit
     if let Some(cli) = &cli {
-        if cli.operation.is_none() && cli.alias_file_path.is_some() {
-            let cli_path_str = to_absolute_path(&cli.alias_file_path.as_ref().unwrap().to_string_lossy());
+        if let (None, Some(cli_path)) = (&cli.operation, &cli.alias_file_path) {
+            let cli_path_str = to_absolute_path(&cli_path.to_string_lossy());
             if !alias_file_paths.contains(&cli_path_str) {
                 alias_file_paths.push(cli_path_str.clone());
             }
@@ -120,7 +123,8 @@ fn main() {
                 let new_default = alias_file_paths.remove(pos);
                 alias_file_paths.insert(0, new_default);
             }
-            let new_config = AppConfig { alias_file_paths: alias_file_paths.clone() };
+            let mut new_config = load_config().unwrap_or_default();
+            new_config.alias_file_paths = alias_file_paths.clone();
             let _ = save_config(&new_config);
             println!("Default alias file path set to {}", cli_path_str.green());
             return;
@@ -133,7 +137,8 @@ fn main() {
             let cli_path_str = to_absolute_path(&cli_path.to_string_lossy());
             if !alias_file_paths.contains(&cli_path_str) {
                 alias_file_paths.push(cli_path_str.clone());
-                let new_config = AppConfig { alias_file_paths: alias_file_paths.clone() };
+                let mut new_config = load_config().unwrap_or_default();
+                new_config.alias_file_paths = alias_file_paths.clone();
                 let _ = save_config(&new_config);
             }
         }
@@ -152,6 +157,7 @@ fn main() {
                 reverse_command_map: std::collections::HashMap::new(),
                 total_num_commands: 0,
                 total_score: 0,
+                format_version: database::database_structs::DB_FORMAT_VERSION,
             }
         }
     };
@@ -160,9 +166,7 @@ fn main() {
         Ok(dc) => dc,
         Err(e) => {
             eprintln!("{}", format!("Failed to load deleted commands: {}", e).red());
-            DeletedCommands {
-                deleted_commands: std::collections::BTreeSet::new(),
-            }
+            DeletedCommands::default()
         }
     };
 
@@ -179,7 +183,21 @@ fn main() {
         }
         
         let command = command_strings[2..].join(" ");
-        insert_command(command.to_string(), db_ref, dc_ref);
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64;
+        let mut usage = ops::usage_stats::UsageRecorder::load(&alias_file_paths);
+        usage.record(&command, now, db_ref, dc_ref);
+        usage.save();
+        insert_command(command.to_string(), db_ref);
+
+        // Keep track of what this shell ran before, for combo suggestions
+        use ops::command_sequences::{current_session_id, record_sequence};
+        use database::persistence::{get_sequences_path, load_sequence_stats, save_sequence_stats};
+        let sequences_path = get_sequences_path();
+        let mut sequences = load_sequence_stats(&sequences_path).unwrap_or_default();
+        record_sequence(&current_session_id(), &command, now, &mut sequences, db_ref);
+        if let Err(e) = save_sequence_stats(&sequences, &sequences_path) {
+            eprintln!("Failed to save command sequences: {}", e);
+        }
 
         // Save database after inserting command
         if let Err(e) = save_database(db_ref, &db_path) {
@@ -192,9 +210,31 @@ fn main() {
         // This is a subcommand, parse and handle it
         let cli = parse_args();
 
+        // Colours are for people reading a terminal, never for pipes or the script formats
+        let script_format = cli.operation.as_ref().and_then(Operation::output_format).is_some_and(|f| f != OutputFormat::Table);
+        if script_format || !std::io::stdout().is_terminal() {
+            colored::control::set_override(false);
+        }
+
+        // Pick up anything new in tracked history files before doing anything else
+        let tracked_history_files = config.as_ref().map(|cfg| cfg.history_file_paths.clone()).unwrap_or_default();
+        if !tracked_history_files.is_empty() {
+            let synced = sync_history_files(&tracked_history_files, db_ref, dc_ref);
+            if synced.count == 0 {
+                synced.save();
+            } else if let Err(e) = save_database(db_ref, &db_path) {
+                eprintln!("{}", format!("Failed to save database: {}", e).red());
+            } else {
+                synced.save();
+            }
+        }
+
         match &cli.operation {
             Some(Operation::Add { alias, command }) => {
                 use ops::alias_ops::add_alias_to_multiple_files;
+                if let Some(conflict) = ops::shell_conflicts::ConflictChecker::load().conflict(alias) {
+                    eprintln!("{}", format!("Warning: '{}' {}", alias, conflict).yellow());
+                }
                 add_alias_to_multiple_files(&alias_file_paths, alias, command);
                 if let Some(first_path) = alias_file_paths.first() {
                     add_alias(db_ref, dc_ref, first_path, alias, command);
@@ -216,8 +256,31 @@ fn main() {
                     eprintln!("{}", format!("Failed to save deleted commands: {}", e).red());
                 }
             }
-            Some(Operation::List) => {
+            Some(Operation::Move { to, aliases }) => {
+                use ops::alias_ops::move_alias_to_file;
+                let to = to_absolute_path(&to.to_string_lossy());
+                if !alias_file_paths.contains(&to) {
+                    eprintln!("{}", format!("'{}' is not a tracked alias file, tracked files are: {}", to, alias_file_paths.join(", ")).red());
+                    return;
+                }
+                for alias in aliases {
+                    match move_alias_to_file(&alias_file_paths, alias, &to) {
+                        Some(from) if from == to => println!("{} is already in {}", alias.cyan(), to),
+                        Some(from) => println!("Moved {} from {} to {}", alias.cyan(), from, to),
+                        None => eprintln!("{}", format!("No alias named '{}'", alias).yellow()),
+                    }
+                }
+            }
+            Some(Operation::List { format }) => {
                 use ops::alias_ops::get_aliases_from_multiple_files;
+                if *format != OutputFormat::Table {
+                    let records: Vec<output::AliasRecord> = ops::alias_ops::get_aliases_with_files(&alias_file_paths)
+                        .into_iter()
+                        .map(|(alias, command, file)| output::AliasRecord { alias, command, file })
+                        .collect();
+                    output::print_aliases(*format, &records);
+                    return;
+                }
                 let aliases = get_aliases_from_multiple_files(&alias_file_paths);
                 if aliases.is_empty() {
                     println!("{}", "No aliases found.".yellow());
@@ -230,27 +293,53 @@ fn main() {
                 println!("{}", format!("│ {:<alias$} │ {:<cmd$} │", "ALIAS", "COMMAND", alias = max_alias_length, cmd = max_command_length).cyan());
                 println!("{}", format!("├{:─<alias$}┼{:─<cmd$}┤", "", "", alias = max_alias_length + 2, cmd = max_command_length + 2).cyan());
                 for (alias, command) in &aliases {
-                    println!("│ {} │ {} │",
+                    println!("│ {} │ {:<width$} │",
                         format!("{:<width$}", alias, width = max_alias_length).cyan(),
-                        format!("{:<width$}", command, width = max_command_length)
+                        command,
+                        width = max_command_length
                     );
                 }
                 println!("{}", format!("└{:─<alias$}┴{:─<cmd$}┘", "", "", alias = max_alias_length + 2, cmd = max_command_length + 2).cyan());
                 println!("{}", format!("Total: {} alias(es) across {} file(s)", aliases.len(), alias_file_paths.len()).green());
             }
-            Some(Operation::Change { old_alias, new_alias, command }) => {
-                use ops::alias_ops::remove_alias_from_multiple_files;
-                use ops::alias_ops::add_alias_to_multiple_files;
-                // First remove the old alias
-                remove_alias_from_multiple_files(&alias_file_paths, old_alias);
-                if let Some(first_path) = alias_file_paths.first() {
-                    remove_alias(dc_ref, first_path, old_alias);
+            Some(Operation::Change { old_alias, new_alias, new_command, command, edit }) => {
+                use ops::change_alias::{change_alias, edit_history, find_alias};
+                use tui::line_editor::{read_line, LineEditor};
+                let mut command = command.clone().or_else(|| new_command.clone());
+                if *edit || (new_alias.is_none() && command.is_none()) {
+                    let Some(definition) = find_alias(&alias_file_paths, old_alias) else {
+                        eprintln!("{}", format!("No alias named '{}'", old_alias).red());
+                        return;
+                    };
+                    if !std::io::stdin().is_terminal() {
+                        eprintln!("{}", "Give the new command with -c, there's no terminal to edit it in.".red());
+                        return;
+                    }
+                    let mut editor = LineEditor::new(&definition.command, edit_history(db_ref.reverse_command_map.values(), &definition.command));
+                    println!("{}", "Enter to save, Esc to cancel, ↑/↓ for recorded commands".dimmed());
+                    match read_line(&format!("{} = ", old_alias), &mut editor) {
+                        Ok(Some(edited)) => command = Some(edited),
+                        Ok(None) => {
+                            println!("Cancelled.");
+                            return;
+                        }
+                        Err(e) => {
+                            eprintln!("{}", format!("Failed to read the command: {}", e).red());
+                            return;
+                        }
+                    }
                 }
-                // Then add the new alias with the provided command
-                add_alias_to_multiple_files(&alias_file_paths, new_alias, command);
-                if let Some(first_path) = alias_file_paths.first() {
-                    add_alias(db_ref, dc_ref, first_path, new_alias, command);
+                let outcome = match change_alias(db_ref, dc_ref, &alias_file_paths, old_alias, new_alias.as_deref(), command.as_deref()) {
+                    Ok(outcome) => outcome,
+                    Err(e) => {
+                        eprintln!("{}", e.to_string().red());
+                        return;
+                    }
+                };
+                if let Some(warning) = &outcome.warning {
+                    eprintln!("{}", format!("Warning: {}", warning).yellow());
                 }
+                println!("Changed {} = {} to {} = {} in {}", outcome.old_alias.cyan(), outcome.old_command, outcome.alias.cyan(), outcome.command, outcome.file);
                 if let Err(e) = save_database(db_ref, &db_path) {
                     eprintln!("{}", format!("Failed to save database: {}", e).red());
                 }
@@ -258,9 +347,10 @@ fn main() {
                     eprintln!("{}", format!("Failed to save deleted commands: {}", e).red());
                 }
             }
-            Some(Operation::GetSuggestions { num }) => {
-                // Get total number of commands in the database
-                let total_commands = db_ref.command_list.len();
+            Some(Operation::GetSuggestions { num, format }) => {
+                // Get total number of commands that could be suggested
+                let scorer = crate::database::scoring::configured_scorer();
+                let total_commands = db_ref.get_top_commands(Some(usize::MAX), scorer.as_ref(), dc_ref).len();
                 if let Some(n) = num {
                     if *n == 0 {
                         eprintln!("{}", "Number of suggestions must be greater than 0.".red());
@@ -271,8 +361,12 @@ fn main() {
                         return;
                     }
                 }
-                let list = get_suggestions::get_suggestions_with_aliases(*num, db_ref, alias_file_paths.first().unwrap_or(&crate::database::persistence::get_default_alias_file_path()));
-                
+                let list = get_suggestions::get_suggestions_with_aliases(*num, db_ref, dc_ref, alias_file_paths.first().unwrap_or(&crate::database::persistence::get_default_alias_file_path()));
+                if *format != OutputFormat::Table {
+                    output::print_suggestions(*format, &list);
+                    return;
+                }
+
                 if list.is_empty() {
                     println!("{}", "No suggestions found.".yellow());
                     return;
@@ -288,16 +382,18 @@ fn main() {
                 let max_command_length = filtered.iter().map(|(cmd, _)| cmd.command.command_text.len()).max().unwrap_or(7).max(7); // at least 'COMMAND'
                 let max_alias_length = filtered.iter().map(|(_, alias_opt)| alias_opt.map(|a| a.alias.len()).unwrap_or(0)).max().unwrap_or(9).max(9); // at least 'TOP ALIAS'
                 let max_score_length = filtered.iter().map(|(cmd, _)| cmd.command.score.to_string().len()).max().unwrap_or(5).max(5); // at least 'SCORE'
+                let format_savings = |savings: f64| format!("{:.0}", savings);
+                let max_savings_length = filtered.iter().map(|(cmd, _)| format_savings(cmd.daily_savings).len()).max().unwrap_or(9).max(9); // at least 'SAVED/DAY'
 
                 // Table width: borders + padding + columns
-                let _total_width = 3 + max_command_length + 3 + max_alias_length + 3 + max_score_length + 2; // | command | alias | score |
+                let _total_width = 3 + max_command_length + 3 + max_alias_length + 3 + max_score_length + 3 + max_savings_length + 2; // | command | alias | score | saved |
 
                 // Top border
-                println!("{}", format!("┌{:─<cmd$}┬{:─<alias$}┬{:─<score$}┐", "", "", "", cmd = max_command_length + 2, alias = max_alias_length + 2, score = max_score_length + 2).cyan());
+                println!("{}", format!("┌{:─<cmd$}┬{:─<alias$}┬{:─<score$}┬{:─<saved$}┐", "", "", "", "", cmd = max_command_length + 2, alias = max_alias_length + 2, score = max_score_length + 2, saved = max_savings_length + 2).cyan());
                 // Header
-                println!("{}", format!("│ {:<cmd$} │ {:>alias$} │ {:>score$} │", "COMMAND", "TOP ALIAS", "SCORE", cmd = max_command_length, alias = max_alias_length, score = max_score_length).cyan());
+                println!("{}", format!("│ {:<cmd$} │ {:>alias$} │ {:>score$} │ {:>saved$} │", "COMMAND", "TOP ALIAS", "SCORE", "SAVED/DAY", cmd = max_command_length, alias = max_alias_length, score = max_score_length, saved = max_savings_length).cyan());
                 // Separator
-                println!("{}", format!("├{:─<cmd$}┼{:─<alias$}┼{:─<score$}┤", "", "", "", cmd = max_command_length + 2, alias = max_alias_length + 2, score = max_score_length + 2).cyan());
+                println!("{}", format!("├{:─<cmd$}┼{:─<alias$}┼{:─<score$}┼{:─<saved$}┤", "", "", "", "", cmd = max_command_length + 2, alias = max_alias_length + 2, score = max_score_length + 2, saved = max_savings_length + 2).cyan());
 
                 // Rows
                 for (cmd_with_alias, top_alias_opt) in &filtered {
@@ -308,26 +404,178 @@ fn main() {
                         format!("{:>width$}", "", width = max_alias_length)
                     };
                     let score_text = format!("{:>width$}", cmd_with_alias.command.score, width = max_score_length);
-                    println!("│ {} │ {} │ {} │",
+                    let savings_text = format!("{:>width$}", format_savings(cmd_with_alias.daily_savings), width = max_savings_length);
+                    println!("│ {} │ {} │ {} │ {} │",
                         command_text.bold(),
                         alias_text.cyan(),
-                        score_text.yellow()
+                        score_text.yellow(),
+                        savings_text.green()
                     );
                 }
 
                 // Bottom border
-                println!("{}", format!("└{:─<cmd$}┴{:─<alias$}┴{:─<score$}┘", "", "", "", cmd = max_command_length + 2, alias = max_alias_length + 2, score = max_score_length + 2).cyan());
-                println!("{}", format!("Total: {} suggestion(s)", filtered.len()).green());
+                println!("{}", format!("└{:─<cmd$}┴{:─<alias$}┴{:─<score$}┴{:─<saved$}┘", "", "", "", "", cmd = max_command_length + 2, alias = max_alias_length + 2, score = max_score_length + 2, saved = max_savings_length + 2).cyan());
+                println!("{}", format!("Total: {} suggestion(s), ranked by estimated keystrokes saved per day", filtered.len()).green());
             }
-            Some(Operation::DeleteSuggestion { alias }) => {
-                delete_suggestion(alias, db_ref, dc_ref);
-                println!("{}", format!("Deleted suggestions for: {}", alias).yellow());
+            Some(Operation::Accept { num, filter, dry_run, interactive }) => {
+                use ops::accept::{confirm, pick};
+                use ops::alias_ops::{alias_line, get_aliases_from_multiple_files};
+                let alias_file_path = alias_file_paths.first().cloned().unwrap_or_else(crate::database::persistence::get_default_alias_file_path);
+                let list = get_suggestions::get_matching_suggestions(Some(*num), filter.as_deref(), db_ref, dc_ref, &alias_file_path);
+                let existing_aliases = get_aliases_from_multiple_files(&alias_file_paths);
+                let mut picks = pick(&list, &existing_aliases, *num);
+                if picks.is_empty() {
+                    println!("{}", "No suggestions to accept.".yellow());
+                    return;
+                }
+                if *interactive {
+                    picks = confirm(picks, &existing_aliases);
+                    if picks.is_empty() {
+                        println!("{}", "Nothing accepted.".yellow());
+                        return;
+                    }
+                }
+
+                if *dry_run {
+                    println!("{}", format!("Would add {} alias(es) to {}:", picks.len(), alias_file_path).cyan());
+                    for p in &picks {
+                        print!("+ {}", alias_line(&p.alias, &p.command));
+                    }
+                    return;
+                }
+                for p in &picks {
+                    add_alias(db_ref, dc_ref, &alias_file_path, &p.alias, &p.command);
+                }
                 if let Err(e) = save_database(db_ref, &db_path) {
                     eprintln!("{}", format!("Failed to save database: {}", e).red());
                 }
                 if let Err(e) = save_deleted_commands(dc_ref, &deleted_commands_path) {
                     eprintln!("{}", format!("Failed to save deleted commands: {}", e).red());
                 }
+                println!("{}", format!("Added {} alias(es) to {}:", picks.len(), alias_file_path).green());
+                let alias_width = picks.iter().map(|p| p.alias.len()).max().unwrap_or(0);
+                for p in &picks {
+                    println!("  {} = {}", format!("{:<width$}", p.alias, width = alias_width).cyan(), p.command);
+                }
+                println!("Open a new shell or run `source {}` to use them.", alias_file_path);
+            }
+            Some(Operation::Search { query, format }) => {
+                use ops::alias_lookup::search;
+                let definitions = ops::alias_ops::get_alias_definitions(&alias_file_paths);
+                let hits = search(&definitions, &query.join(" "));
+                output::print_search_hits(*format, &hits);
+            }
+            Some(Operation::Which { alias, format }) => {
+                use ops::alias_lookup::which;
+                let definitions = ops::alias_ops::get_alias_definitions(&alias_file_paths);
+                let report = which(&definitions, alias);
+                output::print_which_as(*format, &report);
+                if report.definitions.is_empty() {
+                    std::process::exit(1);
+                }
+            }
+            Some(Operation::Expand { command }) => {
+                use ops::alias_lookup::{alias_map, expand};
+                let definitions = ops::alias_ops::get_alias_definitions(&alias_file_paths);
+                println!("{}", expand(&command.join(" "), &alias_map(&definitions)));
+            }
+            Some(Operation::Explain { command, format }) => {
+                use ops::explain::explain_command;
+                let alias_file_path = alias_file_paths.first().cloned().unwrap_or_else(crate::database::persistence::get_default_alias_file_path);
+                let explanation = match explain_command(&command.join(" "), db_ref, dc_ref, &alias_file_path) {
+                    Ok(explanation) => explanation,
+                    Err(e) => {
+                        eprintln!("{}", e.red());
+                        return;
+                    }
+                };
+
+                output::print_explanation_as(*format, &explanation);
+            }
+            Some(Operation::Stats { days, json, format }) => {
+                use database::persistence::{get_usage_history_path, load_usage_history};
+                use ops::usage_stats::build_report;
+                let history = match load_usage_history(&get_usage_history_path()) {
+                    Ok(history) => history,
+                    Err(e) => {
+                        eprintln!("{}", format!("Failed to load usage history: {}", e).red());
+                        return;
+                    }
+                };
+                let report = build_report(&history, *days);
+                if *json {
+                    eprintln!("{}", "--json is deprecated, use --format json".yellow());
+                }
+                output::print_stats(if *json { OutputFormat::Json } else { *format }, &report);
+            }
+            Some(Operation::DeleteSuggestion { alias }) => {
+                delete_suggestion(alias, dc_ref);
+                println!("{}", format!("Deleted suggestions for: {}", alias).yellow());
+                if let Err(e) = save_deleted_commands(dc_ref, &deleted_commands_path) {
+                    eprintln!("{}", format!("Failed to save deleted commands: {}", e).red());
+                }
+            }
+            Some(Operation::Ignored { action }) => {
+                use cli::cli_data::IgnoredAction;
+                use database::database_structs::BlockKind;
+                use ops::ignored::{add_block_rule, format_time, ignored_entries, parse_duration, prune_expired, restore, snooze};
+                let now = database::scoring::now();
+                prune_expired(dc_ref, now);
+                match action {
+                    IgnoredAction::List { format } => {
+                        let aliases = ops::alias_ops::get_aliases_from_multiple_files(&alias_file_paths);
+                        let entries = ignored_entries(dc_ref, &aliases, now);
+                        if *format != OutputFormat::Table {
+                            output::print_ignored(*format, &entries);
+                        } else if entries.is_empty() {
+                            println!("{}", "Nothing is ignored.".yellow());
+                        } else {
+                            let label_width = entries.iter().map(|e| e.label().len()).max().unwrap_or(0);
+                            for entry in &entries {
+                                println!("{}  {}", format!("{:<width$}", entry.label(), width = label_width).cyan(), entry.text);
+                            }
+                            println!("{}", format!("Total: {} ignored entr{}", entries.len(), if entries.len() == 1 { "y" } else { "ies" }).green());
+                        }
+                    }
+                    IgnoredAction::Restore { entry } => {
+                        let text = entry.join(" ");
+                        let aliases = ops::alias_ops::get_aliases_from_multiple_files(&alias_file_paths);
+                        match restore(dc_ref, &aliases, &text) {
+                            Ok(0) => {
+                                eprintln!("{}", format!("'{}' is not ignored, see `alman ignored list`", text).red());
+                                return;
+                            }
+                            Ok(_) => println!("{}", format!("Restored: {}", text).green()),
+                            Err(e) => {
+                                eprintln!("{}", e.red());
+                                return;
+                            }
+                        }
+                    }
+                    IgnoredAction::Block { pattern, regex } => {
+                        let kind = if *regex { BlockKind::Regex } else { BlockKind::Glob };
+                        if let Err(e) = add_block_rule(dc_ref, pattern, kind) {
+                            eprintln!("{}", format!("Invalid pattern '{}': {}", pattern, e).red());
+                            return;
+                        }
+                        println!("{}", format!("Blocked suggestions matching '{}'", pattern).yellow());
+                    }
+                    IgnoredAction::Snooze { duration, command } => {
+                        let seconds = match parse_duration(duration) {
+                            Ok(seconds) => seconds,
+                            Err(e) => {
+                                eprintln!("{}", e.red());
+                                return;
+                            }
+                        };
+                        let text = command.join(" ");
+                        snooze(dc_ref, &text, now + seconds);
+                        println!("{}", format!("Snoozed '{}' until {}", text, format_time(now + seconds)).yellow());
+                    }
+                }
+                if let Err(e) = save_deleted_commands(dc_ref, &deleted_commands_path) {
+                    eprintln!("{}", format!("Failed to save deleted commands: {}", e).red());
+                }
             }
             Some(Operation::Tui) => {
                 let tui_path = cli.alias_file_path.clone().unwrap_or_else(|| {
@@ -337,6 +585,98 @@ fn main() {
                     eprintln!("{}", format!("TUI error: {}", e).red());
                 }
             }
+            Some(Operation::SyncHistory { histfile, track, untrack }) => {
+                let path = match histfile {
+                    Some(p) => to_absolute_path(&p.to_string_lossy()),
+                    None => match default_history_file() {
+                        Some(p) => p,
+                        None => {
+                            eprintln!("{}", "Could not determine the history file, pass it explicitly.".red());
+                            return;
+                        }
+                    },
+                };
+
+                if *track || *untrack {
+                    let mut new_config = load_config().unwrap_or_default();
+                    if new_config.alias_file_paths.is_empty() {
+                        new_config.alias_file_paths = alias_file_paths.clone();
+                    }
+                    new_config.history_file_paths.retain(|p| p != &path);
+                    if *track {
+                        new_config.history_file_paths.push(path.clone());
+                        println!("{}", format!("Tracking history file {}", path).green());
+                    } else {
+                        println!("{}", format!("Stopped tracking history file {}", path).yellow());
+                    }
+                    if let Err(e) = save_config(&new_config) {
+                        eprintln!("{}", format!("Failed to save config: {}", e).red());
+                    }
+                    if *untrack {
+                        return;
+                    }
+                }
+
+                let synced = sync_history_files(std::slice::from_ref(&path), db_ref, dc_ref);
+                if let Err(e) = save_database(db_ref, &db_path) {
+                    eprintln!("{}", format!("Failed to save database: {}", e).red());
+                    return;
+                }
+                let count = synced.count;
+                synced.save();
+                println!("{}", format!("Synced {} new command(s) from {}", count, path).green());
+            }
+            Some(Operation::Import { source, path, all_hosts }) => {
+                use crate::database::persistence::{get_history_sync_path, load_history_sync_state, save_history_sync_state};
+                use ops::import_history::{default_database_path, import_history};
+
+                let path = match path.clone().or_else(|| default_database_path(source)) {
+                    Some(p) => to_absolute_path(&p.to_string_lossy()),
+                    None => {
+                        eprintln!("{}", "Could not determine the history database, pass it with --path.".red());
+                        return;
+                    }
+                };
+                if !Path::new(&path).exists() {
+                    eprintln!("{}", format!("History database not found: {}", path).red());
+                    return;
+                }
+
+                let state_path = get_history_sync_path();
+                let mut state = match load_history_sync_state(&state_path) {
+                    Ok(state) => state,
+                    Err(e) => {
+                        eprintln!("{}", format!("Failed to load history sync state: {}", e).red());
+                        return;
+                    }
+                };
+                let sequences_path = database::persistence::get_sequences_path();
+                let mut sequences = database::persistence::load_sequence_stats(&sequences_path).unwrap_or_default();
+                let mut usage = ops::usage_stats::UsageRecorder::load(&alias_file_paths);
+                match import_history(source, &path, *all_hosts, db_ref, dc_ref, &mut state, &mut sequences, &mut usage) {
+                    Ok(summary) => {
+                        if let Err(e) = save_database(db_ref, &db_path) {
+                            eprintln!("{}", format!("Failed to save database: {}", e).red());
+                            return;
+                        }
+                        usage.save();
+                        if let Err(e) = database::persistence::save_sequence_stats(&sequences, &sequences_path) {
+                            eprintln!("{}", format!("Failed to save command sequences: {}", e).red());
+                        }
+                        // Only move the cursor once the commands are safely stored
+                        if let Err(e) = save_history_sync_state(&state, &state_path) {
+                            eprintln!("{}", format!("Failed to save history sync state: {}", e).red());
+                        }
+                        println!("{}", format!("Imported {} new command(s) from {} ({} skipped)", summary.imported, path, summary.skipped).green());
+                        if summary.already_recorded > 0 {
+                            println!("{} command(s) ran after alman started recording and were already counted", summary.already_recorded);
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("{}", format!("Failed to import {}: {}", path, e).red());
+                    }
+                }
+            }
             Some(Operation::Init { shell }) => {
                 let opts = ShellOpts::new();
                 let init_script = render_shell_init(shell.clone(), &opts);
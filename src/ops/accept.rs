@@ -0,0 +1,80 @@
+// `alman accept`: turns the top suggestions into aliases in one go, without the TUI
+use crate::ops::get_suggestions::CommandWithAlias;
+use crate::ops::shell_conflicts::ConflictChecker;
+use std::collections::HashSet;
+use std::io::{BufRead, Write};
+
+#[derive(Debug, Clone)]
+pub struct Pick {
+    pub alias: String,
+    // What the alias stands for, the stable part for templates
+    pub command: String,
+}
+
+// The top alias of the first `num` suggestions that don't collide with an alias defined in any
+// tracked file. Suggestions already have conflicting and duplicate aliases taken out.
+pub fn pick(list: &[CommandWithAlias], existing_aliases: &[(String, String)], num: usize) -> Vec<Pick> {
+    list.iter()
+        .filter_map(|s| s.alias_suggestions.first())
+        .filter(|top| !existing_aliases.iter().any(|(alias, _)| *alias == top.alias))
+        .take(num)
+        .map(|top| Pick { alias: top.alias.clone(), command: top.command.clone() })
+        .collect()
+}
+
+// Asks y/n/edit/quit about every pick on stdin and returns the ones to write. Stops at the end
+// of input as if told to quit.
+pub fn confirm(picks: Vec<Pick>, existing_aliases: &[(String, String)]) -> Vec<Pick> {
+    let conflicts = ConflictChecker::load();
+    let mut taken: HashSet<String> = existing_aliases.iter().map(|(alias, _)| alias.clone()).collect();
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut prompt = |text: &str| -> Option<String> {
+        print!("{}", text);
+        let _ = std::io::stdout().flush();
+        lines.next().and_then(|line| line.ok()).map(|line| line.trim().to_string())
+    };
+
+    let total = picks.len();
+    let mut confirmed = Vec::new();
+    'picks: for (i, mut pick) in picks.into_iter().enumerate() {
+        loop {
+            let answer = match prompt(&format!("[{}/{}] {} = {}  add? [y/N/e(dit)/q] ", i + 1, total, pick.alias, pick.command)) {
+                Some(answer) => answer.to_lowercase(),
+                None => break 'picks,
+            };
+            match answer.as_str() {
+                "y" | "yes" => {
+                    // An earlier pick may have been renamed to this one
+                    if !taken.insert(pick.alias.clone()) {
+                        println!("  '{}' is already an alias, pick another with e", pick.alias);
+                        continue;
+                    }
+                    confirmed.push(pick);
+                    break;
+                }
+                "n" | "no" | "" => break,
+                "e" | "edit" => {
+                    let alias = match prompt(&format!("  alias for {}: ", pick.command)) {
+                        Some(alias) => alias,
+                        None => break 'picks,
+                    };
+                    if alias.is_empty() {
+                        continue;
+                    }
+                    if taken.contains(&alias) {
+                        println!("  '{}' is already an alias", alias);
+                        continue;
+                    }
+                    if let Some(conflict) = conflicts.conflict(&alias) {
+                        println!("  Warning: '{}' {}", alias, conflict);
+                    }
+                    pick.alias = alias;
+                }
+                "q" | "quit" => break 'picks,
+                _ => println!("  Please answer y, n, e or q"),
+            }
+        }
+    }
+    confirmed
+}
@@ -1,12 +1,34 @@
-use crate::database::database_structs::{Database,DeletedCommands};
-use crate::ops::alias_ops::{add_alias_to_file};
-
-pub fn add_alias(db: &mut Database, deleted_commands: &mut DeletedCommands, file_path: &str, alias: &str, command: &str) {
-    // first add it to deleted commands
-    // use wrapper from database_ops.rs
-    db.remove_command(&command.to_string(), deleted_commands);
-
-    // second add to file.
-    add_alias_to_file(file_path, alias, command);
-}
-
+use crate::database::database_structs::{Database,DeletedCommands};
+use crate::ops::alias_ops::{add_alias_to_file};
+use crate::ops::normalize_command::{is_template, stable_prefix};
+
+pub fn add_alias(db: &mut Database, deleted_commands: &mut DeletedCommands, file_path: &str, alias: &str, command: &str) {
+    // first add it to deleted commands
+    hide_aliased_command(db, deleted_commands, command);
+
+    // second add to file.
+    add_alias_to_file(file_path, alias, command);
+}
+
+// An aliased command, and the templates it is the stable part of, aren't suggested anymore
+pub fn hide_aliased_command(db: &Database, deleted_commands: &mut DeletedCommands, command: &str) {
+    deleted_commands.hide_aliased(command);
+
+    // templates whose stable part is now aliased don't need suggesting anymore
+    for template in templates_of(db.reverse_command_map.keys(), command) {
+        deleted_commands.hide_aliased(&template);
+    }
+}
+
+// Undoes hide_aliased_command once no alias stands for `command`, leaving whatever was
+// deleted on purpose deleted
+pub fn unhide_aliased_command(deleted_commands: &mut DeletedCommands, command: &str) {
+    deleted_commands.unhide_aliased(command);
+    for template in templates_of(deleted_commands.aliased.iter(), command) {
+        deleted_commands.unhide_aliased(&template);
+    }
+}
+
+fn templates_of<'a>(texts: impl Iterator<Item = &'a String>, command: &str) -> Vec<String> {
+    texts.filter(|text| is_template(text) && stable_prefix(text) == command).cloned().collect()
+}
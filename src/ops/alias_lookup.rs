@@ -0,0 +1,236 @@
+// Finding your way around existing aliases: `alman search` (fuzzy, over names and commands),
+// `alman which` (where a name is defined and what it hides) and `alman expand` (a command
+// line with its aliases replaced the way the shell would).
+use crate::ops::alias_ops::AliasDefinition;
+use crate::ops::fuzzy::fuzzy_match;
+use crate::ops::shell_conflicts::ConflictChecker;
+use colored::Colorize;
+use std::collections::HashMap;
+
+// Words after which the shell still looks for a command, so an alias there gets expanded
+const KEEPS_COMMAND_POSITION: &[&str] = &["!", "{", "if", "then", "else", "elif", "do", "while", "until", "time"];
+
+pub struct SearchHit<'a> {
+    pub definition: &'a AliasDefinition,
+    pub score: i32,
+    // Matched char indices, empty where that side didn't match
+    pub alias_positions: Vec<usize>,
+    pub command_positions: Vec<usize>,
+}
+
+// Best match first, ties in source order
+pub fn search<'a>(definitions: &'a [AliasDefinition], query: &str) -> Vec<SearchHit<'a>> {
+    let mut hits: Vec<SearchHit> = definitions
+        .iter()
+        .filter_map(|definition| {
+            let on_alias = fuzzy_match(query, &definition.alias);
+            let on_command = fuzzy_match(query, &definition.command);
+            let score = match (&on_alias, &on_command) {
+                (None, None) => return None,
+                // Names are what people half remember, a hit there counts a bit more
+                (Some(a), Some(c)) => (a.score + 2).max(c.score),
+                (Some(a), None) => a.score + 2,
+                (None, Some(c)) => c.score,
+            };
+            Some(SearchHit {
+                definition,
+                score,
+                alias_positions: on_alias.map(|m| m.positions).unwrap_or_default(),
+                command_positions: on_command.map(|m| m.positions).unwrap_or_default(),
+            })
+        })
+        .collect();
+    hits.sort_by_key(|hit| std::cmp::Reverse(hit.score));
+    hits
+}
+
+pub struct WhichReport<'a> {
+    pub alias: String,
+    // In source order, the last one is what the shell uses
+    pub definitions: Vec<&'a AliasDefinition>,
+    // What else answers to the name (a command on PATH, a builtin, an rc alias...)
+    pub shadows: Option<String>,
+}
+
+pub fn which<'a>(definitions: &'a [AliasDefinition], alias: &str) -> WhichReport<'a> {
+    let found: Vec<&AliasDefinition> = definitions.iter().filter(|d| d.alias == alias).collect();
+    let shadows = ConflictChecker::load().conflict(alias);
+    WhichReport { alias: alias.to_string(), definitions: found, shadows }
+}
+
+// name -> command, the last definition of a name wins like it does when the files are sourced
+pub fn alias_map(definitions: &[AliasDefinition]) -> HashMap<String, String> {
+    definitions.iter().map(|d| (d.alias.clone(), d.command.clone())).collect()
+}
+
+pub fn expand(line: &str, aliases: &HashMap<String, String>) -> String {
+    expand_with(line, aliases, &mut Vec::new()).0
+}
+
+// Returns the expanded text and whether the word after it is in command position. `active`
+// holds the aliases being expanded, an alias is never expanded inside itself so
+// `alias ls='ls --color'` stops after one round.
+fn expand_with(line: &str, aliases: &HashMap<String, String>, active: &mut Vec<String>) -> (String, bool) {
+    let chars: Vec<char> = line.chars().collect();
+    let mut out = String::new();
+    let mut command_position = true;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            out.push(c);
+            command_position = true;
+            i += 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            out.push(c);
+            i += 1;
+            continue;
+        }
+        if let Some(op) = operator_at(&chars, i) {
+            out.push_str(op);
+            i += op.len();
+            command_position = op != ")";
+            continue;
+        }
+
+        let (end, quoted) = word_end(&chars, i);
+        let word: String = chars[i..end].iter().collect();
+        i = end;
+        if command_position && !quoted && !active.contains(&word) {
+            if let Some(value) = aliases.get(&word) {
+                active.push(word);
+                let (expanded, next_is_command) = expand_with(value, aliases, active);
+                active.pop();
+                out.push_str(&expanded);
+                // A value ending in a blank gets the next word checked for an alias too
+                command_position = next_is_command || value.ends_with([' ', '\t']);
+                continue;
+            }
+        }
+        command_position = command_position && (KEEPS_COMMAND_POSITION.contains(&word.as_str()) || is_assignment(&word));
+        out.push_str(&word);
+    }
+    (out, command_position)
+}
+
+fn operator_at(chars: &[char], i: usize) -> Option<&'static str> {
+    let next = chars.get(i + 1).copied();
+    match (chars[i], next) {
+        ('&', Some('&')) => Some("&&"),
+        ('|', Some('|')) => Some("||"),
+        ('|', Some('&')) => Some("|&"),
+        (';', Some(';')) => Some(";;"),
+        ('&', _) => Some("&"),
+        ('|', _) => Some("|"),
+        (';', _) => Some(";"),
+        ('(', _) => Some("("),
+        (')', _) => Some(")"),
+        _ => None,
+    }
+}
+
+// End of the word starting at `start`, and whether any of it was quoted or escaped. Quoted
+// words are never alias names to the shell.
+fn word_end(chars: &[char], start: usize) -> (usize, bool) {
+    let mut i = start;
+    let mut quoted = false;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => {
+                quoted = true;
+                i += 2;
+            }
+            '\'' => {
+                quoted = true;
+                i += 1;
+                while i < chars.len() && chars[i] != '\'' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            '"' => {
+                quoted = true;
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    i += if chars[i] == '\\' { 2 } else { 1 };
+                }
+                i += 1;
+            }
+            c if c.is_whitespace() || operator_at(chars, i).is_some() => break,
+            _ => i += 1,
+        }
+    }
+    (i.min(chars.len()), quoted)
+}
+
+// `FOO=bar` before a command
+fn is_assignment(word: &str) -> bool {
+    match word.split_once('=') {
+        Some((name, _)) => {
+            !name.is_empty()
+                && !name.starts_with(|c: char| c.is_ascii_digit())
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        None => false,
+    }
+}
+
+// `text` with the chars at `positions` picked out, for the search table
+pub fn highlight(text: &str, positions: &[usize]) -> String {
+    text.chars()
+        .enumerate()
+        .map(|(i, c)| {
+            if positions.contains(&i) {
+                c.to_string().yellow().bold().to_string()
+            } else {
+                c.to_string()
+            }
+        })
+        .collect()
+}
+
+pub fn print_search(hits: &[SearchHit]) {
+    if hits.is_empty() {
+        println!("{}", "No matching aliases.".yellow());
+        return;
+    }
+    let alias_width = hits.iter().map(|h| h.definition.alias.chars().count()).max().unwrap_or(0);
+    let command_width = hits.iter().map(|h| h.definition.command.chars().count()).max().unwrap_or(0);
+    for hit in hits {
+        let d = hit.definition;
+        println!(
+            "{}{}  {}{}  {}",
+            highlight(&d.alias, &hit.alias_positions).cyan(),
+            " ".repeat(alias_width - d.alias.chars().count()),
+            highlight(&d.command, &hit.command_positions),
+            " ".repeat(command_width - d.command.chars().count()),
+            format!("{}:{}", d.file, d.line).dimmed(),
+        );
+    }
+}
+
+pub fn print_which(report: &WhichReport) {
+    match report.definitions.last() {
+        Some(active) => {
+            println!("{} = {}", report.alias.cyan(), active.command);
+            for (i, d) in report.definitions.iter().enumerate().rev() {
+                if i + 1 == report.definitions.len() {
+                    println!("  {}:{}  {}", d.file, d.line, "(active)".green());
+                } else {
+                    println!("  {}:{}  {}  {}", d.file, d.line, d.command, "(shadowed)".yellow());
+                }
+            }
+            if let Some(shadows) = &report.shadows {
+                println!("{}", format!("Note: '{}' {}, the alias hides it", report.alias, shadows).yellow());
+            }
+        }
+        None => {
+            println!("{}", format!("'{}' is not an alias in any tracked file", report.alias).yellow());
+            if let Some(shadows) = &report.shadows {
+                println!("'{}' {}", report.alias, shadows);
+            }
+        }
+    }
+}
@@ -36,31 +36,63 @@ pub fn get_aliases(file_path: &str) -> Vec<(String, String)> {
                 continue;
             }
         };
-        let line = line.trim();
-        if line.starts_with("alias ") {
-            let line_in = line[6..].trim();
-            // now split by =
-            if let Some(eq_index) = line_in.find('=') {
-                let alias = line_in[..eq_index].trim().to_string();
-                let mut command = line_in[(eq_index + 1)..].trim();
-                if (command.starts_with('\'') && command.ends_with('\'')) ||
-                   (command.starts_with('"') && command.ends_with('"')) {
-                    command = &command[1..command.len()-1];
-                }
-                let command = command.to_string();
-                aliases.push((alias, command));
-            } else {
-                // Invalid alias format
-            }
+        if let Some(alias) = parse_alias_line(&line) {
+            aliases.push(alias);
         }
     }
     aliases
 }
 
+// `alias name='command'` -> (name, command)
+fn parse_alias_line(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    let line_in = line.strip_prefix("alias ")?.trim();
+    // now split by =
+    let eq_index = line_in.find('=')?;
+    let alias = line_in[..eq_index].trim().to_string();
+    let mut command = line_in[(eq_index + 1)..].trim();
+    if (command.starts_with('\'') && command.ends_with('\'')) ||
+       (command.starts_with('"') && command.ends_with('"')) {
+        command = &command[1..command.len()-1];
+    }
+    Some((alias, command.to_string()))
+}
+
+#[derive(Debug, Clone)]
+pub struct AliasDefinition {
+    pub alias: String,
+    pub command: String,
+    pub file: String,
+    pub line: usize, // 1 based
+}
+
+// Every alias definition in the files, in the order the shell sources them, so for a name
+// defined twice the last one is what the shell uses
+pub fn get_alias_definitions(file_paths: &[String]) -> Vec<AliasDefinition> {
+    let mut definitions = Vec::new();
+    for file_path in file_paths {
+        let content = match std::fs::read_to_string(file_path) {
+            Ok(content) => content,
+            Err(_) => continue,
+        };
+        for (i, line) in content.lines().enumerate() {
+            if let Some((alias, command)) = parse_alias_line(line) {
+                definitions.push(AliasDefinition { alias, command, file: file_path.clone(), line: i + 1 });
+            }
+        }
+    }
+    definitions
+}
+
+// How an alias is written to an alias file
+pub fn alias_line(alias: &str, command: &str) -> String {
+    format!("alias {}='{}'\n", alias, command)
+}
+
 pub fn write_aliases(file_path: &str, aliases: Vec<(String, String)>) {
     let mut file = File::create(file_path).expect("Could not create alias file");
     for (alias, command) in aliases {
-        let line = format!("alias {}='{}'\n", alias, command);
+        let line = alias_line(&alias, &command);
         file.write_all(line.as_bytes()).expect("Could not write to alias file");
     }
     file.flush().expect("Could not flush alias file");
@@ -97,6 +129,18 @@ pub fn get_aliases_from_multiple_files(file_paths: &[String]) -> Vec<(String, St
     all_aliases
 }
 
+// Same as above, with the file each alias came from
+pub fn get_aliases_with_files(file_paths: &[String]) -> Vec<(String, String, String)> {
+    file_paths
+        .iter()
+        .flat_map(|file_path| {
+            get_aliases(file_path)
+                .into_iter()
+                .map(move |(alias, command)| (alias, command, file_path.clone()))
+        })
+        .collect()
+}
+
 pub fn add_alias_to_multiple_files(file_paths: &[String], alias: &str, command: &str) {
     // Check if alias exists in any file
     let all_aliases = get_aliases_from_multiple_files(file_paths);
@@ -111,19 +155,27 @@ pub fn add_alias_to_multiple_files(file_paths: &[String], alias: &str, command:
 }
 
 pub fn remove_alias_from_multiple_files(file_paths: &[String], alias: &str) {
-    let mut found = false;
-    
     for file_path in file_paths {
         let mut aliases = get_aliases(file_path);
         if let Some(pos) = aliases.iter().position(|(a, _)| a == alias) {
             aliases.remove(pos);
             write_aliases(file_path, aliases);
-            found = true;
             break; // Remove from first file where found
         }
     }
-    
-    if !found {
-        return;
+}
+
+// Moves an alias from the first file that defines it to `to_file`, replacing any definition
+// already there. Returns the file it came from, None if no file has it.
+pub fn move_alias_to_file(file_paths: &[String], alias: &str, to_file: &str) -> Option<String> {
+    let (command, from_file) = get_aliases_with_files(file_paths)
+        .into_iter()
+        .find(|(a, _, _)| a == alias)
+        .map(|(_, command, file)| (command, file))?;
+    if from_file != to_file {
+        remove_alias_from_file(&from_file, alias);
+        remove_alias_from_file(to_file, alias);
+        add_alias_to_file(to_file, alias, &command);
     }
-}
\ No newline at end of file
+    Some(from_file)
+}
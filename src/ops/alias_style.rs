@@ -0,0 +1,86 @@
+// What the user's existing aliases say about how they like them named: which kind of
+// generator would have come up with them, how long they are and how each tool is abbreviated.
+// Suggestions that fit get a bonus on top of their priority.
+use std::collections::HashMap;
+
+// Too few aliases say nothing about a style
+const MIN_ALIASES: usize = 3;
+// Bonus when every existing alias follows the strategy / has the length
+const MAX_STRATEGY_BONUS: f64 = 20.0;
+const MAX_LENGTH_BONUS: f64 = 10.0;
+const TOOL_PREFIX_BONUS: i32 = 10;
+
+#[derive(Debug, Clone, Default)]
+pub struct AliasStyle {
+    total: usize,
+    // Priority category of the generator that reproduces each alias
+    strategies: HashMap<&'static str, usize>,
+    lengths: HashMap<usize, usize>,
+    // What every alias for a tool starts with, e.g. "g" or "gt" for git
+    tool_prefixes: HashMap<String, String>,
+}
+
+impl AliasStyle {
+    // `aliases` holds (alias, command, category of the generator that produces the alias)
+    pub fn learn(aliases: &[(String, String, Option<&'static str>)]) -> Self {
+        let mut style = Self {
+            total: aliases.len(),
+            ..Default::default()
+        };
+        if style.total < MIN_ALIASES {
+            return style;
+        }
+
+        let mut by_tool: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (alias, command, strategy) in aliases {
+            if let Some(strategy) = strategy {
+                *style.strategies.entry(strategy).or_insert(0) += 1;
+            }
+            *style.lengths.entry(alias.chars().count()).or_insert(0) += 1;
+            if let Some(tool) = command.split_whitespace().next() {
+                by_tool.entry(tool).or_default().push(alias);
+            }
+        }
+
+        for (tool, aliases) in by_tool {
+            if aliases.len() < 2 {
+                continue;
+            }
+            let prefix = aliases[1..].iter().fold(aliases[0].to_string(), |prefix, alias| {
+                prefix.chars().zip(alias.chars()).take_while(|(a, b)| a == b).map(|(a, _)| a).collect()
+            });
+            // Only a prefix drawn from the tool's name is a convention, "gs"/"gst" for git
+            // sharing "gs" is a coincidence of the subcommand
+            if !prefix.is_empty() && prefix.len() < aliases.iter().map(|a| a.len()).min().unwrap_or(0) && is_abbreviation_of(&prefix, tool) {
+                style.tool_prefixes.insert(tool.to_string(), prefix);
+            }
+        }
+        style
+    }
+
+    pub fn bonus(&self, category: &str, alias: &str, command: &str) -> i32 {
+        if self.total < MIN_ALIASES {
+            return 0;
+        }
+        let share = |count: Option<&usize>| count.copied().unwrap_or(0) as f64 / self.total as f64;
+        let mut bonus = (share(self.strategies.get(category)) * MAX_STRATEGY_BONUS).round() as i32;
+        bonus += (share(self.lengths.get(&alias.chars().count())) * MAX_LENGTH_BONUS).round() as i32;
+
+        let tool = command.split_whitespace().next().unwrap_or("");
+        if let Some(prefix) = self.tool_prefixes.get(tool) {
+            if alias.starts_with(prefix.as_str()) {
+                bonus += TOOL_PREFIX_BONUS;
+            }
+        }
+        bonus
+    }
+}
+
+// Whether the letters of `short` appear in order in `word` starting with its first letter
+fn is_abbreviation_of(short: &str, word: &str) -> bool {
+    if short.chars().next() != word.chars().next() {
+        return false;
+    }
+    let mut letters = word.chars();
+    short.chars().all(|c| letters.any(|w| w == c))
+}
@@ -1,88 +1,142 @@
+use crate::database::command_tree::CommandTree;
+use crate::ops::alias_style::AliasStyle;
+use crate::ops::keystroke_savings::{estimate, saved_per_run};
+use crate::ops::shell_conflicts::ConflictChecker;
+use crate::ops::suggestion_templates::{load_templates, CompiledTemplate};
+use crate::ops::tool_rules::ToolRules;
+use serde::Serialize;
+use std::cell::OnceCell;
 use std::collections::HashSet;
-use std::env;
+use std::sync::Mutex;
 
-#[derive(Debug, Clone)]
+// The style learned last, with the aliases it was learned from. The TUI builds a suggester for
+// every alias it offers, and classifying all existing aliases each time adds up.
+type Aliases = Vec<(String, String)>;
+static LEARNED_STYLE: Mutex<Option<(Aliases, AliasStyle)>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
 pub struct AliasSuggestion {
     pub alias: String,
     pub command: String,
     pub reason: String,
 }
 
-pub struct AliasSuggester {
+#[derive(Debug, Clone, Serialize)]
+pub struct PriorityBreakdown {
+    pub category: &'static str,
+    pub base: i32,
+    pub length_bonus: i32,
+    // For fitting the naming style of the user's existing aliases
+    pub style_bonus: i32,
+}
+
+impl PriorityBreakdown {
+    pub fn total(&self) -> i32 {
+        self.base + self.length_bonus + self.style_bonus
+    }
+
+    // e.g. "90 abbreviation +7 length +12 style"
+    pub fn describe(&self) -> String {
+        let mut text = format!("{} {} {:+} length", self.base, self.category, self.length_bonus);
+        if self.style_bonus != 0 {
+            text.push_str(&format!(" {:+} style", self.style_bonus));
+        }
+        text
+    }
+}
+
+pub struct AliasSuggester<'a> {
+    aliases: Aliases,
     existing_aliases: HashSet<String>,
-    system_commands: HashSet<String>,
+    tool_rules: ToolRules,
+    templates: Vec<CompiledTemplate>,
+    // Learned from `aliases` the first time a suggestion is ranked
+    style: OnceCell<AliasStyle>,
+    conflicts: ConflictChecker,
+    // History to estimate daily keystroke savings from, and when "now" is
+    savings: Option<(&'a CommandTree, i64)>,
 }
 
-impl AliasSuggester {
+impl<'a> AliasSuggester<'a> {
     pub fn new(alias_file_path: &str) -> Self {
-        let existing_aliases = Self::load_existing_aliases(alias_file_path);
-        let system_commands = Self::load_system_commands();
-        
+        use crate::ops::alias_ops::get_aliases;
+
+        let aliases = get_aliases(alias_file_path);
         Self {
-            existing_aliases,
-            system_commands,
+            existing_aliases: aliases.iter().map(|(alias, _)| alias.clone()).collect(),
+            aliases,
+            tool_rules: ToolRules::load(),
+            templates: load_templates(),
+            style: OnceCell::new(),
+            conflicts: ConflictChecker::load(),
+            savings: None,
         }
     }
 
-    fn load_existing_aliases(alias_file_path: &str) -> HashSet<String> {
-        use crate::ops::alias_ops::get_aliases;
-        
-        let aliases = get_aliases(alias_file_path);
-        aliases.into_iter().map(|(alias, _)| alias).collect()
-    }
-
-    fn load_system_commands() -> HashSet<String> {
-        let mut commands = HashSet::new();
-        
-        // Get PATH from environment
-        if let Ok(path) = env::var("PATH") {
-            for path_dir in path.split(':') {
-                if let Ok(entries) = std::fs::read_dir(path_dir) {
-                    for entry in entries.flatten() {
-                        if let Ok(metadata) = entry.metadata() {
-                            if metadata.is_file() {
-                                // Check if file is executable (check execute permissions)
-                                use std::os::unix::fs::PermissionsExt;
-                                if metadata.permissions().mode() & 0o111 != 0 {
-                                    if let Some(name) = entry.file_name().to_str() {
-                                        commands.insert(name.to_string());
-                                    }
-                                }
-                            }
-                        }
-                    }
+    fn style(&self) -> &AliasStyle {
+        self.style.get_or_init(|| {
+            let mut learned = LEARNED_STYLE.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some((aliases, style)) = learned.as_ref() {
+                if *aliases == self.aliases {
+                    return style.clone();
                 }
             }
-        }
+            let classified: Vec<_> = self
+                .aliases
+                .iter()
+                .map(|(alias, command)| (alias.clone(), command.clone(), self.strategy_of(alias, command)))
+                .collect();
+            let style = AliasStyle::learn(&classified);
+            *learned = Some((self.aliases.clone(), style.clone()));
+            style
+        })
+    }
+
+    // Rank suggestions by how many keystrokes they'd save a day given how often the command runs
+    pub fn with_savings(mut self, tree: &'a CommandTree, now: i64) -> Self {
+        self.savings = Some((tree, now));
+        self
+    }
+
+    // The category of the best generator that comes up with `alias` for `command`, if any does
+    fn strategy_of(&self, alias: &str, command: &str) -> Option<&'static str> {
+        self.generate_candidates(command)
+            .into_iter()
+            .filter(|s| s.alias == alias)
+            .map(|s| self.base_priority(&s.reason))
+            .max_by_key(|(base, _)| *base)
+            .map(|(_, category)| category)
+    }
+
+    pub fn suggest_aliases(&self, command: &str) -> Vec<AliasSuggestion> {
+        let mut suggestions = self.generate_candidates(command);
+
+        // Filter out conflicts and sort by priority
+        suggestions.retain(|s| !self.has_conflicts(&s.alias));
         
-        // Get shell aliases using the user's current shell from $SHELL
-        if let Ok(shell_path) = std::env::var("SHELL") {
-            if let Ok(output) = std::process::Command::new(shell_path)
-                .arg("-i")
-                .arg("-c")
-                .arg("alias")
-                .output() {
-                if let Ok(alias_output) = String::from_utf8(output.stdout) {
-                    for line in alias_output.lines() {
-                        if let Some(alias_name) = line.split('=').next() {
-                            // Remove any quotes and whitespace
-                            let clean_alias = alias_name.trim_matches('"').trim_matches('\'').trim();
-                            if !clean_alias.is_empty() {
-                                commands.insert(clean_alias.to_string());
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        // Remove duplicates based on alias name
+        let mut seen = std::collections::HashSet::new();
+        suggestions.retain(|s| seen.insert(s.alias.clone()));
+        
+        let mut ranked: Vec<_> = suggestions.into_iter().map(|s| (self.get_priority(&s), s)).collect();
+        ranked.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
         
-        commands
+        // Show all suggestions (no top 3 limit)
+        ranked.into_iter().map(|(_, s)| s).collect()
     }
 
-    pub fn suggest_aliases(&self, command: &str) -> Vec<AliasSuggestion> {
+    // Everything the generators come up with, before conflicts and duplicates are dropped
+    fn generate_candidates(&self, command: &str) -> Vec<AliasSuggestion> {
         let mut suggestions = Vec::new();
-        
+
+        // Sequences like `git add -A && git commit && git push` get their own naming scheme,
+        // the word-based generators below would only produce noise for them
+        if crate::ops::command_sequences::is_combo(command) {
+            return self.generate_combo_aliases(command);
+        }
+
         // Generate different types of suggestions
+        suggestions.extend(self.templates.iter().filter_map(|t| t.suggest(command)));
         suggestions.extend(self.generate_semantic_aliases(command));
         suggestions.extend(self.generate_abbreviation_aliases(command));
         suggestions.extend(self.generate_vowel_removal_aliases(command));
@@ -94,17 +148,53 @@ impl AliasSuggester {
         suggestions.extend(self.generate_keyboard_pattern_aliases(command));
         suggestions.extend(self.generate_smart_prefix_aliases(command));
         suggestions.extend(self.generate_common_pattern_aliases(command));
-        
-        // Filter out conflicts and sort by priority
-        suggestions.retain(|s| !self.has_conflicts(&s.alias));
-        
-        // Remove duplicates based on alias name
-        let mut seen = std::collections::HashSet::new();
-        suggestions.retain(|s| seen.insert(s.alias.clone()));
-        
-        suggestions.sort_by(|a, b| self.get_priority(b).cmp(&self.get_priority(a)));
-        
-        // Show all suggestions (no top 3 limit)
+        suggestions
+    }
+
+    fn generate_combo_aliases(&self, command: &str) -> Vec<AliasSuggestion> {
+        use crate::ops::command_sequences::COMBO_SEPARATOR;
+
+        let mut suggestions = Vec::new();
+        let steps: Vec<Vec<&str>> = command
+            .split(COMBO_SEPARATOR)
+            .map(|step| step.split_whitespace().collect::<Vec<_>>())
+            .collect();
+        if steps.len() < 2 || steps.iter().any(|words| words.is_empty()) {
+            return suggestions;
+        }
+        let initial = |word: &str| word.trim_start_matches('-').chars().next().filter(|c| c.is_ascii_alphanumeric());
+
+        // Same tool all the way through: tool initial then each subcommand's initial (git add/commit/push -> gacp)
+        let tool = steps[0][0];
+        if steps.iter().all(|words| words[0] == tool && words.len() > 1) {
+            let mut alias: String = initial(tool).into_iter().collect();
+            alias.extend(steps.iter().filter_map(|words| initial(words[1])));
+            suggestions.push(AliasSuggestion {
+                alias,
+                command: command.to_string(),
+                reason: format!("Combo {} sequence", tool),
+            });
+        }
+
+        // Initial of every step's tool and subcommand (cargo fmt && cargo test -> cfct)
+        let alias: String = steps
+            .iter()
+            .flat_map(|words| words.iter().take(2).filter_map(|w| initial(w)))
+            .collect();
+        suggestions.push(AliasSuggestion {
+            alias,
+            command: command.to_string(),
+            reason: "Combo step initials".to_string(),
+        });
+
+        // Just the tools (cd && make -> cm)
+        let alias: String = steps.iter().filter_map(|words| initial(words[0])).collect();
+        suggestions.push(AliasSuggestion {
+            alias,
+            command: command.to_string(),
+            reason: "Combo tool initials".to_string(),
+        });
+
         suggestions
     }
 
@@ -126,7 +216,7 @@ impl AliasSuggester {
         }
 
         // Generate tool-specific semantic aliases
-        if let Some(semantic_alias) = self.generate_tool_specific_alias(tool, args) {
+        if let Some(semantic_alias) = self.tool_rules.suggest(command) {
             suggestions.push(semantic_alias);
         }
 
@@ -148,7 +238,7 @@ impl AliasSuggester {
         let mut suggestions = Vec::new();
         
         // Extract the executable name from the path
-        let executable_name = tool.split('/').last().unwrap_or(tool);
+        let executable_name = tool.split('/').next_back().unwrap_or(tool);
         
         // Generate alias based on executable name
         if let Some(name) = executable_name.strip_suffix(".exe") {
@@ -191,180 +281,6 @@ impl AliasSuggester {
         suggestions
     }
 
-    fn generate_tool_specific_alias(&self, tool: &str, args: &[&str]) -> Option<AliasSuggestion> {
-        if args.is_empty() {
-            return None;
-        }
-
-        let subcommand = args[0];
-        let remaining_args = &args[1..];
-
-        match tool {
-            "git" => self.generate_git_alias(subcommand, remaining_args),
-            "docker" => self.generate_docker_alias(subcommand),
-            "npm" => self.generate_npm_alias(subcommand),
-            "ssh" => self.generate_ssh_alias(subcommand),
-            _ => None,
-        }
-    }
-
-    fn generate_git_alias(&self, subcommand: &str, remaining_args: &[&str]) -> Option<AliasSuggestion> {
-        match subcommand {
-            "status" => Some(AliasSuggestion {
-                alias: "gs".to_string(),
-                command: "git status".to_string(),
-                reason: "Git status".to_string(),
-            }),
-            "add" => {
-                if !remaining_args.is_empty() && remaining_args[0] == "." {
-                    Some(AliasSuggestion {
-                        alias: "gaa".to_string(),
-                        command: "git add .".to_string(),
-                        reason: "Git add all".to_string(),
-                    })
-                } else {
-                    Some(AliasSuggestion {
-                        alias: "ga".to_string(),
-                        command: "git add".to_string(),
-                        reason: "Git add".to_string(),
-                    })
-                }
-            }
-            "commit" => {
-                if remaining_args.len() >= 2 && remaining_args[0] == "-m" {
-                    Some(AliasSuggestion {
-                        alias: "gcm".to_string(),
-                        command: format!("git commit -m \"{}\"", remaining_args[1]),
-                        reason: "Git commit with message".to_string(),
-                    })
-                } else {
-                    Some(AliasSuggestion {
-                        alias: "gc".to_string(),
-                        command: "git commit".to_string(),
-                        reason: "Git commit".to_string(),
-                    })
-                }
-            }
-            "checkout" => {
-                if !remaining_args.is_empty() && remaining_args[0] == "-b" {
-                    Some(AliasSuggestion {
-                        alias: "gcb".to_string(),
-                        command: format!("git checkout -b {}", remaining_args[1]),
-                        reason: "Git checkout new branch".to_string(),
-                    })
-                } else {
-                    Some(AliasSuggestion {
-                        alias: "gco".to_string(),
-                        command: "git checkout".to_string(),
-                        reason: "Git checkout".to_string(),
-                    })
-                }
-            }
-            "push" => Some(AliasSuggestion {
-                alias: "gp".to_string(),
-                command: "git push".to_string(),
-                reason: "Git push".to_string(),
-            }),
-            "pull" => Some(AliasSuggestion {
-                alias: "gl".to_string(),
-                command: "git pull".to_string(),
-                reason: "Git pull".to_string(),
-            }),
-            "log" => Some(AliasSuggestion {
-                alias: "glg".to_string(),
-                command: "git log".to_string(),
-                reason: "Git log".to_string(),
-            }),
-            "branch" => Some(AliasSuggestion {
-                alias: "gb".to_string(),
-                command: "git branch".to_string(),
-                reason: "Git branch".to_string(),
-            }),
-            _ => None,
-        }
-    }
-
-    fn generate_docker_alias(&self, subcommand: &str) -> Option<AliasSuggestion> {
-        match subcommand {
-            "ps" => Some(AliasSuggestion {
-                alias: "dps".to_string(),
-                command: "docker ps".to_string(),
-                reason: "Docker ps".to_string(),
-            }),
-            "run" => Some(AliasSuggestion {
-                alias: "dr".to_string(),
-                command: "docker run".to_string(),
-                reason: "Docker run".to_string(),
-            }),
-            "build" => Some(AliasSuggestion {
-                alias: "db".to_string(),
-                command: "docker build".to_string(),
-                reason: "Docker build".to_string(),
-            }),
-            "exec" => Some(AliasSuggestion {
-                alias: "de".to_string(),
-                command: "docker exec".to_string(),
-                reason: "Docker exec".to_string(),
-            }),
-            "rm" => Some(AliasSuggestion {
-                alias: "drm".to_string(),
-                command: "docker rm".to_string(),
-                reason: "Docker rm".to_string(),
-            }),
-            "rmi" => Some(AliasSuggestion {
-                alias: "drmi".to_string(),
-                command: "docker rmi".to_string(),
-                reason: "Docker rmi".to_string(),
-            }),
-            _ => None,
-        }
-    }
-
-    fn generate_npm_alias(&self, subcommand: &str) -> Option<AliasSuggestion> {
-        match subcommand {
-            "install" => Some(AliasSuggestion {
-                alias: "ni".to_string(),
-                command: "npm install".to_string(),
-                reason: "NPM install".to_string(),
-            }),
-            "run" => Some(AliasSuggestion {
-                alias: "nr".to_string(),
-                command: "npm run".to_string(),
-                reason: "NPM run".to_string(),
-            }),
-            "start" => Some(AliasSuggestion {
-                alias: "ns".to_string(),
-                command: "npm start".to_string(),
-                reason: "NPM start".to_string(),
-            }),
-            "test" => Some(AliasSuggestion {
-                alias: "nt".to_string(),
-                command: "npm test".to_string(),
-                reason: "NPM test".to_string(),
-            }),
-            "publish" => Some(AliasSuggestion {
-                alias: "np".to_string(),
-                command: "npm publish".to_string(),
-                reason: "NPM publish".to_string(),
-            }),
-            _ => None,
-        }
-    }
-
-    fn generate_ssh_alias(&self, host: &str) -> Option<AliasSuggestion> {
-        // Generate alias based on hostname
-        if let Some(short_name) = host.split('.').next() {
-            if short_name.len() >= 2 {
-                return Some(AliasSuggestion {
-                    alias: short_name.to_string(),
-                    command: format!("ssh {}", host),
-                    reason: format!("SSH to {}", host),
-                });
-            }
-        }
-        None
-    }
-
     fn generate_abbreviation_aliases(&self, command: &str) -> Vec<AliasSuggestion> {
         let mut suggestions = Vec::new();
         let parts: Vec<&str> = command.split_whitespace().collect();
@@ -577,77 +493,83 @@ impl AliasSuggester {
             return true;
         }
 
-        // Check if alias conflicts with system command
-        if self.system_commands.contains(alias) {
-            return true;
-        }
-
         // Check if alias is too short (likely to conflict)
         if alias.len() < 2 {
             return true;
         }
 
-        false
+        // Executables, builtins, reserved words, rc aliases and functions, git aliases and names
+        // the shell can't define
+        self.conflicts.conflict(alias).is_some()
     }
 
-    fn get_priority(&self, suggestion: &AliasSuggestion) -> i32 {
-        let mut priority = 0;
-        
+    // The generator's priority with the length and style bonuses first, so a rule or template
+    // beats a shorter alias that only happens to be short. Keystrokes saved a day (or per run,
+    // without history) only break ties.
+    fn get_priority(&self, suggestion: &AliasSuggestion) -> (i32, f64, i64) {
+        let (daily, per_run) = match self.savings {
+            Some((tree, now)) => {
+                let savings = estimate(&suggestion.command, &suggestion.alias, tree, now);
+                (savings.daily_keystrokes, savings.per_run)
+            }
+            None => (0.0, saved_per_run(&suggestion.command, &suggestion.alias)),
+        };
+        (self.priority_breakdown(suggestion).total(), daily, per_run)
+    }
+
+    // The ranking part of get_priority, so `alman explain` can show why a suggestion ranks where it does
+    pub fn priority_breakdown(&self, suggestion: &AliasSuggestion) -> PriorityBreakdown {
+        let (base, category) = self.base_priority(&suggestion.reason);
+        PriorityBreakdown {
+            category,
+            base,
+            // Higher priority for shorter aliases within the same type
+            length_bonus: 10 - suggestion.alias.len() as i32,
+            style_bonus: self.style().bonus(category, &suggestion.alias, &suggestion.command),
+        }
+    }
+
+    fn base_priority(&self, reason: &str) -> (i32, &'static str) {
+        // User templates carry their own priority
+        if let Some(template) = self.templates.iter().find(|t| t.reason() == reason) {
+            return (template.priority(), "user template");
+        }
+
         // Priority based on suggestion type (higher number = higher priority)
-        match suggestion.reason.as_str() {
+        match reason {
+            // Combos of commands usually run together, the subcommand based name reads best
+            reason if reason.starts_with("Combo") && reason.ends_with("sequence") => (98, "combo named after its subcommands"),
+            "Combo step initials" => (95, "combo of step initials"),
+            "Combo tool initials" => (90, "combo of tool initials"),
             // Semantic aliases (tool-specific) - highest priority
+            reason if reason.starts_with("Rule:") => (100, "tool-specific semantic alias"),
             reason if reason.contains("Git") || reason.contains("Docker") || reason.contains("NPM") || reason.contains("SSH") => {
-                priority += 100;
+                (100, "tool-specific semantic alias")
             }
             // Abbreviation aliases - second priority
-            "Abbreviation" => {
-                priority += 90;
-            }
+            "Abbreviation" => (90, "abbreviation"),
             // Vowel removal - third priority
-            "Vowel Removal" => {
-                priority += 80;
-            }
+            "Vowel Removal" => (80, "vowel removal"),
             // Combined aliases - fourth priority
-            reason if reason.contains("combination") => {
-                priority += 70;
-            }
+            reason if reason.contains("combination") => (70, "tool and subcommand combination"),
             // Syllable-based - fifth priority
-            "Syllable-based" => {
-                priority += 65;
-            }
+            "Syllable-based" => (65, "syllable-based"),
             // Smart prefixes/suffixes - sixth priority
-            reason if reason.contains("Remove prefix") || reason.contains("Remove suffix") => {
-                priority += 60;
-            }
+            reason if reason.contains("Remove prefix") || reason.contains("Remove suffix") => (60, "prefix/suffix removal"),
             // Single word aliases - seventh priority
             reason if reason.contains("abbreviation") || reason.contains("First-last") || reason.contains("LazyGit") || reason.contains("Docker") || reason.contains("Node") => {
-                priority += 55;
+                (55, "single word alias")
             }
             // Phonetic aliases - eighth priority
-            "Phonetic" => {
-                priority += 50;
-            }
+            "Phonetic" => (50, "phonetic"),
             // Common patterns - ninth priority
-            reason if reason.contains("Remove duplicates") || reason.contains("Smart consonants") => {
-                priority += 45;
-            }
+            reason if reason.contains("Remove duplicates") || reason.contains("Smart consonants") => (45, "common pattern"),
             // Keyboard patterns - tenth priority
-            "Keyboard pattern" => {
-                priority += 40;
-            }
+            "Keyboard pattern" => (40, "keyboard pattern"),
             // Truncated aliases - lowest priority
-            reason if reason.contains("Truncated") => {
-                priority += 35;
-            }
-            _ => {
-                priority += 30; // Default priority for unknown types
-            }
+            reason if reason.contains("Truncated") => (35, "truncation"),
+            _ => (30, "other"), // Default priority for unknown types
         }
-        
-        // Higher priority for shorter aliases within the same type
-        priority += 10 - suggestion.alias.len() as i32;
-        
-        priority
     }
 
     fn generate_syllable_aliases(&self, command: &str) -> Vec<AliasSuggestion> {
@@ -741,8 +663,7 @@ impl AliasSuggester {
                 // Common prefixes
                 let prefixes = ["un", "re", "pre", "post", "anti", "pro", "sub", "super", "inter"];
                 for prefix in &prefixes {
-                    if word.starts_with(prefix) {
-                        let without_prefix = &word[prefix.len()..];
+                    if let Some(without_prefix) = word.strip_prefix(prefix) {
                         if without_prefix.len() >= 2 {
                             suggestions.push(AliasSuggestion {
                                 alias: without_prefix.to_string(),
@@ -756,8 +677,7 @@ impl AliasSuggester {
                 // Common suffixes
                 let suffixes = ["ing", "ed", "er", "est", "ly", "tion", "sion", "ment"];
                 for suffix in &suffixes {
-                    if word.ends_with(suffix) {
-                        let without_suffix = &word[..word.len() - suffix.len()];
+                    if let Some(without_suffix) = word.strip_suffix(suffix) {
                         if without_suffix.len() >= 2 {
                             suggestions.push(AliasSuggestion {
                                 alias: without_suffix.to_string(),
@@ -860,3 +780,66 @@ impl AliasSuggester {
             .collect()
     }
 } 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::suggestion_templates::SuggestionTemplate;
+
+    fn suggester(aliases: &[(&str, &str)], templates: Vec<SuggestionTemplate>) -> AliasSuggester<'static> {
+        let aliases: Aliases = aliases.iter().map(|(a, c)| (a.to_string(), c.to_string())).collect();
+        AliasSuggester {
+            existing_aliases: aliases.iter().map(|(alias, _)| alias.clone()).collect(),
+            aliases,
+            tool_rules: ToolRules::load(),
+            templates: templates.into_iter().map(|t| CompiledTemplate::new(t).unwrap()).collect(),
+            style: OnceCell::new(),
+            conflicts: ConflictChecker::default(),
+            savings: None,
+        }
+    }
+
+    fn template(tool: &str, alias: &str, priority: i32) -> SuggestionTemplate {
+        SuggestionTemplate {
+            tool: Some(tool.to_string()),
+            subcommand: None,
+            regex: None,
+            alias: alias.to_string(),
+            priority,
+            description: None,
+        }
+    }
+
+    fn top(suggester: &AliasSuggester, command: &str) -> AliasSuggestion {
+        suggester.suggest_aliases(command).remove(0)
+    }
+
+    #[test]
+    fn rule_aliases_come_before_shorter_ones() {
+        let suggester = suggester(&[], Vec::new());
+        assert_eq!(top(&suggester, "git checkout").alias, "gco");
+        assert_eq!(top(&suggester, "cargo build --release").alias, "cbr");
+        assert_eq!(top(&suggester, "docker compose up -d").alias, "dcud");
+    }
+
+    #[test]
+    fn template_priority_beats_truncation() {
+        let suggestions = suggester(&[], vec![template("rsync", "sync-{initials}", 150)]).suggest_aliases("rsync -av src dest");
+        assert_eq!(suggestions[0].alias, "sync-sd");
+        let truncated = suggestions.iter().position(|s| s.reason.contains("Truncat")).unwrap();
+        assert!(truncated > 0);
+
+        // Priority decides, not length: a low one goes below the built-in generators
+        let suggestions = suggester(&[], vec![template("rsync", "r{initials}", 10)]).suggest_aliases("rsync -av src dest");
+        assert_eq!(suggestions.last().unwrap().alias, "rsd");
+    }
+
+    #[test]
+    fn style_bonus_reorders_aliases() {
+        let plain = suggester(&[], Vec::new());
+        assert_ne!(top(&plain, "tmux attach").reason, "Vowel Removal");
+
+        let styled = suggester(&[("tmxls", "tmux ls"), ("tmxnw", "tmux new"), ("tmxkll", "tmux kill-server")], Vec::new());
+        assert_eq!(top(&styled, "tmux attach").alias, "tmxttc");
+    }
+}
@@ -0,0 +1,132 @@
+// `alman change` and the TUI's change flow: rename an alias and/or edit its command where it
+// is defined, without moving it to another file or rewriting the lines around it.
+use crate::database::database_structs::{Command, Database, DeletedCommands};
+use crate::ops::add_alias::{hide_aliased_command, unhide_aliased_command};
+use crate::ops::alias_ops::{alias_line, get_alias_definitions, AliasDefinition};
+use crate::ops::shell_conflicts::ConflictChecker;
+
+// How many recorded commands the editor offers on Up/Down
+const HISTORY_LEN: usize = 50;
+
+pub struct ChangeOutcome {
+    pub old_alias: String,
+    pub old_command: String,
+    pub alias: String,
+    pub command: String,
+    pub file: String,
+    // Set when the new name is also something else, like with `alman add`
+    pub warning: Option<String>,
+}
+
+// The definition the shell uses, the last one when a name is defined twice
+pub fn find_alias(file_paths: &[String], alias: &str) -> Option<AliasDefinition> {
+    get_alias_definitions(file_paths).into_iter().rev().find(|d| d.alias == alias)
+}
+
+// Recorded commands that start like `command`, most used last so Up reaches them first
+pub fn edit_history<'a>(commands: impl IntoIterator<Item = &'a Command>, command: &str) -> Vec<String> {
+    let first_word = command.split_whitespace().next().unwrap_or("");
+    let mut related: Vec<&Command> = commands
+        .into_iter()
+        .filter(|c| c.command_text != command && c.command_text.split_whitespace().next() == Some(first_word))
+        .collect();
+    related.sort_by_key(|c| std::cmp::Reverse(c.frequency));
+    related.into_iter().take(HISTORY_LEN).rev().map(|c| c.command_text.clone()).collect()
+}
+
+// What can't be written as `alias x='...'`, or isn't valid shell
+pub fn check_command(command: &str) -> Result<(), String> {
+    if command.trim().is_empty() {
+        return Err("the command can't be empty".to_string());
+    }
+    if command.contains('\'') {
+        return Err("single quotes can't be written to an alias file yet, use double quotes".to_string());
+    }
+    if command.contains('\n') {
+        return Err("the command has to fit on one line".to_string());
+    }
+    // `sh -n` only parses; if there's no sh to ask, there's nothing to check against
+    let checked = std::process::Command::new("sh")
+        .args(["-n", "-c", command])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .output();
+    match checked {
+        Ok(output) if !output.status.success() => {
+            let message = String::from_utf8_lossy(&output.stderr);
+            // "sh: 1: Syntax error: ..." in dash, "sh: -c: line 1: ..." in bash
+            let message = message.lines().last().unwrap_or("").trim().trim_start_matches("sh: ");
+            let message = message.trim_start_matches("-c: ").trim_start_matches(|c: char| c.is_ascii_digit() || c == ':' || c == ' ');
+            Err(format!("not valid shell: {}", message))
+        }
+        _ => Ok(()),
+    }
+}
+
+// Rewrites the alias's line in its file. `new_alias`/`command` of None keep what's there.
+pub fn change_alias(
+    db: &mut Database,
+    deleted_commands: &mut DeletedCommands,
+    file_paths: &[String],
+    old_alias: &str,
+    new_alias: Option<&str>,
+    command: Option<&str>,
+) -> Result<ChangeOutcome, Box<dyn std::error::Error>> {
+    let definition = find_alias(file_paths, old_alias).ok_or_else(|| format!("No alias named '{}'", old_alias))?;
+    let alias = new_alias.map(str::trim).unwrap_or(old_alias).to_string();
+    let command = command.map(str::trim).unwrap_or(&definition.command).to_string();
+    if alias.is_empty() {
+        return Err("the alias name can't be empty".into());
+    }
+
+    let mut warning = None;
+    if alias != old_alias {
+        if let Some(taken) = get_alias_definitions(file_paths).into_iter().find(|d| d.alias == alias) {
+            return Err(format!("'{}' is already an alias in {}:{}", alias, taken.file, taken.line).into());
+        }
+        warning = ConflictChecker::load().conflict(&alias).map(|conflict| format!("'{}' {}", alias, conflict));
+    }
+    if command != definition.command {
+        check_command(&command)?;
+    }
+
+    replace_line(&definition, &alias, &command)?;
+
+    // The new command is aliased now. The old one can be suggested again, unless another alias
+    // still stands for it or it had been deleted before it was aliased.
+    if command != definition.command {
+        let still_aliased = get_alias_definitions(file_paths)
+            .iter()
+            .any(|d| d.command == definition.command && !(d.file == definition.file && d.line == definition.line));
+        if !still_aliased {
+            unhide_aliased_command(deleted_commands, &definition.command);
+        }
+        hide_aliased_command(db, deleted_commands, &command);
+    }
+
+    Ok(ChangeOutcome {
+        old_alias: old_alias.to_string(),
+        old_command: definition.command,
+        alias,
+        command,
+        file: definition.file,
+        warning,
+    })
+}
+
+// Only the definition's own line changes, comments, line endings and everything else stay as
+// they are
+fn replace_line(definition: &AliasDefinition, alias: &str, command: &str) -> std::io::Result<()> {
+    let content = std::fs::read_to_string(&definition.file)?;
+    let mut updated = String::with_capacity(content.len());
+    for (i, line) in content.split_inclusive('\n').enumerate() {
+        if i + 1 == definition.line {
+            let text = line.trim_end_matches(['\r', '\n']);
+            updated.push_str(alias_line(alias, command).trim_end());
+            updated.push_str(&line[text.len()..]);
+        } else {
+            updated.push_str(line);
+        }
+    }
+    std::fs::write(&definition.file, updated)
+}
@@ -0,0 +1,262 @@
+// Every name on this machine an alias could collide with: executables on PATH and what the
+// rc files define. Reading it all takes a while, so it is cached in ~/.alman/command_index.json
+// and only rebuilt when PATH, a PATH directory or one of the files it came from changes.
+use crate::database::database_structs::CommandIndex;
+use crate::database::persistence::{get_command_index_path, load_command_index, save_command_index};
+use crate::ops::shell_conflicts::Shell;
+use regex::Regex;
+use std::collections::{BTreeSet, HashSet};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+pub fn load_index(shells: &[Shell]) -> CommandIndex {
+    let path_var = std::env::var("PATH").unwrap_or_default();
+    let shell_names: Vec<String> = shells.iter().map(|s| s.name().to_string()).collect();
+    let index_path = get_command_index_path();
+
+    if let Ok(index) = load_command_index(&index_path) {
+        let fresh = !index.sources.is_empty()
+            && index.path == path_var
+            && index.shells == shell_names
+            && index.sources.iter().all(|(source, modified)| mtime(Path::new(source)) == *modified);
+        if fresh {
+            return index;
+        }
+    }
+
+    let index = build_index(path_var, shell_names, shells);
+    if let Err(e) = save_command_index(&index, &index_path) {
+        eprintln!("Failed to save command index: {}", e);
+    }
+    index
+}
+
+fn build_index(path_var: String, shell_names: Vec<String>, shells: &[Shell]) -> CommandIndex {
+    let mut index = CommandIndex {
+        path: path_var,
+        shells: shell_names,
+        ..Default::default()
+    };
+
+    for dir in index.path.clone().split(':').filter(|dir| !dir.is_empty()) {
+        index.sources.insert(dir.to_string(), mtime(Path::new(dir)));
+        if let Ok(entries) = std::fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                // Follows symlinks, most of /usr/bin is one
+                let executable = std::fs::metadata(entry.path())
+                    .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+                    .unwrap_or(false);
+                if executable {
+                    index.executables.insert(entry.file_name().to_string_lossy().to_string());
+                }
+            }
+        }
+    }
+
+    let parser = RcParser::new();
+    let mut pending: Vec<(PathBuf, Shell)> = Vec::new();
+    for shell in shells {
+        pending.extend(rc_files(*shell).into_iter().rev().map(|file| (file, *shell)));
+        // New autoloaded fish functions show up as a changed directory
+        for dir in rc_dirs(*shell) {
+            index.sources.insert(dir.to_string_lossy().to_string(), mtime(&dir));
+        }
+    }
+    // Alman's own alias files are usually sourced from an rc file, and what's in them isn't
+    // in the way of alman, it's alman's. Changing them in the config means a rebuild.
+    let own_files = tracked_alias_files();
+    let config_path = crate::database::persistence::get_config_path();
+    index.sources.insert(config_path.clone(), mtime(Path::new(&config_path)));
+    let mut seen = HashSet::new();
+    while let Some((file, shell)) = pending.pop() {
+        if !seen.insert(file.clone()) {
+            continue;
+        }
+        let name = file.to_string_lossy().to_string();
+        index.sources.insert(name.clone(), mtime(&file));
+        let content = match std::fs::read_to_string(&file) {
+            Ok(content) => content,
+            Err(_) => continue,
+        };
+        let parsed = parser.parse(&content, shell);
+        for alias in parsed.aliases {
+            index.shell_aliases.entry(alias).or_insert_with(|| name.clone());
+        }
+        for function in parsed.functions {
+            index.functions.entry(function).or_insert_with(|| name.clone());
+        }
+        // Autoloaded fish functions are named after their file
+        if shell == Shell::Fish && file.parent().and_then(|p| p.file_name()).is_some_and(|d| d == "functions") {
+            if let Some(stem) = file.file_stem() {
+                index.functions.entry(stem.to_string_lossy().to_string()).or_insert_with(|| name.clone());
+            }
+        }
+        pending.extend(
+            parsed
+                .sourced
+                .into_iter()
+                .filter(|file| !own_files.contains(&file.canonicalize().unwrap_or_else(|_| file.clone())))
+                .map(|file| (file, shell)),
+        );
+    }
+
+    for file in git_config_files() {
+        index.sources.insert(file.to_string_lossy().to_string(), mtime(&file));
+    }
+    index.git_aliases = load_git_aliases();
+    index
+}
+
+// The alias files alman writes to, resolved so they match however an rc file refers to them
+fn tracked_alias_files() -> HashSet<PathBuf> {
+    let paths = crate::database::persistence::load_config()
+        .map(|config| config.alias_file_paths)
+        .filter(|paths| !paths.is_empty())
+        .unwrap_or_else(|| vec![crate::database::persistence::get_default_alias_file_path()]);
+    paths
+        .iter()
+        .filter_map(|path| expand_path(path))
+        .map(|path| path.canonicalize().unwrap_or(path))
+        .collect()
+}
+
+// Modification time in nanoseconds, 0 when the file doesn't exist
+fn mtime(path: &Path) -> i64 {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos() as i64)
+        .unwrap_or(0)
+}
+
+fn rc_files(shell: Shell) -> Vec<PathBuf> {
+    let home = match dirs::home_dir() {
+        Some(home) => home,
+        None => return Vec::new(),
+    };
+    match shell {
+        Shell::Sh => vec![home.join(".profile")],
+        Shell::Bash => [".bashrc", ".bash_profile", ".bash_aliases", ".bash_functions", ".profile"]
+            .iter()
+            .map(|f| home.join(f))
+            .collect(),
+        Shell::Zsh => {
+            let zdotdir = std::env::var("ZDOTDIR").map(PathBuf::from).unwrap_or_else(|_| home.clone());
+            [".zshrc", ".zshenv", ".zprofile", ".zlogin"].iter().map(|f| zdotdir.join(f)).collect()
+        }
+        Shell::Fish => {
+            let mut files = vec![fish_config_dir().join("config.fish")];
+            for dir in rc_dirs(shell) {
+                if let Ok(entries) = std::fs::read_dir(dir) {
+                    files.extend(entries.flatten().map(|e| e.path()).filter(|p| p.extension().is_some_and(|ext| ext == "fish")));
+                }
+            }
+            files.sort();
+            files
+        }
+    }
+}
+
+fn rc_dirs(shell: Shell) -> Vec<PathBuf> {
+    match shell {
+        Shell::Fish => vec![fish_config_dir().join("functions"), fish_config_dir().join("conf.d")],
+        _ => Vec::new(),
+    }
+}
+
+fn fish_config_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| dirs::home_dir().unwrap_or_default().join(".config"))
+        .join("fish")
+}
+
+struct RcParser {
+    sh_alias: Regex,
+    fish_alias: Regex,
+    sh_function: Regex,
+    fish_function: Regex,
+    source: Regex,
+}
+
+#[derive(Default)]
+struct ParsedRc {
+    aliases: Vec<String>,
+    functions: Vec<String>,
+    sourced: Vec<PathBuf>,
+}
+
+impl RcParser {
+    fn new() -> Self {
+        Self {
+            sh_alias: Regex::new(r"^\s*alias\s+(?:-g\s+)?([^\s=]+)=").expect("valid alias pattern"),
+            // `alias name cmd`, `alias name=cmd` and `abbr -a name cmd`
+            fish_alias: Regex::new(r"^\s*(?:alias\s+([^\s=]+)|abbr\s+(?:-a|--add)\s+(?:-g\s+)?([^\s=]+))").expect("valid alias pattern"),
+            // `name() {` and `function name {`
+            sh_function: Regex::new(r"^\s*(?:function\s+([^\s(){};]+)|([A-Za-z_][\w.:-]*)\s*\(\s*\))").expect("valid function pattern"),
+            fish_function: Regex::new(r"^\s*function\s+([^\s;]+)").expect("valid function pattern"),
+            source: Regex::new(r#"^\s*(?:source|\.)\s+["']?([^\s"';]+)"#).expect("valid source pattern"),
+        }
+    }
+
+    fn parse(&self, content: &str, shell: Shell) -> ParsedRc {
+        let (alias, function) = match shell {
+            Shell::Fish => (&self.fish_alias, &self.fish_function),
+            _ => (&self.sh_alias, &self.sh_function),
+        };
+        let first_group = |c: regex::Captures| c.get(1).or_else(|| c.get(2)).map(|m| m.as_str().to_string());
+
+        let mut parsed = ParsedRc::default();
+        for line in content.lines() {
+            if let Some(name) = alias.captures(line).and_then(first_group) {
+                parsed.aliases.push(name);
+            } else if let Some(name) = function.captures(line).and_then(first_group) {
+                parsed.functions.push(name);
+            } else if let Some(file) = self.source.captures(line).and_then(first_group) {
+                parsed.sourced.extend(expand_path(&file));
+            }
+        }
+        parsed
+    }
+}
+
+// ~ and $HOME are expanded; paths that need anything else from the shell are skipped
+fn expand_path(path: &str) -> Option<PathBuf> {
+    let home = dirs::home_dir()?;
+    let home_str = home.to_string_lossy();
+    let expanded = if let Some(rest) = path.strip_prefix("~/") {
+        format!("{}/{}", home_str, rest)
+    } else {
+        path.replace("${HOME}", &home_str).replace("$HOME", &home_str)
+    };
+    if expanded.contains('$') || expanded.contains('`') {
+        return None;
+    }
+    let expanded = PathBuf::from(expanded);
+    Some(if expanded.is_absolute() { expanded } else { home.join(expanded) })
+}
+
+fn git_config_files() -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    if let Some(home) = dirs::home_dir() {
+        files.push(home.join(".gitconfig"));
+    }
+    if let Some(config) = dirs::config_dir() {
+        files.push(config.join("git").join("config"));
+    }
+    files
+}
+
+// The one place git aliases are read, the conflict checker gets them through the index
+pub fn load_git_aliases() -> BTreeSet<String> {
+    let output = match std::process::Command::new("git").args(["config", "--global", "--get-regexp", r"^alias\."]).output() {
+        Ok(output) => output,
+        Err(_) => return BTreeSet::new(),
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .filter_map(|key| key.strip_prefix("alias."))
+        .map(|name| name.to_string())
+        .collect()
+}
@@ -0,0 +1,110 @@
+use crate::database::database_structs::{Database, SequenceStats, SequenceStep};
+use crate::ops::parse_command::parse_command_line;
+
+// Commands more than this far apart are not treated as one workflow
+const COMBO_WINDOW_SECS: i64 = 300;
+// How many times a sequence has to be seen before it is offered as a combo
+const MIN_COMBO_COUNT: i32 = 3;
+// Longest sequence considered, in commands
+const MAX_COMBO_LENGTH: usize = 3;
+// Sessions idle for this long are forgotten
+const SESSION_TTL_SECS: i64 = 24 * 60 * 60;
+// Sequences that never made it to a combo are dropped after this long
+const NGRAM_TTL_SECS: i64 = 30 * 24 * 60 * 60;
+
+pub const COMBO_SEPARATOR: &str = " && ";
+
+// The shell that ran us, so interleaved terminals don't mix their sequences. The init scripts
+// export ALMAN_SESSION, the parent pid covers shells set up without them.
+pub fn current_session_id() -> String {
+    std::env::var("ALMAN_SESSION").unwrap_or_else(|_| std::os::unix::process::parent_id().to_string())
+}
+
+pub fn is_combo(command: &str) -> bool {
+    command.contains(COMBO_SEPARATOR)
+}
+
+// Records `command_line` as the next command of `session`. Every 2 and 3 command sequence that
+// ends with it and fits in the time window is counted, and once one has been seen often enough
+// it goes into the database as a combo like `git add -A && git commit && git push`.
+pub fn record_sequence(
+    session: &str,
+    command_line: &str,
+    timestamp: i64,
+    stats: &mut SequenceStats,
+    db: &mut Database,
+) {
+    let command = command_line.split_whitespace().collect::<Vec<_>>().join(" ");
+    if command.is_empty() || is_own_command(&command) {
+        return;
+    }
+    // Lines that already chain commands were typed as one, they're not part of a sequence
+    match parse_command_line(&command) {
+        // A subshell stage running several commands, `(cd src && make)`, chains them too
+        Ok(pipelines) if pipelines.len() == 1 && pipelines[0].commands.len() == pipelines[0].stages.len() => {}
+        _ => return,
+    }
+
+    prune(stats, timestamp);
+
+    let steps = stats.sessions.entry(session.to_string()).or_default();
+    // Running the same thing twice in a row (ls, ls) doesn't make a sequence
+    if let Some(last) = steps.last_mut() {
+        if last.command == command {
+            last.timestamp = timestamp;
+            return;
+        }
+    }
+
+    // Walk back while the gaps stay inside the window
+    let mut chain = vec![command.clone()];
+    let mut next_timestamp = timestamp;
+    for step in steps.iter().rev() {
+        if next_timestamp - step.timestamp > COMBO_WINDOW_SECS || chain.len() == MAX_COMBO_LENGTH {
+            break;
+        }
+        if chain.contains(&step.command) {
+            break;
+        }
+        chain.insert(0, step.command.clone());
+        next_timestamp = step.timestamp;
+    }
+
+    steps.push(SequenceStep { timestamp, command });
+    // Only the tail is needed to extend a sequence
+    if steps.len() >= MAX_COMBO_LENGTH {
+        let excess = steps.len() + 1 - MAX_COMBO_LENGTH;
+        steps.drain(..excess);
+    }
+
+    for start in 0..chain.len().saturating_sub(1) {
+        let combo = chain[start..].join(COMBO_SEPARATOR);
+        let ngram = stats.ngrams.entry(combo.clone()).or_default();
+        ngram.count += 1;
+        ngram.last_seen = timestamp;
+        if ngram.count == MIN_COMBO_COUNT {
+            // Catch up on the runs seen before it qualified
+            for _ in 0..MIN_COMBO_COUNT {
+                db.add_command_at(combo.clone(), timestamp);
+            }
+        } else if ngram.count > MIN_COMBO_COUNT {
+            db.add_command_at(combo, timestamp);
+        }
+    }
+}
+
+fn prune(stats: &mut SequenceStats, now: i64) {
+    stats.sessions.retain(|_, steps| steps.last().map(|s| now - s.timestamp < SESSION_TTL_SECS).unwrap_or(false));
+    stats.ngrams.retain(|_, ngram| ngram.count >= MIN_COMBO_COUNT || now - ngram.last_seen < NGRAM_TTL_SECS);
+}
+
+fn is_own_command(command: &str) -> bool {
+    let binary_name = std::env::args()
+        .next()
+        .and_then(|path| std::path::Path::new(&path).file_name().map(|f| f.to_os_string()))
+        .and_then(|os_str| os_str.into_string().ok());
+    match (binary_name, command.split_whitespace().next()) {
+        (Some(name), Some(first)) => first == name,
+        _ => false,
+    }
+}
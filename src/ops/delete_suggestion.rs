@@ -1,5 +1,5 @@
-use crate::database::database_structs::{Database,DeletedCommands};
+use crate::database::database_structs::DeletedCommands;
 
-pub fn delete_suggestion(alias: &str, db: &mut Database,deleted_commands: &mut DeletedCommands) {
-    db.remove_command(&alias.to_string(), deleted_commands);
+pub fn delete_suggestion(alias: &str, deleted_commands: &mut DeletedCommands) {
+    deleted_commands.delete(alias);
 }
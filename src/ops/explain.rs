@@ -0,0 +1,157 @@
+use crate::database::command_tree::CommandTree;
+use crate::database::database_structs::{Command, Database, DeletedCommands};
+use crate::database::scoring::{configured_scorer, now, ScoreComponent};
+use crate::ops::alias_suggestions::{AliasSuggester, AliasSuggestion, PriorityBreakdown};
+use crate::ops::ignored::IgnoreMatcher;
+use crate::ops::keystroke_savings::{estimate, SavingsEstimate};
+use crate::ops::normalize_command::{alias_target, normalize_command};
+use colored::*;
+use serde::Serialize;
+
+// How many longer commands to list under "extended by"
+const MAX_EXTENSIONS: usize = 5;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Explanation {
+    pub command: Command,
+    pub scorer: &'static str,
+    pub score: i32,
+    pub components: Vec<ScoreComponent>,
+    // Position among the suggested commands, 1 based. None when a longer or shorter form of
+    // the command ranks in its place.
+    pub rank: Option<usize>,
+    pub total_commands: usize,
+    // Shorter commands recorded alongside this one, e.g. `git` and `git add` for `git add .`
+    pub prefixes: Vec<(String, i32)>,
+    // Longer commands that also counted towards this one
+    pub extensions: Vec<(String, i32)>,
+    pub template: Option<String>,
+    pub suggestions: Vec<RankedAlias>,
+}
+
+impl Explanation {
+    pub fn rank_text(&self) -> String {
+        match self.rank {
+            Some(rank) => format!("{} of {} ({} scorer)", rank, self.total_commands, self.scorer),
+            None => format!("not ranked, a longer or shorter form of it is suggested instead ({} scorer)", self.scorer),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RankedAlias {
+    pub suggestion: AliasSuggestion,
+    pub priority: PriorityBreakdown,
+    pub savings: SavingsEstimate,
+}
+
+pub fn explain_command(
+    command_text: &str,
+    db: &Database,
+    deleted_commands: &DeletedCommands,
+    alias_file_path: &str,
+) -> Result<Explanation, String> {
+    let command_text = command_text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if let Some(reason) = IgnoreMatcher::new(deleted_commands, now()).reason(&command_text) {
+        return Err(format!("'{}' {}, see `alman ignored list`", command_text, reason));
+    }
+    let tree = CommandTree::build(db);
+    let command = match tree.lookup(&command_text) {
+        Some(command) => command,
+        None => return Err(format!("'{}' is not in the command database", command_text)),
+    };
+
+    let scorer = configured_scorer();
+    let ranked = tree.select(scorer.as_ref(), now(), deleted_commands);
+    let rank = ranked.iter().position(|c| c.command_text == command_text).map(|i| i + 1);
+    let score = scorer.score(&command, now());
+
+    let prefixes = tree
+        .ancestors(&command_text)
+        .into_iter()
+        .map(|node| (node.text.clone(), node.frequency))
+        .collect();
+
+    let mut extensions: Vec<(String, i32)> = tree
+        .node(&command_text)
+        .map(|node| node.descendants())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|node| (node.text.clone(), node.frequency))
+        .collect();
+    extensions.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    extensions.truncate(MAX_EXTENSIONS);
+
+    let suggester = AliasSuggester::new(alias_file_path).with_savings(&tree, now());
+    let target = alias_target(&command_text);
+    let suggestions = suggester
+        .suggest_aliases(&target)
+        .into_iter()
+        .map(|suggestion| RankedAlias {
+            priority: suggester.priority_breakdown(&suggestion),
+            savings: estimate(&target, &suggestion.alias, &tree, now()),
+            suggestion,
+        })
+        .collect();
+
+    Ok(Explanation {
+        components: scorer.explain(&command, now()),
+        scorer: scorer.name(),
+        score,
+        rank,
+        total_commands: ranked.len(),
+        prefixes,
+        extensions,
+        template: normalize_command(&command_text).filter(|t| db.reverse_command_map.contains_key(t)),
+        suggestions,
+        command,
+    })
+}
+
+pub fn print_explanation(explanation: &Explanation) {
+    println!("{} {}", "Command:".cyan(), explanation.command.command_text.bold());
+    println!("{} {}", "Rank:".cyan(), explanation.rank_text());
+    println!("{} {}", "Score:".cyan(), explanation.score.to_string().yellow());
+    let label_width = explanation.components.iter().map(|c| c.label.len()).max().unwrap_or(0);
+    for component in &explanation.components {
+        println!("  {:<width$}  {}", component.label, component.value, width = label_width);
+    }
+    if explanation.command.decays > 0 {
+        println!("  Frequency halved {} time(s) by score resets", explanation.command.decays);
+    }
+
+    let format_counts = |commands: &[(String, i32)]| {
+        commands.iter().map(|(text, freq)| format!("{} ({})", text, freq)).collect::<Vec<_>>().join(", ")
+    };
+    if !explanation.prefixes.is_empty() {
+        println!("{} {}", "Prefixes:".cyan(), format_counts(&explanation.prefixes));
+    }
+    if !explanation.extensions.is_empty() {
+        println!("{} {}", "Extended by:".cyan(), format_counts(&explanation.extensions));
+    }
+    if let Some(template) = &explanation.template {
+        println!("{} {}", "Also counted as:".cyan(), template);
+    }
+
+    if explanation.suggestions.is_empty() {
+        println!("{}", "No alias suggestions.".yellow());
+        return;
+    }
+    println!("{}", "Alias suggestions:".cyan());
+    let alias_width = explanation.suggestions.iter().map(|s| s.suggestion.alias.len()).max().unwrap_or(0);
+    for (i, ranked) in explanation.suggestions.iter().take(10).enumerate() {
+        println!(
+            "  {:>2}. {:<width$}  {:>3} = {}  ({})",
+            i + 1,
+            ranked.suggestion.alias.green(),
+            ranked.priority.total(),
+            ranked.priority.describe(),
+            ranked.suggestion.reason,
+            width = alias_width
+        );
+        println!(
+            "      saves {} keystroke(s) per run x {:.1} run(s)/day = ~{:.0}/day",
+            ranked.savings.per_run, ranked.savings.daily_runs, ranked.savings.daily_keystrokes
+        );
+    }
+}
@@ -0,0 +1,105 @@
+// Fuzzy matching for `alman search` and the TUI filters: the query's characters have to show up in order, and
+// matches that start words or run together score higher, so `gcb` finds `git checkout -b`.
+// Case is ignored.
+
+const MATCH: i32 = 1;
+const RUN_BONUS: i32 = 8;
+const WORD_START_BONUS: i32 = 6;
+const FIRST_CHAR_BONUS: i32 = 4;
+const MAX_GAP_PENALTY: i32 = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch {
+    pub score: i32,
+    // Char indices into the text that matched, for highlighting
+    pub positions: Vec<usize>,
+}
+
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let query: Vec<char> = query.to_lowercase().chars().filter(|c| !c.is_whitespace()).collect();
+    if query.is_empty() {
+        return Some(FuzzyMatch { score: 0, positions: Vec::new() });
+    }
+    let text: Vec<char> = text.chars().flat_map(|c| c.to_lowercase().next()).collect();
+
+    // Greedy from every place the first character shows up, keep the best
+    let mut best: Option<FuzzyMatch> = None;
+    for start in (0..text.len()).filter(|&i| text[i] == query[0]) {
+        if let Some(found) = match_from(&query, &text, start) {
+            if best.as_ref().map(|b| found.score > b.score).unwrap_or(true) {
+                best = Some(found);
+            }
+        }
+    }
+    best
+}
+
+// For the TUI filters: every whitespace separated term has to match one of `fields`, so
+// `git -m` narrows like fzf does. Returns the summed score and the matched char indices in
+// each field.
+pub fn fuzzy_match_terms(query: &str, fields: &[&str]) -> Option<(i32, Vec<Vec<usize>>)> {
+    let mut score = 0;
+    let mut positions = vec![Vec::new(); fields.len()];
+    for term in query.split_whitespace() {
+        let (field, found) = fields
+            .iter()
+            .enumerate()
+            .filter_map(|(i, text)| fuzzy_match(term, text).map(|m| (i, m)))
+            .max_by_key(|(_, m)| m.score)?;
+        score += found.score;
+        positions[field].extend(found.positions);
+    }
+    for field_positions in &mut positions {
+        field_positions.sort_unstable();
+        field_positions.dedup();
+    }
+    Some((score, positions))
+}
+
+fn match_from(query: &[char], text: &[char], start: usize) -> Option<FuzzyMatch> {
+    let mut positions = vec![start];
+    let mut i = start + 1;
+    for (k, &wanted) in query.iter().enumerate().skip(1) {
+        let next = (i..text.len()).find(|&j| text[j] == wanted)?;
+        // A match at the start of a word beats an earlier one in the middle of a word, as
+        // long as the rest of the query still fits after it
+        let at_word_start = (next..text.len())
+            .find(|&j| text[j] == wanted && is_word_start(text, j))
+            .filter(|&j| is_subsequence(&query[k + 1..], &text[j + 1..]));
+        let chosen = match at_word_start {
+            Some(j) if next != i && !is_word_start(text, next) => j,
+            _ => next,
+        };
+        positions.push(chosen);
+        i = chosen + 1;
+    }
+
+    let mut score = 0;
+    for (k, &pos) in positions.iter().enumerate() {
+        score += MATCH;
+        if is_word_start(text, pos) {
+            score += WORD_START_BONUS;
+        }
+        if k > 0 {
+            let gap = (pos - positions[k - 1] - 1) as i32;
+            if gap == 0 {
+                score += RUN_BONUS;
+            } else {
+                score -= gap.min(MAX_GAP_PENALTY);
+            }
+        }
+    }
+    if start == 0 {
+        score += FIRST_CHAR_BONUS;
+    }
+    Some(FuzzyMatch { score, positions })
+}
+
+fn is_word_start(text: &[char], i: usize) -> bool {
+    i == 0 || !text[i - 1].is_alphanumeric()
+}
+
+fn is_subsequence(query: &[char], text: &[char]) -> bool {
+    let mut chars = text.iter();
+    query.iter().all(|q| chars.any(|c| c == q))
+}
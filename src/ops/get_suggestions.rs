@@ -1,28 +1,277 @@
-use crate::database::database_structs::{Command, Database};
-use crate::ops::alias_suggestions::{AliasSuggester, AliasSuggestion};
-
-#[derive(Debug, Clone)]
-pub struct CommandWithAlias {
-    pub command: Command,
-    pub alias_suggestions: Vec<AliasSuggestion>,
-}
-
-pub fn get_suggestions_with_aliases(
-    num: Option<usize>, 
-    db: &mut Database, 
-    alias_file_path: &str
-) -> Vec<CommandWithAlias> {
-    db.update_db();
-    let commands = db.get_top_commands(num);
-    
-    let suggester = AliasSuggester::new(alias_file_path);
-    
-    commands.into_iter().map(|cmd| {
-        let alias_suggestions = suggester.suggest_aliases(&cmd.command_text);
-        CommandWithAlias {
-            command: cmd.clone(),
-            alias_suggestions,
-        }
-    }).collect()
-}
-
+use crate::database::command_tree::CommandTree;
+use crate::database::database_structs::{Command, Database, DeletedCommands};
+use crate::database::scoring::{configured_scorer, now};
+use crate::ops::alias_suggestions::{AliasSuggester, AliasSuggestion, PriorityBreakdown};
+use crate::ops::keystroke_savings::estimate;
+use crate::ops::normalize_command::alias_target;
+use std::collections::HashSet;
+
+// The scorer shortlists this many candidates per requested suggestion before they are
+// re-ranked by keystrokes saved
+const CANDIDATES_PER_SUGGESTION: usize = 4;
+const MIN_CANDIDATES: usize = 50;
+
+#[derive(Debug, Clone)]
+pub struct CommandWithAlias {
+    pub command: Command,
+    pub alias_suggestions: Vec<AliasSuggestion>,
+    // How each alias suggestion was ranked, in the same order
+    pub priorities: Vec<PriorityBreakdown>,
+    // Estimated keystrokes per day the top alias would save
+    pub daily_savings: f64,
+}
+
+pub fn get_suggestions_with_aliases(
+    num: Option<usize>, 
+    db: &mut Database, 
+    deleted_commands: &DeletedCommands,
+    alias_file_path: &str
+) -> Vec<CommandWithAlias> {
+    get_matching_suggestions(num, None, db, deleted_commands, alias_file_path)
+}
+
+// Only for the commands that contain `filter`, ignoring case. They are picked out before any
+// alias is generated, wherever they are in the ranking.
+pub fn get_matching_suggestions(
+    num: Option<usize>,
+    filter: Option<&str>,
+    db: &mut Database,
+    deleted_commands: &DeletedCommands,
+    alias_file_path: &str
+) -> Vec<CommandWithAlias> {
+    let num = num.unwrap_or(5);
+    let scorer = configured_scorer();
+    let tree = CommandTree::build(db);
+    let mut commands = tree.select(scorer.as_ref(), now(), deleted_commands);
+    if let Some(filter) = filter.map(str::to_lowercase) {
+        commands.retain(|c| c.command_text.to_lowercase().contains(&filter));
+    }
+    commands.truncate((num * CANDIDATES_PER_SUGGESTION).max(MIN_CANDIDATES));
+    
+    let now = now();
+    let suggester = AliasSuggester::new(alias_file_path).with_savings(&tree, now);
+    
+    let mut suggestions: Vec<CommandWithAlias> = commands.into_iter().map(|cmd| {
+        // Templates like `git checkout <branch>` get a prefix alias for their stable part
+        let target = alias_target(&cmd.command_text);
+        let alias_suggestions = suggester.suggest_aliases(&target);
+        let daily_savings = alias_suggestions
+            .first()
+            .map(|top| estimate(&target, &top.alias, &tree, now).daily_keystrokes)
+            .unwrap_or(0.0);
+        CommandWithAlias {
+            command: cmd,
+            alias_suggestions,
+            priorities: Vec::new(),
+            daily_savings,
+        }
+    }).collect();
+
+    // What matters is keystrokes saved, the score only decides what gets considered
+    sort_by_savings(&mut suggestions);
+    // `git checkout <branch>` is aliased as `git checkout`, and the node itself may be picked
+    // too. Only the one that saves the most stays.
+    let mut targets = HashSet::new();
+    let mut candidates = suggestions
+        .into_iter()
+        .filter(|s| !s.alias_suggestions.is_empty())
+        .filter(|s| targets.insert(alias_target(&s.command.command_text)));
+    // Aliases are shared out among the commands that are shown. One that loses every alias it
+    // has to the others drops out, and the next candidate gets a go.
+    let mut suggestions: Vec<CommandWithAlias> = Vec::new();
+    let picks = loop {
+        suggestions.extend(candidates.by_ref().take(num - suggestions.len()));
+        let picks = assign_unique_aliases(&suggestions, &suggester, &tree, now);
+        if picks.iter().all(Option::is_some) {
+            break picks;
+        }
+        let mut picks = picks.into_iter();
+        suggestions.retain(|_| picks.next().flatten().is_some());
+    };
+    apply_picks(&mut suggestions, &picks, &tree, now);
+    sort_by_savings(&mut suggestions);
+    for suggestion in &mut suggestions {
+        suggestion.priorities = suggestion.alias_suggestions.iter().map(|s| suggester.priority_breakdown(s)).collect();
+    }
+    suggestions
+}
+
+fn sort_by_savings(suggestions: &mut [CommandWithAlias]) {
+    suggestions.sort_by(|a, b| {
+        b.daily_savings
+            .partial_cmp(&a.daily_savings)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| b.command.score.cmp(&a.command.score))
+    });
+}
+
+// Each command was given suggestions on its own, so `git commit` and `git checkout` can both
+// want `gc`. Every command gets one of its aliases, no two the same. An alias is worth its
+// priority times how often the command runs a day, so the commands run most often get the
+// aliases they rank highest. Returns the index of the alias each command gets in its
+// suggestions, None for those that are better off without one.
+fn assign_unique_aliases(suggestions: &[CommandWithAlias], suggester: &AliasSuggester, tree: &CommandTree, now: i64) -> Vec<Option<usize>> {
+    let mut names: Vec<&str> = Vec::new();
+    let mut weights: Vec<Vec<Option<f64>>> = Vec::new();
+    for suggestion in suggestions {
+        let count = suggestion.alias_suggestions.len();
+        let mut row = vec![None; names.len()];
+        for (rank, alias) in suggestion.alias_suggestions.iter().enumerate() {
+            let column = match names.iter().position(|name| *name == alias.alias) {
+                Some(column) => column,
+                None => {
+                    names.push(&alias.alias);
+                    names.len() - 1
+                }
+            };
+            row.resize(names.len(), None);
+            // Between aliases worth as much, the one ranked higher wins
+            let priority = suggester.priority_breakdown(alias).total().max(1) as f64;
+            let runs = estimate(&alias.command, &alias.alias, tree, now).daily_runs;
+            row[column] = Some(priority * runs + (count - rank) as f64 * TIE_BREAK);
+        }
+        weights.push(row);
+    }
+    for row in &mut weights {
+        row.resize(names.len(), None);
+    }
+
+    max_weight_assignment(&weights)
+        .into_iter()
+        .zip(suggestions)
+        .map(|(column, suggestion)| column.and_then(|column| suggestion.alias_suggestions.iter().position(|s| s.alias == names[column])))
+        .collect()
+}
+
+const TIE_BREAK: f64 = 1e-6;
+
+// Puts every command's alias first and takes the aliases given to the others out of its alternatives
+fn apply_picks(suggestions: &mut [CommandWithAlias], picks: &[Option<usize>], tree: &CommandTree, now: i64) {
+    let taken: HashSet<String> = suggestions
+        .iter()
+        .zip(picks)
+        .filter_map(|(suggestion, pick)| pick.map(|i| suggestion.alias_suggestions[i].alias.clone()))
+        .collect();
+    for (suggestion, pick) in suggestions.iter_mut().zip(picks) {
+        let Some(pick) = *pick else { continue };
+        let top = suggestion.alias_suggestions.remove(pick);
+        suggestion.alias_suggestions.retain(|s| !taken.contains(&s.alias));
+        suggestion.daily_savings = estimate(&top.command, &top.alias, tree, now).daily_keystrokes;
+        suggestion.alias_suggestions.insert(0, top);
+    }
+}
+
+// Hungarian algorithm: picks at most one column per row and never the same column twice so the
+// picked weights add up to the most. None marks the pairs that can't be picked. Every row also
+// gets a column of its own worth nothing, so a row can go without and there's always a solution.
+fn max_weight_assignment(weights: &[Vec<Option<f64>>]) -> Vec<Option<usize>> {
+    let rows = weights.len();
+    let columns = weights.first().map_or(0, Vec::len);
+    let width = columns + rows;
+    // Cheaper than any solution that uses it, so the forbidden pairs are never picked
+    let forbidden = 1.0 + weights.iter().flatten().flatten().map(|w| w.abs()).sum::<f64>();
+    let cost = |row: usize, column: usize| {
+        if column < columns {
+            weights[row][column].map_or(forbidden, |w| -w)
+        } else if column - columns == row {
+            0.0
+        } else {
+            forbidden
+        }
+    };
+
+    // Row and column potentials, and which row has each column, all counting from 1 so 0 can
+    // stand for nothing
+    let mut row_potential = vec![0.0; rows + 1];
+    let mut column_potential = vec![0.0; width + 1];
+    let mut owner = vec![0usize; width + 1];
+    let mut previous = vec![0usize; width + 1];
+    for row in 1..=rows {
+        owner[0] = row;
+        let mut current = 0;
+        let mut slack = vec![f64::INFINITY; width + 1];
+        let mut visited = vec![false; width + 1];
+        loop {
+            visited[current] = true;
+            let from = owner[current];
+            let mut delta = f64::INFINITY;
+            let mut next = 0;
+            for column in 1..=width {
+                if visited[column] {
+                    continue;
+                }
+                let reduced = cost(from - 1, column - 1) - row_potential[from] - column_potential[column];
+                if reduced < slack[column] {
+                    slack[column] = reduced;
+                    previous[column] = current;
+                }
+                if slack[column] < delta {
+                    delta = slack[column];
+                    next = column;
+                }
+            }
+            for column in 0..=width {
+                if visited[column] {
+                    row_potential[owner[column]] += delta;
+                    column_potential[column] -= delta;
+                } else {
+                    slack[column] -= delta;
+                }
+            }
+            current = next;
+            if owner[current] == 0 {
+                break;
+            }
+        }
+        while current != 0 {
+            let before = previous[current];
+            owner[current] = owner[before];
+            current = before;
+        }
+    }
+
+    let mut assignment = vec![None; rows];
+    for (column, &row) in owner.iter().enumerate().skip(1) {
+        if row != 0 && column - 1 < columns {
+            assignment[row - 1] = Some(column - 1);
+        }
+    }
+    assignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conflicting_rows_share_out_the_columns() {
+        // Both want column 0, the first wants it more than the second
+        let weights = vec![vec![Some(5.0), Some(1.0)], vec![Some(4.0), Some(3.0)]];
+        assert_eq!(max_weight_assignment(&weights), vec![Some(0), Some(1)]);
+    }
+
+    #[test]
+    fn row_with_every_pair_forbidden_goes_without() {
+        let weights = vec![vec![Some(2.0), None], vec![None, None], vec![None, Some(1.0)]];
+        assert_eq!(max_weight_assignment(&weights), vec![Some(0), None, Some(1)]);
+    }
+
+    #[test]
+    fn more_rows_than_columns() {
+        let weights = vec![vec![Some(1.0)], vec![Some(3.0)], vec![Some(2.0)]];
+        assert_eq!(max_weight_assignment(&weights), vec![None, Some(0), None]);
+    }
+
+    #[test]
+    fn optimal_beats_greedy() {
+        // Greedy hands column 0 to the row that wants it most (10), leaving the other row
+        // with 1 for 11 in all; giving it away instead makes 9 + 8 = 17
+        let weights = vec![vec![Some(10.0), Some(9.0)], vec![Some(8.0), Some(1.0)]];
+        assert_eq!(max_weight_assignment(&weights), vec![Some(1), Some(0)]);
+    }
+
+    #[test]
+    fn no_rows() {
+        assert!(max_weight_assignment(&[]).is_empty());
+    }
+}
@@ -0,0 +1,354 @@
+use crate::database::database_structs::{Database, DeletedCommands, HistorySyncState, SequenceStats};
+use crate::ops::command_sequences::record_sequence;
+use crate::ops::insert_command::insert_command_at;
+use crate::ops::usage_stats::UsageRecorder;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::os::unix::fs::MetadataExt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// A history entry together with the byte offset right after it
+struct HistoryEntry {
+    command: String,
+    timestamp: Option<i64>,
+    end: usize,
+}
+
+#[derive(PartialEq)]
+enum HistoryFormat {
+    Plain, // bash / ksh / sh, optionally with `#<timestamp>` lines
+    Zsh,   // `: <timestamp>:<duration>;command`
+    Fish,  // `- cmd: command` followed by `  when: <timestamp>`
+}
+
+// Reads everything appended to `path` since the last sync and inserts it into the database.
+// Returns the number of commands ingested.
+pub fn sync_history_file(
+    path: &str,
+    db: &mut Database,
+    deleted_commands: &DeletedCommands,
+    state: &mut HistorySyncState,
+    sequences: &mut SequenceStats,
+    usage: &mut UsageRecorder,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let mut file = File::open(path)?;
+    let metadata = file.metadata()?;
+    let inode = metadata.ino();
+    let size = metadata.len();
+    let previous = state.files.get(path).cloned().unwrap_or_default();
+
+    // Fast path: same file, not truncated, and the line we stopped at is still right before the offset
+    let mut start = None;
+    if previous.inode == inode && previous.offset <= size {
+        if previous.offset == 0 {
+            start = Some(0);
+        } else if line_before(&mut file, previous.offset)? == previous.last_line {
+            start = Some(previous.offset);
+        }
+    }
+
+    let mut content = Vec::new();
+    let mut resume_line = previous.last_line.clone();
+    let start = match start {
+        Some(offset) => {
+            file.seek(SeekFrom::Start(offset))?;
+            file.read_to_end(&mut content)?;
+            offset
+        }
+        None => {
+            // The file was rotated, truncated or rewritten (bash rewrites it via a temp file).
+            // Resume after the last line we saw if it is still there. If it isn't, there is no
+            // telling what was already read, so skip to the end rather than count it all twice.
+            file.seek(SeekFrom::Start(0))?;
+            file.read_to_end(&mut content)?;
+            let resume = if previous.last_line.is_empty() {
+                0
+            } else {
+                find_resume_point(&content, &previous.last_line).unwrap_or_else(|| {
+                    let end = content.iter().rposition(|b| *b == b'\n').map(|i| i + 1).unwrap_or(0);
+                    resume_line = last_line_of(&content[..end]);
+                    end
+                })
+            };
+            content.drain(..resume);
+            resume as u64
+        }
+    };
+
+    let format = detect_format(path, &content);
+    let entries = parse_entries(&content, &format);
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
+    // A history file is the closest thing to a session we get here. Without timestamps
+    // there is no way to tell which commands ran close together, so no sequences then.
+    let session = format!("history:{}", path);
+    let mut consumed = 0;
+    for entry in &entries {
+        usage.record(&entry.command, entry.timestamp.unwrap_or(now), db, deleted_commands);
+        insert_command_at(entry.command.clone(), entry.timestamp.unwrap_or(now), db);
+        if let Some(timestamp) = entry.timestamp {
+            record_sequence(&session, &entry.command, timestamp, sequences, db);
+        }
+        consumed = entry.end;
+    }
+
+    let mut new_state = previous;
+    new_state.inode = inode;
+    if consumed > 0 {
+        new_state.offset = start + consumed as u64;
+        new_state.last_line = last_line_of(&content[..consumed]);
+    } else {
+        new_state.offset = start;
+        new_state.last_line = resume_line;
+    }
+    state.files.insert(path.to_string(), new_state);
+
+    Ok(entries.len())
+}
+
+// The history file the current shell most likely writes to
+pub fn default_history_file() -> Option<String> {
+    if let Ok(histfile) = std::env::var("HISTFILE") {
+        if !histfile.is_empty() {
+            return Some(histfile);
+        }
+    }
+    let home_dir = dirs::home_dir()?;
+    let shell = std::env::var("SHELL").unwrap_or_default();
+    let path = if shell.contains("zsh") {
+        home_dir.join(".zsh_history")
+    } else if shell.contains("fish") {
+        home_dir.join(".local").join("share").join("fish").join("fish_history")
+    } else if shell.contains("bash") {
+        home_dir.join(".bash_history")
+    } else {
+        home_dir.join(".sh_history")
+    };
+    Some(path.to_string_lossy().to_string())
+}
+
+fn line_before(file: &mut File, offset: u64) -> Result<String, Box<dyn std::error::Error>> {
+    // History lines are short, 4k is plenty to get back to the previous newline
+    let window = offset.min(4096);
+    let mut buf = vec![0u8; window as usize];
+    file.seek(SeekFrom::Start(offset - window))?;
+    file.read_exact(&mut buf)?;
+    Ok(last_line_of(&buf))
+}
+
+fn last_line_of(bytes: &[u8]) -> String {
+    let trimmed = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+    let start = trimmed.iter().rposition(|b| *b == b'\n').map(|i| i + 1).unwrap_or(0);
+    String::from_utf8_lossy(&trimmed[start..]).to_string()
+}
+
+fn find_resume_point(content: &[u8], last_line: &str) -> Option<usize> {
+    let needle = format!("{}\n", last_line);
+    let needle = needle.as_bytes();
+    if content.len() < needle.len() {
+        return None;
+    }
+    // Search backwards, the line has to start at the beginning of the file or right after a newline
+    (0..=content.len() - needle.len())
+        .rev()
+        .find(|&i| &content[i..i + needle.len()] == needle && (i == 0 || content[i - 1] == b'\n'))
+        .map(|i| i + needle.len())
+}
+
+fn detect_format(path: &str, content: &[u8]) -> HistoryFormat {
+    if path.ends_with("fish_history") || content.starts_with(b"- cmd: ") {
+        HistoryFormat::Fish
+    } else if path.contains("zsh") || content.starts_with(b": ") {
+        HistoryFormat::Zsh
+    } else {
+        HistoryFormat::Plain
+    }
+}
+
+fn parse_entries(content: &[u8], format: &HistoryFormat) -> Vec<HistoryEntry> {
+    // Only look at complete lines, a trailing partial line is picked up by the next sync
+    let complete = match content.iter().rposition(|b| *b == b'\n') {
+        Some(pos) => &content[..=pos],
+        None => return Vec::new(),
+    };
+
+    let mut lines = Vec::new();
+    let mut line_start = 0;
+    for (i, b) in complete.iter().enumerate() {
+        if *b == b'\n' {
+            lines.push((String::from_utf8_lossy(&complete[line_start..i]).to_string(), i + 1));
+            line_start = i + 1;
+        }
+    }
+
+    match format {
+        HistoryFormat::Plain => parse_plain(&lines),
+        HistoryFormat::Zsh => parse_zsh(&lines),
+        HistoryFormat::Fish => parse_fish(&lines),
+    }
+}
+
+fn parse_plain(lines: &[(String, usize)]) -> Vec<HistoryEntry> {
+    let mut entries = Vec::new();
+    let mut pending_timestamp = None;
+    for (line, end) in lines {
+        // bash writes `#<unix time>` before each entry when HISTTIMEFORMAT is set
+        if let Some(ts) = line.strip_prefix('#').and_then(|t| t.trim().parse::<i64>().ok()) {
+            pending_timestamp = Some(ts);
+            continue;
+        }
+        if !line.trim().is_empty() {
+            entries.push(HistoryEntry {
+                command: line.clone(),
+                timestamp: pending_timestamp.take(),
+                end: *end,
+            });
+        }
+    }
+    entries
+}
+
+fn parse_zsh(lines: &[(String, usize)]) -> Vec<HistoryEntry> {
+    let mut entries = Vec::new();
+    let mut current: Option<(String, Option<i64>)> = None;
+    for (line, end) in lines {
+        let (text, timestamp) = match current.take() {
+            // Multi-line commands are stored with a trailing backslash on every line but the last
+            Some((mut text, timestamp)) => {
+                text.push('\n');
+                text.push_str(line);
+                (text, timestamp)
+            }
+            None => {
+                if let Some(rest) = line.strip_prefix(": ") {
+                    match rest.split_once(';') {
+                        Some((meta, command)) => {
+                            let timestamp = meta.split(':').next().and_then(|t| t.trim().parse::<i64>().ok());
+                            (command.to_string(), timestamp)
+                        }
+                        None => (line.clone(), None),
+                    }
+                } else {
+                    (line.clone(), None)
+                }
+            }
+        };
+        if text.ends_with('\\') {
+            current = Some((text, timestamp));
+            continue;
+        }
+        if !text.trim().is_empty() {
+            entries.push(HistoryEntry {
+                command: text.replace("\\\n", " "),
+                timestamp,
+                end: *end,
+            });
+        }
+    }
+    entries
+}
+
+fn parse_fish(lines: &[(String, usize)]) -> Vec<HistoryEntry> {
+    let mut entries: Vec<HistoryEntry> = Vec::new();
+    for (line, end) in lines {
+        if let Some(command) = line.strip_prefix("- cmd: ") {
+            entries.push(HistoryEntry {
+                command: unescape_fish(command),
+                timestamp: None,
+                end: *end,
+            });
+        } else if let Some(entry) = entries.last_mut() {
+            if let Some(ts) = line.trim_start().strip_prefix("when: ") {
+                entry.timestamp = ts.trim().parse::<i64>().ok();
+            }
+            // `paths:` and other metadata lines belong to the previous entry
+            entry.end = *end;
+        }
+    }
+    entries
+}
+
+// fish escapes newlines and backslashes in its history file. One pass from the left, so `\\n`
+// is a backslash and an `n` rather than a line break.
+fn unescape_fish(command: &str) -> String {
+    let mut unescaped = String::with_capacity(command.len());
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push(' '),
+            Some('\\') => unescaped.push('\\'),
+            Some(other) => {
+                unescaped.push('\\');
+                unescaped.push(other);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+// What a sync read: the commands are in the database already, the read positions and the rest
+// are written by `save` once the database has been saved
+pub struct SyncedHistory {
+    pub count: usize,
+    state: HistorySyncState,
+    sequences: SequenceStats,
+    usage: UsageRecorder,
+}
+
+impl SyncedHistory {
+    // Only move the cursor once the commands are safely stored, or they'd never be read again
+    pub fn save(self) {
+        use crate::database::persistence::{get_history_sync_path, get_sequences_path, save_history_sync_state, save_sequence_stats};
+        if let Err(e) = save_history_sync_state(&self.state, &get_history_sync_path()) {
+            eprintln!("Failed to save history sync state: {}", e);
+        }
+        if let Err(e) = save_sequence_stats(&self.sequences, &get_sequences_path()) {
+            eprintln!("Failed to save command sequences: {}", e);
+        }
+        self.usage.save();
+    }
+}
+
+// Syncs every given history file from the read positions kept in ~/.alman
+pub fn sync_history_files(paths: &[String], db: &mut Database, deleted_commands: &DeletedCommands) -> SyncedHistory {
+    use crate::database::persistence::{get_history_sync_path, get_sequences_path, load_config, load_history_sync_state, load_sequence_stats};
+
+    let mut state = match load_history_sync_state(&get_history_sync_path()) {
+        Ok(state) => state,
+        Err(e) => {
+            eprintln!("Failed to load history sync state: {}", e);
+            HistorySyncState::default()
+        }
+    };
+
+    let mut sequences = load_sequence_stats(&get_sequences_path()).unwrap_or_default();
+    let alias_file_paths = load_config().map(|config| config.alias_file_paths).unwrap_or_default();
+    let mut usage = UsageRecorder::load(&alias_file_paths);
+
+    let mut count = 0;
+    for path in paths {
+        match sync_history_file(path, db, deleted_commands, &mut state, &mut sequences, &mut usage) {
+            Ok(synced) => count += synced,
+            Err(e) => eprintln!("Failed to sync history file {}: {}", path, e),
+        }
+    }
+    SyncedHistory { count, state, sequences, usage }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unescapes_fish_history_in_one_pass() {
+        assert_eq!(unescape_fish(r"echo a\nb"), "echo a b");
+        assert_eq!(unescape_fish(r"printf 'a\\nb'"), r"printf 'a\nb'");
+        assert_eq!(unescape_fish(r"echo \\"), r"echo \");
+        assert_eq!(unescape_fish(r"echo \t"), r"echo \t");
+    }
+}
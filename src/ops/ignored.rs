@@ -0,0 +1,210 @@
+// Everything kept out of the suggestions: commands deleted one by one, glob or regex block
+// rules, and snoozes that lift on their own once their time is up.
+use crate::database::database_structs::{BlockKind, BlockRule, DeletedCommands};
+use crate::ops::normalize_command::alias_target;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use regex::Regex;
+
+// The rules compiled once, for checking a whole command list
+pub struct IgnoreMatcher<'a> {
+    deleted_commands: &'a DeletedCommands,
+    now: i64,
+    globs: GlobSet,
+    glob_patterns: Vec<&'a str>,
+    regexes: Vec<(Regex, &'a str)>,
+}
+
+impl<'a> IgnoreMatcher<'a> {
+    pub fn new(deleted_commands: &'a DeletedCommands, now: i64) -> Self {
+        let mut builder = GlobSetBuilder::new();
+        let mut glob_patterns = Vec::new();
+        let mut regexes = Vec::new();
+        // Rules are checked when they're added, one broken by hand is skipped
+        for rule in &deleted_commands.block_rules {
+            match rule.kind {
+                BlockKind::Glob => {
+                    if let Ok(glob) = Glob::new(&rule.pattern) {
+                        builder.add(glob);
+                        glob_patterns.push(rule.pattern.as_str());
+                    }
+                }
+                BlockKind::Regex => {
+                    if let Ok(regex) = Regex::new(&rule.pattern) {
+                        regexes.push((regex, rule.pattern.as_str()));
+                    }
+                }
+            }
+        }
+        Self {
+            deleted_commands,
+            now,
+            globs: builder.build().unwrap_or_else(|_| GlobSet::empty()),
+            glob_patterns,
+            regexes,
+        }
+    }
+
+    pub fn is_ignored(&self, command_text: &str) -> bool {
+        self.reason(command_text).is_some()
+    }
+
+    // Why `command_text` isn't suggested, None if it is
+    pub fn reason(&self, command_text: &str) -> Option<String> {
+        if self.deleted_commands.deleted_commands.contains(command_text) {
+            return Some("was deleted from suggestions".to_string());
+        }
+        if let Some(&until) = self.deleted_commands.snoozed.get(command_text) {
+            if until > self.now {
+                return Some(format!("is snoozed until {}", format_time(until)));
+            }
+        }
+        if let Some(i) = self.globs.matches(command_text).first() {
+            return Some(format!("is blocked by glob '{}'", self.glob_patterns[*i]));
+        }
+        self.regexes
+            .iter()
+            .find(|(regex, _)| regex.is_match(command_text))
+            .map(|(_, pattern)| format!("is blocked by regex '{}'", pattern))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum IgnoredKind {
+    Deleted,
+    // Hidden because an alias for it exists
+    Aliased(String),
+    Blocked(BlockKind),
+    Snoozed(i64),
+}
+
+#[derive(Debug, Clone)]
+pub struct IgnoredEntry {
+    pub kind: IgnoredKind,
+    // The command, or the pattern of a block rule
+    pub text: String,
+}
+
+impl IgnoredEntry {
+    pub fn label(&self) -> String {
+        match self.kind {
+            IgnoredKind::Deleted => "deleted".to_string(),
+            IgnoredKind::Aliased(ref alias) => format!("aliased as {}", alias),
+            IgnoredKind::Blocked(BlockKind::Glob) => "glob".to_string(),
+            IgnoredKind::Blocked(BlockKind::Regex) => "regex".to_string(),
+            IgnoredKind::Snoozed(until) => format!("snoozed until {}", format_time(until)),
+        }
+    }
+}
+
+// Drops snoozes that have run out, returns whether anything changed
+pub fn prune_expired(deleted_commands: &mut DeletedCommands, now: i64) -> bool {
+    let before = deleted_commands.snoozed.len();
+    deleted_commands.snoozed.retain(|_, until| *until > now);
+    deleted_commands.snoozed.len() != before
+}
+
+// `aliases` are (alias, command) pairs, adding an alias hides its command the same way a
+// deletion does
+pub fn ignored_entries(deleted_commands: &DeletedCommands, aliases: &[(String, String)], now: i64) -> Vec<IgnoredEntry> {
+    let mut entries: Vec<IgnoredEntry> = deleted_commands
+        .block_rules
+        .iter()
+        .map(|rule| IgnoredEntry { kind: IgnoredKind::Blocked(rule.kind), text: rule.pattern.clone() })
+        .collect();
+    entries.extend(
+        deleted_commands
+            .snoozed
+            .iter()
+            .filter(|(_, until)| **until > now)
+            .map(|(command, until)| IgnoredEntry { kind: IgnoredKind::Snoozed(*until), text: command.clone() }),
+    );
+    entries.extend(
+        deleted_commands
+            .deleted_commands
+            .iter()
+            .map(|command| {
+                let kind = match alias_for(aliases, command) {
+                    Some(alias) => IgnoredKind::Aliased(alias.to_string()),
+                    None => IgnoredKind::Deleted,
+                };
+                IgnoredEntry { kind, text: command.clone() }
+            }),
+    );
+    entries
+}
+
+// The alias `command` is hidden for, if one still exists
+fn alias_for<'a>(aliases: &'a [(String, String)], command: &str) -> Option<&'a str> {
+    let target = alias_target(command);
+    aliases.iter().find(|(_, aliased)| *aliased == target).map(|(alias, _)| alias.as_str())
+}
+
+// Lifts everything recorded under `text`, a command or a rule's pattern. Returns how many
+// entries were removed. A command stays hidden as long as an alias for it exists.
+pub fn restore(deleted_commands: &mut DeletedCommands, aliases: &[(String, String)], text: &str) -> Result<usize, String> {
+    if deleted_commands.deleted_commands.contains(text) {
+        if let Some(alias) = alias_for(aliases, text) {
+            return Err(format!("'{}' is aliased as {}, remove the alias to have it suggested again", text, alias));
+        }
+    }
+    let mut restored = 0;
+    if deleted_commands.deleted_commands.remove(text) {
+        deleted_commands.aliased.remove(text);
+        restored += 1;
+    }
+    if deleted_commands.snoozed.remove(text).is_some() {
+        restored += 1;
+    }
+    let rules_before = deleted_commands.block_rules.len();
+    deleted_commands.block_rules.retain(|rule| rule.pattern != text);
+    Ok(restored + rules_before - deleted_commands.block_rules.len())
+}
+
+pub fn add_block_rule(deleted_commands: &mut DeletedCommands, pattern: &str, kind: BlockKind) -> Result<(), Box<dyn std::error::Error>> {
+    match kind {
+        BlockKind::Glob => {
+            Glob::new(pattern)?;
+        }
+        BlockKind::Regex => {
+            Regex::new(pattern)?;
+        }
+    }
+    let rule = BlockRule { kind, pattern: pattern.to_string() };
+    if !deleted_commands.block_rules.contains(&rule) {
+        deleted_commands.block_rules.push(rule);
+    }
+    Ok(())
+}
+
+pub fn snooze(deleted_commands: &mut DeletedCommands, command_text: &str, until: i64) {
+    let command_text = command_text.split_whitespace().collect::<Vec<_>>().join(" ");
+    deleted_commands.snoozed.insert(command_text, until);
+}
+
+// "90m", "12h", "3d", "2w" in seconds
+pub fn parse_duration(text: &str) -> Result<i64, String> {
+    let text = text.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: i64 = number.parse().map_err(|_| format!("invalid duration '{}', expected e.g. 12h, 3d or 2w", text))?;
+    let unit_seconds = match unit {
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" | "" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return Err(format!("invalid duration unit '{}', use m, h, d or w", unit)),
+    };
+    if number == 0 {
+        return Err("duration must be greater than 0".to_string());
+    }
+    Ok(number * unit_seconds)
+}
+
+pub fn format_time(timestamp: i64) -> String {
+    use chrono::{Local, TimeZone};
+    Local
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
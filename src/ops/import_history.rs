@@ -0,0 +1,197 @@
+use crate::cli::cli_data::ImportSource;
+use crate::database::database_structs::{Database, DeletedCommands, HistorySyncState, ImportCursor, SequenceStats};
+use crate::ops::command_sequences::record_sequence;
+use crate::ops::insert_command::insert_command_at;
+use crate::ops::usage_stats::UsageRecorder;
+use rusqlite::{Connection, OpenFlags, params};
+use std::path::PathBuf;
+
+// Exit status shells use for "command not found", those are typos and shouldn't count
+const COMMAND_NOT_FOUND: i64 = 127;
+
+struct ImportedCommand {
+    id: String,
+    raw_timestamp: i64, // in the source's own unit, stored in the cursor
+    timestamp: i64,     // seconds
+    command: String,
+    exit_code: Option<i64>,
+    hostname: Option<String>,
+    // The shell session it ran in, so imported history feeds combo suggestions like live history does
+    session: Option<String>,
+}
+
+pub struct ImportSummary {
+    pub imported: usize,
+    pub skipped: usize,
+    // Rows alman had recorded already
+    pub already_recorded: usize,
+}
+
+// Where each tool keeps its database by default
+pub fn default_database_path(source: &ImportSource) -> Option<PathBuf> {
+    let home_dir = dirs::home_dir()?;
+    let data_dir = dirs::data_dir().unwrap_or_else(|| home_dir.join(".local").join("share"));
+    let path = match source {
+        ImportSource::Atuin => data_dir.join("atuin").join("history.db"),
+        ImportSource::ZshHistdb => home_dir.join(".histdb").join("zsh-history.db"),
+        ImportSource::Mcfly => {
+            // Older McFly versions kept everything in ~/.mcfly
+            let legacy = home_dir.join(".mcfly").join("history.db");
+            if legacy.exists() {
+                legacy
+            } else if cfg!(target_os = "macos") {
+                data_dir.join("McFly").join("history.db")
+            } else {
+                data_dir.join("mcfly").join("history.db")
+            }
+        }
+    };
+    Some(path)
+}
+
+// Imports every row newer than the stored cursor for `db_path`, so running it again only picks up new history.
+// Rows from after the first run alman recorded itself are left out, those are counted already.
+// The working directory and duration the tools record are left out, alman keeps neither for any command.
+#[allow(clippy::too_many_arguments)]
+pub fn import_history(
+    source: &ImportSource,
+    db_path: &str,
+    all_hosts: bool,
+    db: &mut Database,
+    deleted_commands: &DeletedCommands,
+    state: &mut HistorySyncState,
+    sequences: &mut SequenceStats,
+    usage: &mut UsageRecorder,
+) -> Result<ImportSummary, Box<dyn std::error::Error>> {
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX)?;
+    let cursor = match state.imports.get(db_path) {
+        Some(cursor) => cursor.clone(),
+        None => ImportCursor {
+            recorded_from: db.reverse_command_map.values().map(|c| c.first_access_time).filter(|t| *t > 0).min(),
+            ..Default::default()
+        },
+    };
+
+    let rows = match source {
+        ImportSource::Atuin => read_atuin(&conn, &cursor)?,
+        ImportSource::ZshHistdb => read_zsh_histdb(&conn, &cursor)?,
+        ImportSource::Mcfly => read_mcfly(&conn, &cursor)?,
+    };
+
+    let hostname = if all_hosts { None } else { current_hostname() };
+    let mut summary = ImportSummary { imported: 0, skipped: 0, already_recorded: 0 };
+    let mut new_cursor = cursor;
+    for row in rows {
+        new_cursor = ImportCursor {
+            last_timestamp: row.raw_timestamp,
+            last_id: row.id.clone(),
+            recorded_from: new_cursor.recorded_from,
+        };
+        if new_cursor.recorded_from.is_some_and(|from| row.timestamp >= from) {
+            summary.already_recorded += 1;
+            continue;
+        }
+
+        let from_other_host = match (&hostname, &row.hostname) {
+            // atuin stores "host:user"
+            (Some(current), Some(host)) => host.split(':').next().unwrap_or(host) != current,
+            _ => false,
+        };
+        if from_other_host || row.exit_code == Some(COMMAND_NOT_FOUND) {
+            summary.skipped += 1;
+            continue;
+        }
+
+        usage.record(&row.command, row.timestamp, db, deleted_commands);
+        if let Some(session) = &row.session {
+            // Session ids are only unique within one database
+            record_sequence(&format!("{}:{}", db_path, session), &row.command, row.timestamp, sequences, db);
+        }
+        insert_command_at(row.command, row.timestamp, db);
+        summary.imported += 1;
+    }
+    state.imports.insert(db_path.to_string(), new_cursor);
+
+    Ok(summary)
+}
+
+fn read_atuin(conn: &Connection, cursor: &ImportCursor) -> rusqlite::Result<Vec<ImportedCommand>> {
+    // Timestamps are in nanoseconds and ids are not ordered in older versions, so page by (timestamp, id)
+    let mut stmt = conn.prepare(
+        "SELECT id, timestamp, command, exit, hostname, session FROM history \
+         WHERE deleted_at IS NULL AND (timestamp > ?1 OR (timestamp = ?1 AND id > ?2)) \
+         ORDER BY timestamp, id",
+    )?;
+    let rows = stmt.query_map(params![cursor.last_timestamp, cursor.last_id], |row| {
+        let timestamp_ns: i64 = row.get(1)?;
+        Ok(ImportedCommand {
+            id: row.get(0)?,
+            raw_timestamp: timestamp_ns,
+            timestamp: timestamp_ns / 1_000_000_000,
+            command: row.get(2)?,
+            exit_code: row.get(3)?,
+            hostname: row.get(4)?,
+            session: row.get(5)?,
+        })
+    })?;
+    rows.collect()
+}
+
+fn read_zsh_histdb(conn: &Connection, cursor: &ImportCursor) -> rusqlite::Result<Vec<ImportedCommand>> {
+    let last_id: i64 = cursor.last_id.parse().unwrap_or(0);
+    let mut stmt = conn.prepare(
+        "SELECT history.id, history.start_time, commands.argv, history.exit_status, places.host, history.session \
+         FROM history \
+         JOIN commands ON history.command_id = commands.id \
+         LEFT JOIN places ON history.place_id = places.id \
+         WHERE history.id > ?1 \
+         ORDER BY history.id",
+    )?;
+    let rows = stmt.query_map(params![last_id], |row| {
+        let id: i64 = row.get(0)?;
+        let timestamp = row.get::<_, Option<i64>>(1)?.unwrap_or(0);
+        Ok(ImportedCommand {
+            id: id.to_string(),
+            raw_timestamp: timestamp,
+            timestamp,
+            command: row.get(2)?,
+            exit_code: row.get(3)?,
+            hostname: row.get(4)?,
+            session: row.get::<_, Option<i64>>(5)?.map(|s| s.to_string()),
+        })
+    })?;
+    rows.collect()
+}
+
+fn read_mcfly(conn: &Connection, cursor: &ImportCursor) -> rusqlite::Result<Vec<ImportedCommand>> {
+    let last_id: i64 = cursor.last_id.parse().unwrap_or(0);
+    let mut stmt = conn.prepare(
+        "SELECT id, when_run, cmd, exit_code, session_id FROM commands WHERE id > ?1 ORDER BY id",
+    )?;
+    let rows = stmt.query_map(params![last_id], |row| {
+        let id: i64 = row.get(0)?;
+        let timestamp = row.get::<_, Option<i64>>(1)?.unwrap_or(0);
+        Ok(ImportedCommand {
+            id: id.to_string(),
+            raw_timestamp: timestamp,
+            timestamp,
+            command: row.get(2)?,
+            exit_code: row.get(3)?,
+            hostname: None, // McFly databases are per machine
+            session: row.get(4)?,
+        })
+    })?;
+    rows.collect()
+}
+
+fn current_hostname() -> Option<String> {
+    if let Ok(name) = std::fs::read_to_string("/proc/sys/kernel/hostname") {
+        let name = name.trim();
+        if !name.is_empty() {
+            return Some(name.to_string());
+        }
+    }
+    let output = std::process::Command::new("hostname").output().ok()?;
+    let name = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    if name.is_empty() { None } else { Some(name) }
+}
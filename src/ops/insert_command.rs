@@ -1,39 +1,58 @@
-use crate::database::database_structs::{Database, DeletedCommands};
-
-pub fn insert_command(command_str: String, db: &mut Database, deleted_commands: &DeletedCommands) {
-    // now we should find every prefix of the command and insert that
-    // for example, if git add . ; we should insert git, git add, git add .
-    // should have single space between words in command, input may have multiple spaces
-    let command_str = command_str.trim().to_string();
-    if command_str.is_empty() {
-        return; // Do not insert empty commands
-    }
-    let command_parts: Vec<&str> = command_str.split_whitespace().collect();
-    if command_parts.is_empty() {
-        return; // Do not insert commands with no words
-    }
-    
-    // Skip commands that start with the current binary name
-    let binary_name = std::env::args()
-        .next()
-        .and_then(|path| std::path::Path::new(&path).file_name().map(|f| f.to_os_string()))
-        .and_then(|os_str| os_str.into_string().ok());
-    if let Some(name) = binary_name {
-        if command_parts[0] == name {
-            return;
-        }
-    }
-    
-    // maintain a string called temp, which stores command so far and then we do a for loop
-    let mut temp = String::new();
-    for word in command_parts.iter() {
-        if !temp.is_empty() {
-            temp.push(' '); // Add a space before the next word
-        }
-        temp.push_str(word);
-        let tempp = temp.clone();
-        // Insert the current command prefix into the database
-        db.add_command(tempp, deleted_commands);
-    }
-    db.add_command(command_str, deleted_commands);
-}
\ No newline at end of file
+use crate::database::database_structs::Database;
+use crate::ops::normalize_command::normalize_words;
+use crate::ops::parse_command::{parse_command_line, SimpleCommand};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub fn insert_command(command_str: String, db: &mut Database) {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
+    insert_command_at(command_str, now, db);
+}
+
+pub fn insert_command_at(command_str: String, timestamp: i64, db: &mut Database) {
+    // a line like `cd foo && make build | tee log` runs several simple commands, each one is
+    // recorded on its own, and pipelines are recorded as a whole as well. Word prefixes (git,
+    // git add) aren't stored, their usage comes from the command tree.
+    let command_str = command_str.trim().to_string();
+    if command_str.is_empty() {
+        return; // Do not insert empty commands
+    }
+
+    let pipelines = match parse_command_line(&command_str) {
+        Ok(pipelines) => pipelines,
+        // Unbalanced quotes or parens, nothing sensible to split, keep the line as typed
+        Err(_) => {
+            db.add_command_at(command_str.split_whitespace().collect::<Vec<_>>().join(" "), timestamp);
+            return;
+        }
+    };
+
+    // Skip commands that start with the current binary name
+    let binary_name = std::env::args()
+        .next()
+        .and_then(|path| std::path::Path::new(&path).file_name().map(|f| f.to_os_string()))
+        .and_then(|os_str| os_str.into_string().ok());
+    let is_own_command = |command: &SimpleCommand| {
+        binary_name.as_deref().map(|name| command.words[0] == name).unwrap_or(false)
+    };
+
+    for pipeline in &pipelines {
+        for command in &pipeline.commands {
+            if is_own_command(command) {
+                continue;
+            }
+            insert_simple_command(command, timestamp, db);
+        }
+        if pipeline.stages.len() > 1 && !pipeline.commands.iter().any(is_own_command) {
+            db.add_command_at(pipeline.text(), timestamp);
+        }
+    }
+}
+
+fn insert_simple_command(command: &SimpleCommand, timestamp: i64, db: &mut Database) {
+    let text = command.text();
+    db.add_command_at(text.clone(), timestamp);
+    // Also count the run towards its template, so `vim a.rs` and `vim b.rs` add up to `vim <path>`
+    if let Some(template) = normalize_words(&command.words) {
+        db.add_command_at(template, timestamp);
+    }
+}
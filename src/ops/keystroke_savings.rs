@@ -0,0 +1,52 @@
+use crate::database::command_tree::CommandTree;
+use crate::database::database_structs::Command;
+use serde::Serialize;
+
+const DAY_SECS: i64 = 86400;
+// Records from before first use was tracked are assumed to cover about a month
+const UNKNOWN_SPAN_DAYS: f64 = 30.0;
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct SavingsEstimate {
+    pub per_run: i64,
+    pub daily_runs: f64,
+    pub daily_keystrokes: f64,
+}
+
+// Characters saved each time `alias` is typed instead of `target`
+pub fn saved_per_run(target: &str, alias: &str) -> i64 {
+    (target.chars().count() as i64 - alias.chars().count() as i64).max(0)
+}
+
+// How often the command is run per day, going by how often it ran since it was first seen
+pub fn daily_runs(command: &Command, now: i64) -> f64 {
+    let span_days = if command.first_access_time > 0 {
+        ((now - command.first_access_time) as f64 / DAY_SECS as f64).max(1.0)
+    } else {
+        UNKNOWN_SPAN_DAYS
+    };
+    command.frequency as f64 / span_days
+}
+
+// (length of `target` - length of `alias`) x expected runs per day. The command tree counts
+// every run of a longer command towards its prefixes, so the frequency of `git commit`
+// already includes `git commit -m ...`, which is exactly where the alias is reused.
+pub fn estimate(target: &str, alias: &str, tree: &CommandTree, now: i64) -> SavingsEstimate {
+    match tree.lookup(target) {
+        Some(command) => estimate_for(&command, alias, now),
+        None => SavingsEstimate {
+            per_run: saved_per_run(target, alias),
+            ..Default::default()
+        },
+    }
+}
+
+pub fn estimate_for(command: &Command, alias: &str, now: i64) -> SavingsEstimate {
+    let per_run = saved_per_run(&command.command_text, alias);
+    let daily_runs = daily_runs(command, now);
+    SavingsEstimate {
+        per_run,
+        daily_runs,
+        daily_keystrokes: per_run as f64 * daily_runs,
+    }
+}
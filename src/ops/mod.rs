@@ -1,7 +1,25 @@
-pub mod get_suggestions;
-pub mod insert_command;
-pub mod delete_suggestion;
-pub mod alias_ops;
-pub mod add_alias;
-pub mod remove_alias;
-pub mod alias_suggestions;
\ No newline at end of file
+pub mod get_suggestions;
+pub mod insert_command;
+pub mod delete_suggestion;
+pub mod alias_ops;
+pub mod add_alias;
+pub mod remove_alias;
+pub mod alias_suggestions;
+pub mod history_sync;
+pub mod import_history;
+pub mod normalize_command;
+pub mod parse_command;
+pub mod command_sequences;
+pub mod explain;
+pub mod usage_stats;
+pub mod keystroke_savings;
+pub mod tool_rules;
+pub mod suggestion_templates;
+pub mod alias_style;
+pub mod shell_conflicts;
+pub mod command_index;
+pub mod ignored;
+pub mod accept;
+pub mod fuzzy;
+pub mod alias_lookup;
+pub mod change_alias;
@@ -0,0 +1,186 @@
+// Turns command lines with volatile arguments into templates, e.g.
+// `git checkout feature-123` -> `git checkout <branch>` and `vim src/a.rs` -> `vim <path>`,
+// so runs that only differ in those arguments are counted together.
+use crate::ops::parse_command::split_words;
+
+pub const PLACEHOLDERS: [&str; 5] = ["<url>", "<hash>", "<number>", "<path>", "<branch>"];
+
+// Subcommands whose next plain argument is a branch name
+const BRANCH_SUBCOMMANDS: [&str; 6] = ["checkout", "switch", "merge", "rebase", "cherry-pick", "worktree"];
+
+// Returns the template for `command`, or None if nothing in it looks volatile
+pub fn normalize_command(command: &str) -> Option<String> {
+    normalize_words(&split_words(command).ok()?)
+}
+
+// Same as normalize_command, for a command already split into shell words, so a quoted
+// argument like "fix bug" is classified as the one argument it is
+pub fn normalize_words(parts: &[String]) -> Option<String> {
+    if parts.len() < 2 {
+        return None;
+    }
+
+    let mut changed = false;
+    let mut normalized = vec![parts[0].clone()];
+    for (i, word) in parts.iter().enumerate().skip(1) {
+        let previous = unquote(&parts[i - 1]);
+        let placeholder = classify_word(&parts[0], previous, unquote(word));
+        match placeholder {
+            Some(p) => {
+                changed = true;
+                normalized.push(p.to_string());
+            }
+            None => normalized.push(word.clone()),
+        }
+    }
+
+    if changed {
+        Some(normalized.join(" "))
+    } else {
+        None
+    }
+}
+
+pub fn is_template(command: &str) -> bool {
+    command.split_whitespace().any(|word| PLACEHOLDERS.contains(&word))
+}
+
+// The part of a template before its first placeholder, which is what an alias can stand for
+pub fn stable_prefix(template: &str) -> String {
+    // Templates are joined with single spaces, splitting on them leaves quoted words as they were
+    template
+        .split(' ')
+        .take_while(|word| !PLACEHOLDERS.contains(word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// The text an alias for `command` should expand to: templates are aliased by their stable prefix
+pub fn alias_target(command: &str) -> String {
+    if is_template(command) {
+        stable_prefix(command)
+    } else {
+        command.to_string()
+    }
+}
+
+// A word wrapped in one pair of quotes, without them
+fn unquote(word: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = word.strip_prefix(quote).and_then(|w| w.strip_suffix(quote)) {
+            return inner;
+        }
+    }
+    word
+}
+
+fn classify_word(tool: &str, previous: &str, word: &str) -> Option<&'static str> {
+    // Flags are part of what the user wants to alias, only their values are volatile
+    if word.starts_with('-') || word.contains('=') || word.contains('$') {
+        return None;
+    }
+
+    if word.contains("://") || word.starts_with("git@") || word.starts_with("www.") {
+        return Some("<url>");
+    }
+    if is_number(word) {
+        return Some("<number>");
+    }
+    if is_hash(word) {
+        return Some("<hash>");
+    }
+    if tool == "git" && BRANCH_SUBCOMMANDS.contains(&previous) && word != "." {
+        return Some("<branch>");
+    }
+    if is_path(word) {
+        return Some("<path>");
+    }
+    if is_branch_like(word) {
+        return Some("<branch>");
+    }
+    None
+}
+
+fn is_number(word: &str) -> bool {
+    let mut seen_digit = false;
+    for c in word.chars() {
+        if c.is_ascii_digit() {
+            seen_digit = true;
+        } else if c != '.' {
+            return false;
+        }
+    }
+    seen_digit
+}
+
+fn is_hash(word: &str) -> bool {
+    (7..=64).contains(&word.len())
+        && word.chars().all(|c| c.is_ascii_hexdigit())
+        && word.chars().any(|c| c.is_ascii_digit())
+        && word.chars().any(|c| c.is_ascii_alphabetic())
+}
+
+fn is_path(word: &str) -> bool {
+    if word == "." || word == ".." {
+        return false;
+    }
+    if word.contains('/') || word.starts_with('~') {
+        return true;
+    }
+    // A file name with an extension, e.g. main.rs or notes.txt
+    match word.rsplit_once('.') {
+        Some((name, ext)) => {
+            !name.is_empty()
+                && (1..=5).contains(&ext.len())
+                && ext.chars().all(|c| c.is_ascii_alphanumeric())
+                && ext.chars().any(|c| c.is_ascii_alphabetic())
+        }
+        None => false,
+    }
+}
+
+fn is_branch_like(word: &str) -> bool {
+    // Ticket style names such as feature-123 or fix_42
+    let has_separator = word.contains('-') || word.contains('_');
+    let has_digit = word.chars().any(|c| c.is_ascii_digit());
+    let starts_with_letter = word.chars().next().map(|c| c.is_ascii_alphabetic()).unwrap_or(false);
+    has_separator && has_digit && starts_with_letter
+        && word.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_volatile_arguments() {
+        assert_eq!(normalize_command("git checkout feature-123").as_deref(), Some("git checkout <branch>"));
+        assert_eq!(normalize_command("vim src/main.rs").as_deref(), Some("vim <path>"));
+        assert_eq!(normalize_command("git show 1a2b3c4d").as_deref(), Some("git show <hash>"));
+        assert_eq!(normalize_command("kill 1234").as_deref(), Some("kill <number>"));
+        assert_eq!(normalize_command("git clone https://example.com/a.git").as_deref(), Some("git clone <url>"));
+    }
+
+    #[test]
+    fn keeps_flags_and_plain_words() {
+        assert_eq!(normalize_command("ls -la"), None);
+        assert_eq!(normalize_command("cargo build --release"), None);
+        assert_eq!(normalize_command("git checkout ."), None);
+        assert_eq!(normalize_command("make CFLAGS=-O2"), None);
+    }
+
+    #[test]
+    fn classifies_quoted_arguments_as_one_word() {
+        assert_eq!(normalize_command(r#"vim "my notes.txt""#).as_deref(), Some("vim <path>"));
+        assert_eq!(normalize_command(r#"git checkout "feature/x""#).as_deref(), Some("git checkout <branch>"));
+    }
+
+    #[test]
+    fn templates_are_aliased_by_their_stable_prefix() {
+        assert!(is_template("git checkout <branch>"));
+        assert!(!is_template("git checkout main"));
+        assert_eq!(stable_prefix("git push origin <branch>"), "git push origin");
+        assert_eq!(alias_target("git checkout <branch>"), "git checkout");
+        assert_eq!(alias_target("git status"), "git status");
+    }
+}
@@ -0,0 +1,465 @@
+// A small shell parser, just enough to split a command line into the simple commands
+// and pipelines it runs. Quoting, `&&`, `||`, `;`, `&`, pipes, subshells and redirections are
+// understood; words keep their original quoting so they can be written back into an alias.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimpleCommand {
+    pub words: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pipeline {
+    // Every simple command the pipeline runs, the ones inside a subshell stage included
+    pub commands: Vec<SimpleCommand>,
+    pub stages: Vec<Stage>,
+}
+
+// One stage of a pipeline as it was written: a simple command or a whole `( ... )` subshell,
+// with the pipe that follows it (`|` or `|&`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stage {
+    pub text: String,
+    pub pipe: Option<&'static str>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    UnterminatedQuote(char),
+    UnbalancedParenthesis,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::UnterminatedQuote(q) => write!(f, "unterminated {} quote", q),
+            ParseError::UnbalancedParenthesis => write!(f, "unbalanced parenthesis"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl SimpleCommand {
+    pub fn text(&self) -> String {
+        self.words.join(" ")
+    }
+}
+
+impl Pipeline {
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        for stage in &self.stages {
+            text.push_str(&stage.text);
+            if let Some(pipe) = stage.pipe {
+                text.push(' ');
+                text.push_str(pipe);
+                text.push(' ');
+            }
+        }
+        text
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Pipe(&'static str), // | or |&
+    Separator,          // && || ; & newline
+    Redirect,           // > >> < << <<< &> 2> ... (the target is the next word)
+    RedirectFd,         // >&2, 2>&1, <&- (no target word)
+    OpenParen(usize),   // with its position in the line, so a subshell stage keeps its text
+    CloseParen(usize),
+}
+
+// Shell keywords that start a command without being the command themselves
+const LEADING_KEYWORDS: [&str; 10] = ["if", "then", "else", "elif", "do", "while", "until", "!", "time", "{"];
+// Keywords that end a compound command and never carry a command
+const CLOSING_KEYWORDS: [&str; 4] = ["fi", "done", "esac", "}"];
+
+pub fn parse_command_line(line: &str) -> Result<Vec<Pipeline>, ParseError> {
+    let chars: Vec<char> = line.chars().collect();
+    let tokens = tokenize(&chars)?;
+    let mut parser = Parser { chars: &chars, tokens: &tokens, pos: 0 };
+    parser.parse_list(false)
+}
+
+struct Parser<'a> {
+    chars: &'a [char],
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+// The pipeline being read, its commands and stages grow together except inside a subshell
+#[derive(Default)]
+struct PipelineBuilder {
+    commands: Vec<SimpleCommand>,
+    stages: Vec<Stage>,
+}
+
+impl PipelineBuilder {
+    fn push_words(&mut self, words: &mut Vec<String>) {
+        let mut taken = std::mem::take(words);
+        while taken.first().map(|w| LEADING_KEYWORDS.contains(&w.as_str())).unwrap_or(false) {
+            taken.remove(0);
+        }
+        let is_loop_header = matches!(taken.first().map(|w| w.as_str()), Some("for") | Some("case") | Some("select"));
+        let is_closing = taken.len() == 1 && CLOSING_KEYWORDS.contains(&taken[0].as_str());
+        if !taken.is_empty() && !is_loop_header && !is_closing {
+            let command = SimpleCommand { words: taken };
+            self.stages.push(Stage { text: command.text(), pipe: None });
+            self.commands.push(command);
+        }
+    }
+
+    fn finish(&mut self, pipelines: &mut Vec<Pipeline>) {
+        let taken = std::mem::take(self);
+        if !taken.commands.is_empty() {
+            pipelines.push(Pipeline { commands: taken.commands, stages: taken.stages });
+        }
+    }
+}
+
+impl Parser<'_> {
+    // Reads pipelines up to the end of the line, or up to the `)` closing a subshell when `nested`
+    fn parse_list(&mut self, nested: bool) -> Result<Vec<Pipeline>, ParseError> {
+        let mut pipelines = Vec::new();
+        let mut pipeline = PipelineBuilder::default();
+        let mut words: Vec<String> = Vec::new();
+        let mut skip_next_word = false;
+
+        while let Some(token) = self.tokens.get(self.pos) {
+            match token {
+                Token::Word(word) => {
+                    if skip_next_word {
+                        skip_next_word = false;
+                    } else {
+                        words.push(word.clone());
+                    }
+                }
+                Token::Redirect => skip_next_word = true,
+                Token::RedirectFd => {}
+                Token::Pipe(pipe) => {
+                    pipeline.push_words(&mut words);
+                    if let Some(stage) = pipeline.stages.last_mut() {
+                        stage.pipe = Some(pipe);
+                    }
+                }
+                Token::Separator => {
+                    pipeline.push_words(&mut words);
+                    pipeline.finish(&mut pipelines);
+                }
+                Token::OpenParen(open) => {
+                    // A subshell is a single stage of the pipeline around it, running everything inside
+                    pipeline.push_words(&mut words);
+                    self.pos += 1;
+                    let inner = self.parse_list(true)?;
+                    let Some(Token::CloseParen(close)) = self.tokens.get(self.pos) else {
+                        return Err(ParseError::UnbalancedParenthesis);
+                    };
+                    pipeline.stages.push(Stage { text: self.chars[*open..=*close].iter().collect(), pipe: None });
+                    pipeline.commands.extend(inner.into_iter().flat_map(|p| p.commands));
+                }
+                Token::CloseParen(_) => {
+                    if !nested {
+                        return Err(ParseError::UnbalancedParenthesis);
+                    }
+                    pipeline.push_words(&mut words);
+                    pipeline.finish(&mut pipelines);
+                    return Ok(pipelines);
+                }
+            }
+            self.pos += 1;
+        }
+        if nested {
+            return Err(ParseError::UnbalancedParenthesis);
+        }
+        pipeline.push_words(&mut words);
+        pipeline.finish(&mut pipelines);
+        Ok(pipelines)
+    }
+}
+
+// The words of a command line with their quoting kept, so `"fix bug"` stays one word.
+// Operators and redirections are left out.
+pub fn split_words(line: &str) -> Result<Vec<String>, ParseError> {
+    let chars: Vec<char> = line.chars().collect();
+    Ok(tokenize(&chars)?
+        .into_iter()
+        .filter_map(|token| match token {
+            Token::Word(word) => Some(word),
+            _ => None,
+        })
+        .collect())
+}
+
+fn tokenize(chars: &[char]) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let mut i = 0;
+
+    let push_word = |word: &mut String, tokens: &mut Vec<Token>| {
+        if !word.is_empty() {
+            tokens.push(Token::Word(std::mem::take(word)));
+        }
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            ' ' | '\t' => {
+                push_word(&mut word, &mut tokens);
+                i += 1;
+            }
+            '\n' | ';' => {
+                push_word(&mut word, &mut tokens);
+                tokens.push(Token::Separator);
+                i += 1;
+            }
+            '#' if word.is_empty() => {
+                // Comment until the end of the line
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '\\' => {
+                word.push(c);
+                if let Some(n) = next {
+                    word.push(n);
+                }
+                i += 2;
+            }
+            '\'' => {
+                let end = find_closing(chars, i + 1, '\'').ok_or(ParseError::UnterminatedQuote('\''))?;
+                word.extend(&chars[i..=end]);
+                i = end + 1;
+            }
+            '"' => {
+                let end = find_closing_double(chars, i + 1).ok_or(ParseError::UnterminatedQuote('"'))?;
+                word.extend(&chars[i..=end]);
+                i = end + 1;
+            }
+            '`' => {
+                let end = find_closing(chars, i + 1, '`').ok_or(ParseError::UnterminatedQuote('`'))?;
+                word.extend(&chars[i..=end]);
+                i = end + 1;
+            }
+            '$' if next == Some('(') || next == Some('{') => {
+                // Command/arithmetic substitution and parameter expansion stay inside the word
+                let (open, close) = if next == Some('(') { ('(', ')') } else { ('{', '}') };
+                let end = find_matching(chars, i + 1, open, close).ok_or(ParseError::UnbalancedParenthesis)?;
+                word.extend(&chars[i..=end]);
+                i = end + 1;
+            }
+            '&' if next == Some('&') => {
+                push_word(&mut word, &mut tokens);
+                tokens.push(Token::Separator);
+                i += 2;
+            }
+            '&' if next == Some('>') => {
+                push_word(&mut word, &mut tokens);
+                i += if chars.get(i + 2) == Some(&'>') { 3 } else { 2 };
+                tokens.push(Token::Redirect);
+            }
+            '&' => {
+                push_word(&mut word, &mut tokens);
+                tokens.push(Token::Separator);
+                i += 1;
+            }
+            '|' if next == Some('|') => {
+                push_word(&mut word, &mut tokens);
+                tokens.push(Token::Separator);
+                i += 2;
+            }
+            '|' => {
+                push_word(&mut word, &mut tokens);
+                if next == Some('&') {
+                    tokens.push(Token::Pipe("|&"));
+                    i += 2;
+                } else {
+                    tokens.push(Token::Pipe("|"));
+                    i += 1;
+                }
+            }
+            '>' | '<' => {
+                // A word made only of digits right before the operator is a file descriptor (2>file)
+                if !word.chars().all(|ch| ch.is_ascii_digit()) {
+                    push_word(&mut word, &mut tokens);
+                }
+                word.clear();
+                let mut j = i + 1;
+                while j < chars.len() && (chars[j] == '>' || chars[j] == '<') {
+                    j += 1;
+                }
+                if chars.get(j) == Some(&'(') {
+                    // Process substitution <(...) / >(...) is an argument, not a redirection
+                    let end = find_matching(chars, j, '(', ')').ok_or(ParseError::UnbalancedParenthesis)?;
+                    word.extend(&chars[i..=end]);
+                    i = end + 1;
+                    continue;
+                }
+                if chars.get(j) == Some(&'&') {
+                    // >&2, 2>&1, <&-
+                    j += 1;
+                    while j < chars.len() && (chars[j].is_ascii_digit() || chars[j] == '-') {
+                        j += 1;
+                    }
+                    tokens.push(Token::RedirectFd);
+                } else {
+                    tokens.push(Token::Redirect);
+                }
+                i = j;
+            }
+            '(' if word.is_empty() => {
+                tokens.push(Token::OpenParen(i));
+                i += 1;
+            }
+            '(' => {
+                // Part of a word, e.g. `arr=(a b)` or a `name()` function definition
+                let end = find_matching(chars, i, '(', ')').ok_or(ParseError::UnbalancedParenthesis)?;
+                word.extend(&chars[i..=end]);
+                i = end + 1;
+            }
+            ')' => {
+                push_word(&mut word, &mut tokens);
+                tokens.push(Token::CloseParen(i));
+                i += 1;
+            }
+            _ => {
+                word.push(c);
+                i += 1;
+            }
+        }
+    }
+    push_word(&mut word, &mut tokens);
+    Ok(tokens)
+}
+
+fn find_closing(chars: &[char], start: usize, quote: char) -> Option<usize> {
+    (start..chars.len()).find(|&j| chars[j] == quote)
+}
+
+fn find_closing_double(chars: &[char], start: usize) -> Option<usize> {
+    let mut j = start;
+    while j < chars.len() {
+        match chars[j] {
+            '\\' => j += 2,
+            '"' => return Some(j),
+            _ => j += 1,
+        }
+    }
+    None
+}
+
+// Index of the bracket closing the one at `start`, skipping over quoted text
+fn find_matching(chars: &[char], start: usize, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    let mut j = start;
+    while j < chars.len() {
+        let c = chars[j];
+        if c == '\\' {
+            j += 2;
+            continue;
+        }
+        if c == '\'' {
+            j = find_closing(chars, j + 1, '\'')? + 1;
+            continue;
+        }
+        if c == '"' {
+            j = find_closing_double(chars, j + 1)? + 1;
+            continue;
+        }
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(j);
+            }
+        }
+        j += 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(line: &str) -> Vec<Vec<String>> {
+        parse_command_line(line)
+            .unwrap()
+            .into_iter()
+            .flat_map(|p| p.commands)
+            .map(|c| c.words)
+            .collect()
+    }
+
+    #[test]
+    fn keeps_quoted_words_whole() {
+        assert_eq!(words(r#"git commit -m "fix bug""#), vec![vec!["git", "commit", "-m", r#""fix bug""#]]);
+        assert_eq!(words("echo 'a && b; c | d'"), vec![vec!["echo", "'a && b; c | d'"]]);
+        assert_eq!(words(r#"echo "say \"hi\"""#), vec![vec!["echo", r#""say \"hi\"""#]]);
+    }
+
+    #[test]
+    fn keeps_escaped_characters_in_the_word() {
+        assert_eq!(words(r"echo a\ b \;"), vec![vec!["echo", r"a\ b", r"\;"]]);
+    }
+
+    #[test]
+    fn splits_on_separators() {
+        assert_eq!(
+            words("cd src && make; ls || true & wait"),
+            vec![vec!["cd", "src"], vec!["make"], vec!["ls"], vec!["true"], vec!["wait"]]
+        );
+        assert_eq!(parse_command_line("cd src && make; ls").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn pipelines_keep_their_pipes() {
+        let pipelines = parse_command_line("cat f | grep x |& tee log").unwrap();
+        assert_eq!(pipelines.len(), 1);
+        assert_eq!(pipelines[0].commands.len(), 3);
+        assert_eq!(pipelines[0].text(), "cat f | grep x |& tee log");
+    }
+
+    #[test]
+    fn drops_redirections() {
+        assert_eq!(words("make 2>&1 > build.log"), vec![vec!["make"]]);
+        assert_eq!(words("sort < in.txt &> out.txt"), vec![vec!["sort"]]);
+        assert_eq!(words("diff <(ls a) <(ls b)"), vec![vec!["diff", "<(ls a)", "<(ls b)"]]);
+    }
+
+    #[test]
+    fn substitutions_stay_inside_the_word() {
+        assert_eq!(words("echo $(date +%s) ${HOME}"), vec![vec!["echo", "$(date +%s)", "${HOME}"]]);
+    }
+
+    #[test]
+    fn a_subshell_is_one_stage() {
+        let pipelines = parse_command_line("a | (b && c)").unwrap();
+        assert_eq!(pipelines.len(), 1);
+        assert_eq!(pipelines[0].stages.len(), 2);
+        assert_eq!(pipelines[0].commands.len(), 3);
+        assert_eq!(pipelines[0].text(), "a | (b && c)");
+    }
+
+    #[test]
+    fn skips_keywords() {
+        assert_eq!(words("if true; then make; fi"), vec![vec!["true"], vec!["make"]]);
+        assert_eq!(words("for f in *; do echo $f; done"), vec![vec!["echo", "$f"]]);
+    }
+
+    #[test]
+    fn reports_broken_lines() {
+        assert_eq!(parse_command_line(r#"echo "x"#), Err(ParseError::UnterminatedQuote('"')));
+        assert_eq!(parse_command_line("(a && b"), Err(ParseError::UnbalancedParenthesis));
+        assert_eq!(parse_command_line("a)"), Err(ParseError::UnbalancedParenthesis));
+    }
+
+    #[test]
+    fn split_words_leaves_out_operators() {
+        assert_eq!(split_words(r#"git add . && git commit -m "a b""#).unwrap(), vec!["git", "add", ".", "git", "commit", "-m", r#""a b""#]);
+    }
+}
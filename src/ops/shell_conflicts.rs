@@ -0,0 +1,163 @@
+// Names an alias mustn't take: executables on PATH, shell builtins and reserved words, aliases
+// and functions from the user's rc files, git aliases, and names a target shell can't define.
+use crate::database::database_structs::CommandIndex;
+use crate::ops::command_index::load_index;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shell {
+    Sh,
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl Shell {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.rsplit('/').next().unwrap_or(name) {
+            "sh" | "dash" | "ash" | "posix" => Some(Shell::Sh),
+            "bash" => Some(Shell::Bash),
+            "zsh" => Some(Shell::Zsh),
+            "fish" => Some(Shell::Fish),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Shell::Sh => "POSIX sh",
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Fish => "fish",
+        }
+    }
+
+    fn builtins(&self) -> &'static [&'static str] {
+        match self {
+            Shell::Sh => SH_BUILTINS,
+            Shell::Bash => BASH_BUILTINS,
+            Shell::Zsh => ZSH_BUILTINS,
+            Shell::Fish => FISH_BUILTINS,
+        }
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        match self {
+            Shell::Sh => SH_KEYWORDS,
+            Shell::Bash => BASH_KEYWORDS,
+            Shell::Zsh => ZSH_KEYWORDS,
+            Shell::Fish => FISH_KEYWORDS,
+        }
+    }
+
+    // Whether `alias name=...` (`alias name ...` in fish) can define this name at all
+    fn accepts_name(&self, name: &str) -> bool {
+        if name.is_empty() || name.starts_with('-') {
+            return false;
+        }
+        match self {
+            // Alphanumerics, underscore and ! % , - @ are all POSIX guarantees
+            Shell::Sh => name.chars().all(|c| c.is_ascii_alphanumeric() || "_!%,-@".contains(c)),
+            _ => !name.chars().any(|c| c.is_whitespace() || "/$`='\"\\|&;()<>".contains(c)),
+        }
+    }
+}
+
+const SH_BUILTINS: &[&str] = &[
+    "break", ":", ".", "continue", "eval", "exec", "exit", "export", "readonly", "return", "set", "shift", "times",
+    "trap", "unset", "alias", "bg", "cd", "command", "false", "fc", "fg", "getopts", "hash", "jobs", "kill", "newgrp",
+    "pwd", "read", "true", "type", "ulimit", "umask", "unalias", "wait",
+];
+const SH_KEYWORDS: &[&str] = &[
+    "!", "{", "}", "case", "do", "done", "elif", "else", "esac", "fi", "for", "if", "in", "then", "until", "while",
+];
+
+const BASH_BUILTINS: &[&str] = &[
+    ":", ".", "[", "alias", "bg", "bind", "break", "builtin", "caller", "cd", "command", "compgen", "complete",
+    "compopt", "continue", "declare", "dirs", "disown", "echo", "enable", "eval", "exec", "exit", "export", "false",
+    "fc", "fg", "getopts", "hash", "help", "history", "jobs", "kill", "let", "local", "logout", "mapfile", "popd",
+    "printf", "pushd", "pwd", "read", "readarray", "readonly", "return", "set", "shift", "shopt", "source", "suspend",
+    "test", "times", "trap", "true", "type", "typeset", "ulimit", "umask", "unalias", "unset", "wait",
+];
+const BASH_KEYWORDS: &[&str] = &[
+    "!", "[[", "]]", "{", "}", "case", "coproc", "do", "done", "elif", "else", "esac", "fi", "for", "function", "if",
+    "in", "select", "then", "time", "until", "while",
+];
+
+const ZSH_BUILTINS: &[&str] = &[
+    ":", ".", "[", "alias", "autoload", "bg", "bindkey", "break", "builtin", "bye", "cd", "chdir", "command",
+    "compadd", "compcall", "compctl", "compdef", "compset", "comptags", "comptry", "continue", "declare", "dirs",
+    "disable", "disown", "echo", "echotc", "echoti", "emulate", "enable", "eval", "exec", "exit", "export", "false",
+    "fc", "fg", "float", "functions", "getln", "getopts", "hash", "history", "integer", "jobs", "kill", "let", "limit",
+    "local", "logout", "noglob", "popd", "print", "printf", "pushd", "pushln", "pwd", "r", "read", "readonly",
+    "rehash", "return", "sched", "set", "setopt", "shift", "source", "suspend", "test", "times", "trap", "true",
+    "ttyctl", "type", "typeset", "ulimit", "umask", "unalias", "unfunction", "unhash", "unlimit", "unset", "unsetopt",
+    "vared", "wait", "whence", "where", "which", "zcompile", "zformat", "zle", "zmodload", "zparseopts", "zprof",
+    "zpty", "zregexparse", "zsocket", "zstyle", "ztcp",
+];
+const ZSH_KEYWORDS: &[&str] = &[
+    "!", "[[", "]]", "{", "}", "case", "coproc", "do", "done", "elif", "else", "end", "esac", "fi", "for", "foreach",
+    "function", "if", "in", "nocorrect", "repeat", "select", "then", "time", "until", "while",
+];
+
+const FISH_BUILTINS: &[&str] = &[
+    ".", ":", "[", "_", "abbr", "alias", "bg", "bind", "block", "breakpoint", "cd", "commandline", "complete",
+    "contains", "count", "dirh", "dirs", "disown", "echo", "emit", "exit", "false", "fg", "functions", "history",
+    "jobs", "math", "path", "popd", "printf", "prevd", "nextd", "pushd", "pwd", "random", "read", "realpath",
+    "set_color", "source", "true", "type", "ulimit", "wait",
+];
+// Fish refuses these as function names, and its aliases are functions
+const FISH_KEYWORDS: &[&str] = &[
+    "and", "argparse", "begin", "break", "builtin", "case", "command", "continue", "else", "end", "eval", "exec", "for",
+    "function", "if", "not", "or", "return", "set", "status", "string", "switch", "test", "time", "while",
+];
+
+// The default checks against nothing, for when the machine isn't what matters
+#[derive(Default)]
+pub struct ConflictChecker {
+    shells: Vec<Shell>,
+    index: CommandIndex,
+}
+
+impl ConflictChecker {
+    pub fn load() -> Self {
+        let configured: Vec<String> = crate::database::persistence::load_config()
+            .map(|config| config.target_shells)
+            .unwrap_or_default();
+        let mut shells: Vec<Shell> = configured.iter().filter_map(|name| Shell::from_name(name)).collect();
+        if shells.is_empty() {
+            // The shell in use, bash if it's nothing we know
+            shells.push(std::env::var("SHELL").ok().and_then(|s| Shell::from_name(&s)).unwrap_or(Shell::Bash));
+        }
+        let index = load_index(&shells);
+        Self { shells, index }
+    }
+
+    // Why `alias` can't be used, None if nothing is in the way
+    pub fn conflict(&self, alias: &str) -> Option<String> {
+        for shell in &self.shells {
+            if !shell.accepts_name(alias) {
+                return Some(format!("is not a valid alias name in {}", shell.name()));
+            }
+            if shell.keywords().contains(&alias) {
+                return Some(format!("is a {} reserved word", shell.name()));
+            }
+            if shell.builtins().contains(&alias) {
+                return Some(format!("is a {} builtin", shell.name()));
+            }
+        }
+        if self.index.executables.contains(alias) {
+            return Some("is a command on PATH".to_string());
+        }
+        if let Some(file) = self.index.shell_aliases.get(alias) {
+            return Some(format!("is already an alias in {}", file));
+        }
+        if let Some(file) = self.index.functions.get(alias) {
+            return Some(format!("is a shell function defined in {}", file));
+        }
+        // `git co` muscle memory turns into running the wrong thing when `co` also exists on its own
+        if self.index.git_aliases.contains(alias) {
+            return Some("is one of your git aliases".to_string());
+        }
+        None
+    }
+}
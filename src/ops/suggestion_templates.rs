@@ -0,0 +1,157 @@
+// Alias naming rules declared in ~/.alman/config.json under "suggestion_templates", for
+// conventions the built-in generators can't guess, e.g.
+//   { "tool": "kubectl", "alias": "k{initials}", "priority": 110 }
+//   { "regex": "^\\./scripts/(?P<name>[\\w-]+)\\.sh", "alias": "x-{name}" }
+use crate::ops::alias_suggestions::AliasSuggestion;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuggestionTemplate {
+    // Every condition that is set has to hold for the template to apply
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subcommand: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>,
+    // Literal text plus {variable} or {variable[range]} placeholders
+    pub alias: String,
+    #[serde(default = "default_priority")]
+    pub priority: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+// Level with the bundled tool rules, so a template for the same tool competes on length
+fn default_priority() -> i32 {
+    100
+}
+
+pub struct CompiledTemplate {
+    template: SuggestionTemplate,
+    regex: Option<Regex>,
+}
+
+impl CompiledTemplate {
+    pub fn new(template: SuggestionTemplate) -> Result<Self, regex::Error> {
+        let regex = template.regex.as_deref().map(Regex::new).transpose()?;
+        Ok(Self { template, regex })
+    }
+
+    pub fn reason(&self) -> String {
+        format!("Template: {}", self.template.description.as_deref().unwrap_or(&self.template.alias))
+    }
+
+    pub fn priority(&self) -> i32 {
+        self.template.priority
+    }
+
+    pub fn suggest(&self, command: &str) -> Option<AliasSuggestion> {
+        let words: Vec<&str> = command.split_whitespace().collect();
+        let tool = *words.first()?;
+        let args: Vec<&str> = words[1..].iter().filter(|w| !w.starts_with('-')).copied().collect();
+        let sub = args.first().copied();
+
+        if self.template.tool.as_deref().is_some_and(|t| t != tool) {
+            return None;
+        }
+        if self.template.subcommand.is_some() && self.template.subcommand.as_deref() != sub {
+            return None;
+        }
+        let captures = match &self.regex {
+            Some(regex) => Some(regex.captures(command)?),
+            None => None,
+        };
+
+        let cmd = tool.rsplit('/').next().unwrap_or(tool);
+        let cmd = cmd.split('.').next().unwrap_or(cmd);
+        let initials: String = args.iter().filter_map(|w| w.chars().next()).collect();
+
+        let lookup = |name: &str| -> Option<String> {
+            match name {
+                "tool" => Some(tool.to_string()),
+                "cmd" => Some(cmd.to_string()),
+                "sub" => sub.map(|s| s.to_string()),
+                "initials" => Some(initials.clone()),
+                _ => {
+                    let captures = captures.as_ref()?;
+                    let group = match name.parse::<usize>() {
+                        Ok(index) => captures.get(index),
+                        Err(_) => captures.name(name),
+                    };
+                    group.map(|m| m.as_str().to_string())
+                }
+            }
+        };
+
+        let alias = render(&self.template.alias, lookup)?;
+        if alias.is_empty() || !alias.chars().all(|c| c.is_alphanumeric() || "-_.".contains(c)) {
+            return None;
+        }
+        Some(AliasSuggestion {
+            alias,
+            command: command.to_string(),
+            reason: self.reason(),
+        })
+    }
+}
+
+// Templates from the config with their regexes compiled, broken ones reported and skipped
+pub fn load_templates() -> Vec<CompiledTemplate> {
+    let templates = crate::database::persistence::load_config()
+        .map(|config| config.suggestion_templates)
+        .unwrap_or_default();
+    templates
+        .into_iter()
+        .filter_map(|template| {
+            let alias = template.alias.clone();
+            CompiledTemplate::new(template)
+                .map_err(|e| eprintln!("Skipping suggestion template '{}': {}", alias, e))
+                .ok()
+        })
+        .collect()
+}
+
+// Fills in the placeholders of `pattern`. None when a variable is unknown, empty or the
+// range falls outside it, in which case the template doesn't apply to the command.
+fn render(pattern: &str, lookup: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let mut out = String::new();
+    let mut rest = pattern;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let end = rest[start..].find('}')? + start;
+        let placeholder = &rest[start + 1..end];
+        let (name, range) = match placeholder.find('[') {
+            Some(open) => (&placeholder[..open], Some(placeholder[open + 1..].strip_suffix(']')?)),
+            None => (placeholder, None),
+        };
+        let value: Vec<char> = lookup(name.trim())?.chars().collect();
+        if value.is_empty() {
+            return None;
+        }
+        match range {
+            Some(range) => out.extend(&value[slice_bounds(range, value.len())?]),
+            None => out.extend(value),
+        }
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    Some(out)
+}
+
+// "0" -> 0..1, "0..2" -> 0..2, "1.." -> 1..len, "..2" -> 0..2; an end past the value is clamped
+fn slice_bounds(range: &str, len: usize) -> Option<std::ops::Range<usize>> {
+    let parse = |s: &str, default: usize| if s.trim().is_empty() { Some(default) } else { s.trim().parse::<usize>().ok() };
+    let (start, end) = match range.split_once("..") {
+        Some((start, end)) => (parse(start, 0)?, parse(end, len)?.min(len)),
+        None => {
+            let index = parse(range, 0)?;
+            (index, index + 1)
+        }
+    };
+    if start >= end || end > len {
+        return None;
+    }
+    Some(start..end)
+}
@@ -0,0 +1,184 @@
+// Conventional aliases for well known tools, kept as data in rules/*.json and embedded in
+// the binary. Files in ~/.alman/rules/ extend those packs (or replace them with
+// "replace": true) and can add packs for tools that aren't covered.
+use crate::ops::alias_suggestions::AliasSuggestion;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+const BUNDLED_PACKS: [&str; 18] = [
+    include_str!("../../rules/apt.json"),
+    include_str!("../../rules/cargo.json"),
+    include_str!("../../rules/dnf.json"),
+    include_str!("../../rules/docker.json"),
+    include_str!("../../rules/git.json"),
+    include_str!("../../rules/go.json"),
+    include_str!("../../rules/helm.json"),
+    include_str!("../../rules/kubectl.json"),
+    include_str!("../../rules/make.json"),
+    include_str!("../../rules/npm.json"),
+    include_str!("../../rules/pacman.json"),
+    include_str!("../../rules/pip.json"),
+    include_str!("../../rules/pnpm.json"),
+    include_str!("../../rules/podman.json"),
+    include_str!("../../rules/ssh.json"),
+    include_str!("../../rules/systemctl.json"),
+    include_str!("../../rules/terraform.json"),
+    include_str!("../../rules/yarn.json"),
+];
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ToolPack {
+    pub tool: String,
+    // Drop the bundled pack for this tool instead of extending it
+    #[serde(default)]
+    pub replace: bool,
+    #[serde(default)]
+    pub rules: Vec<ToolRule>,
+    // Flags worth keeping in the alias, with the letter they add (e.g. "--release": "r")
+    #[serde(default)]
+    pub flags: BTreeMap<String, String>,
+    // The first argument names the target, as with `ssh host`, and the alias is its short name
+    #[serde(default)]
+    pub alias_from_argument: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ToolRule {
+    #[serde(rename = "match")]
+    pub words: Vec<String>,
+    pub alias: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct ToolRules {
+    packs: HashMap<String, ToolPack>,
+}
+
+impl ToolRules {
+    pub fn load() -> Self {
+        let mut rules = Self::default();
+        for pack in BUNDLED_PACKS {
+            match serde_json::from_str::<ToolPack>(pack) {
+                Ok(pack) => rules.merge(pack),
+                Err(e) => eprintln!("Invalid bundled tool rules: {}", e),
+            }
+        }
+        for pack in load_user_packs() {
+            rules.merge(pack);
+        }
+        rules
+    }
+
+    fn merge(&mut self, pack: ToolPack) {
+        match self.packs.get_mut(&pack.tool) {
+            Some(existing) if !pack.replace => {
+                // User rules go first so they win over bundled ones matching the same words
+                let mut rules = pack.rules;
+                rules.append(&mut existing.rules);
+                existing.rules = rules;
+                existing.flags.extend(pack.flags);
+                existing.alias_from_argument |= pack.alias_from_argument;
+            }
+            _ => {
+                self.packs.insert(pack.tool.clone(), pack);
+            }
+        }
+    }
+
+    // The conventional alias for `command`, e.g. `cargo build --release` -> cbr. A leading
+    // `sudo` is kept in the command and adds an `s` in front of the alias.
+    pub fn suggest(&self, command: &str) -> Option<AliasSuggestion> {
+        let mut words: Vec<&str> = command.split_whitespace().collect();
+        let sudo = words.first() == Some(&"sudo");
+        if sudo {
+            words.remove(0);
+        }
+        let (tool, args) = words.split_first()?;
+        let pack = self.packs.get(*tool)?;
+
+        let (alias, matched, description) = if pack.alias_from_argument {
+            let target = args.first()?;
+            let short_name = target.split('@').next_back()?.split('.').next()?;
+            if short_name.len() < 2 {
+                return None;
+            }
+            (short_name.to_string(), vec![target.to_string()], format!("{} to {}", tool, target))
+        } else {
+            // Flags in front of the subcommand, as in `systemctl --user restart`
+            let leading: Vec<&str> = args.iter().take_while(|a| pack.flags.contains_key(**a)).copied().collect();
+            let rest = &args[leading.len()..];
+
+            // The rule matching the most words wins, the earliest one on a tie (max_by_key
+            // keeps the last maximum, hence the rev)
+            let rule = pack
+                .rules
+                .iter()
+                .rev()
+                .filter(|rule| !rule.words.is_empty() && rule.words.len() <= rest.len())
+                .filter(|rule| rule.words.iter().zip(rest.iter()).all(|(w, a)| w == a))
+                .max_by_key(|rule| rule.words.len())?;
+
+            let trailing: Vec<&str> = rest[rule.words.len()..]
+                .iter()
+                .take_while(|a| pack.flags.contains_key(**a))
+                .copied()
+                .collect();
+
+            let mut alias = rule.alias.clone();
+            let mut seen_letters = Vec::new();
+            for flag in leading.iter().chain(trailing.iter()) {
+                let letter = &pack.flags[*flag];
+                // -A and --all both mean "a", don't add it twice
+                if !seen_letters.contains(letter) {
+                    alias.push_str(letter);
+                    seen_letters.push(letter.clone());
+                }
+            }
+
+            let mut matched: Vec<String> = leading.iter().map(|s| s.to_string()).collect();
+            matched.extend(rule.words.iter().cloned());
+            matched.extend(trailing.iter().map(|s| s.to_string()));
+            let description = rule.description.clone().unwrap_or_else(|| rule.words.join(" "));
+            (alias, matched, format!("{} {}", tool, description))
+        };
+
+        let mut expansion = Vec::new();
+        if sudo {
+            expansion.push("sudo".to_string());
+        }
+        expansion.push(tool.to_string());
+        expansion.extend(matched);
+
+        Some(AliasSuggestion {
+            alias: if sudo { format!("s{}", alias) } else { alias },
+            command: expansion.join(" "),
+            reason: format!("Rule: {}", description),
+        })
+    }
+}
+
+fn load_user_packs() -> Vec<ToolPack> {
+    use crate::database::persistence::get_rules_dir;
+
+    let mut packs = Vec::new();
+    let mut paths: Vec<_> = match std::fs::read_dir(get_rules_dir()) {
+        Ok(entries) => entries.flatten().map(|e| e.path()).collect(),
+        Err(_) => return packs,
+    };
+    paths.sort();
+    for path in paths {
+        if path.extension().map(|ext| ext != "json").unwrap_or(true) {
+            continue;
+        }
+        let parsed = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str::<ToolPack>(&content).map_err(|e| e.to_string()));
+        match parsed {
+            Ok(pack) => packs.push(pack),
+            Err(e) => eprintln!("Skipping tool rules {}: {}", path.display(), e),
+        }
+    }
+    packs
+}
@@ -0,0 +1,330 @@
+use crate::database::database_structs::{Database, DayUsage, DeletedCommands, UsageHistory};
+use crate::database::persistence::{get_usage_history_path, load_usage_history, save_usage_history};
+use crate::ops::alias_ops::get_aliases_from_multiple_files;
+use crate::ops::keystroke_savings::saved_per_run;
+use crate::ops::parse_command::parse_command_line;
+use chrono::{Datelike, Duration, Local, TimeZone, Timelike};
+use colored::*;
+use serde::Serialize;
+use std::collections::HashMap;
+
+const TOP_TOOLS: usize = 10;
+const TOP_ALIASES: usize = 10;
+pub const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+// Keeps the per-day usage history up to date while commands are being recorded
+pub struct UsageRecorder {
+    pub history: UsageHistory,
+    aliases: HashMap<String, String>,
+    path: String,
+}
+
+impl UsageRecorder {
+    pub fn load(alias_file_paths: &[String]) -> Self {
+        let path = get_usage_history_path();
+        let history = load_usage_history(&path).unwrap_or_default();
+        let aliases = get_aliases_from_multiple_files(alias_file_paths).into_iter().collect();
+        Self { history, aliases, path }
+    }
+
+    // Has to run before the line is inserted into the database, otherwise every line looks seen
+    pub fn record(&mut self, command_line: &str, timestamp: i64, db: &Database, deleted_commands: &DeletedCommands) {
+        let line = command_line.split_whitespace().collect::<Vec<_>>().join(" ");
+        let first_word = match line.split_whitespace().next() {
+            Some(word) => word,
+            None => return,
+        };
+        if is_own_binary(first_word) {
+            return;
+        }
+        let time = match Local.timestamp_opt(timestamp, 0).single() {
+            Some(time) => time,
+            None => return,
+        };
+
+        let day = self.history.days.entry(time.format("%Y-%m-%d").to_string()).or_default();
+        day.commands += 1;
+        day.hours[time.hour() as usize] += 1;
+
+        // The database keeps `a && b` as its simple commands, so each of those is checked
+        let pipelines = parse_command_line(&line).ok();
+        let mut simple_commands: Vec<String> = match &pipelines {
+            Some(pipelines) => pipelines.iter().flat_map(|p| p.commands.iter().map(|c| c.text())).collect(),
+            None => vec![line.clone()],
+        };
+        simple_commands.sort();
+        simple_commands.dedup();
+        for command in &simple_commands {
+            // Single short words are never stored, so the database can't tell whether they are new
+            let storable = command.len() > 5 || command.contains(' ');
+            if storable && !db.reverse_command_map.contains_key(command) && !deleted_commands.deleted_commands.contains(command) {
+                day.new_commands += 1;
+            }
+        }
+
+        if let Some(expansion) = self.aliases.get(first_word) {
+            day.alias_runs += 1;
+            day.keystrokes_saved += saved_per_run(expansion, first_word) as u64;
+            *day.aliases.entry(first_word.to_string()).or_insert(0) += 1;
+        } else if self.aliases.values().any(|command| line == *command || line.starts_with(&format!("{} ", command))) {
+            day.full_runs_with_alias += 1;
+        }
+
+        // Count the tool behind an alias rather than the alias itself
+        let tools: Vec<String> = match pipelines {
+            Some(pipelines) => pipelines
+                .iter()
+                .flat_map(|p| p.commands.iter().map(|c| c.words[0].clone()))
+                .collect(),
+            None => vec![first_word.to_string()],
+        };
+        for tool in tools {
+            let tool = match self.aliases.get(&tool) {
+                Some(command) => command.split_whitespace().next().unwrap_or(&tool).to_string(),
+                None => tool,
+            };
+            *day.tools.entry(tool).or_insert(0) += 1;
+        }
+    }
+
+    pub fn save(&self) {
+        if let Err(e) = save_usage_history(&self.history, &self.path) {
+            eprintln!("Failed to save usage history: {}", e);
+        }
+    }
+}
+
+fn is_own_binary(word: &str) -> bool {
+    std::env::args()
+        .next()
+        .and_then(|path| std::path::Path::new(&path).file_name().map(|f| f.to_string_lossy().to_string()))
+        .map(|name| name == word)
+        .unwrap_or(false)
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatsReport {
+    pub from: String,
+    pub to: String,
+    pub total_commands: u64,
+    pub active_days: usize,
+    pub average_per_active_day: f64,
+    pub top_tools: Vec<ToolCount>,
+    pub days: Vec<DayCount>,
+    pub hours: [u64; 24],
+    pub weekdays: [u64; 7], // Monday first
+    pub new_commands_per_week: Vec<WeekCount>,
+    pub alias_runs: u64,
+    pub full_runs_with_alias: u64,
+    // Share of runs that could use an alias and did
+    pub alias_usage_ratio: f64,
+    pub keystrokes_saved: u64,
+    // Since the history started, not just in this period
+    pub keystrokes_saved_all_time: u64,
+    pub top_aliases: Vec<AliasCount>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ToolCount {
+    pub tool: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AliasCount {
+    pub alias: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DayCount {
+    pub day: String,
+    pub commands: u32,
+    pub new_commands: u32,
+    pub keystrokes_saved: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WeekCount {
+    pub week: String, // ISO week, e.g. 2025-W07
+    pub new_commands: u64,
+    pub cumulative: u64,
+}
+
+// Summarises the last `days` days of history, today included
+pub fn build_report(history: &UsageHistory, days: usize) -> StatsReport {
+    let today = Local::now().date_naive();
+    let from = today - Duration::days(days.max(1) as i64 - 1);
+
+    let mut tools: HashMap<String, u64> = HashMap::new();
+    let mut aliases: HashMap<String, u64> = HashMap::new();
+    let mut hours = [0u64; 24];
+    let mut weekdays = [0u64; 7];
+    let mut weeks: Vec<WeekCount> = Vec::new();
+    let mut day_counts = Vec::new();
+    let mut total_commands = 0u64;
+    let mut active_days = 0;
+    let mut alias_runs = 0u64;
+    let mut full_runs_with_alias = 0u64;
+    let mut cumulative = 0u64;
+    let mut keystrokes_saved = 0u64;
+
+    let empty = DayUsage::default();
+    let mut date = from;
+    while date <= today {
+        let key = date.format("%Y-%m-%d").to_string();
+        let usage = history.days.get(&key).unwrap_or(&empty);
+
+        total_commands += usage.commands as u64;
+        if usage.commands > 0 {
+            active_days += 1;
+        }
+        for (tool, count) in &usage.tools {
+            *tools.entry(tool.clone()).or_insert(0) += *count as u64;
+        }
+        for (alias, count) in &usage.aliases {
+            *aliases.entry(alias.clone()).or_insert(0) += *count as u64;
+        }
+        for (hour, count) in usage.hours.iter().enumerate() {
+            hours[hour] += *count as u64;
+        }
+        weekdays[date.weekday().num_days_from_monday() as usize] += usage.commands as u64;
+        alias_runs += usage.alias_runs as u64;
+        full_runs_with_alias += usage.full_runs_with_alias as u64;
+        keystrokes_saved += usage.keystrokes_saved;
+
+        let week = format!("{}-W{:02}", date.iso_week().year(), date.iso_week().week());
+        cumulative += usage.new_commands as u64;
+        match weeks.last_mut() {
+            Some(last) if last.week == week => {
+                last.new_commands += usage.new_commands as u64;
+                last.cumulative = cumulative;
+            }
+            _ => weeks.push(WeekCount { week, new_commands: usage.new_commands as u64, cumulative }),
+        }
+
+        day_counts.push(DayCount {
+            day: key,
+            commands: usage.commands,
+            new_commands: usage.new_commands,
+            keystrokes_saved: usage.keystrokes_saved,
+        });
+        date = date.succ_opt().unwrap_or(today + Duration::days(1));
+    }
+
+    let mut top_tools: Vec<ToolCount> = tools.into_iter().map(|(tool, count)| ToolCount { tool, count }).collect();
+    top_tools.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tool.cmp(&b.tool)));
+    top_tools.truncate(TOP_TOOLS);
+
+    let mut top_aliases: Vec<AliasCount> = aliases.into_iter().map(|(alias, count)| AliasCount { alias, count }).collect();
+    top_aliases.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.alias.cmp(&b.alias)));
+    top_aliases.truncate(TOP_ALIASES);
+
+    let aliasable = alias_runs + full_runs_with_alias;
+    StatsReport {
+        from: from.format("%Y-%m-%d").to_string(),
+        to: today.format("%Y-%m-%d").to_string(),
+        total_commands,
+        active_days,
+        average_per_active_day: if active_days > 0 { total_commands as f64 / active_days as f64 } else { 0.0 },
+        top_tools,
+        days: day_counts,
+        hours,
+        weekdays,
+        new_commands_per_week: weeks,
+        alias_runs,
+        full_runs_with_alias,
+        alias_usage_ratio: if aliasable > 0 { alias_runs as f64 / aliasable as f64 } else { 0.0 },
+        keystrokes_saved,
+        keystrokes_saved_all_time: history.days.values().map(|d| d.keystrokes_saved).sum(),
+        top_aliases,
+    }
+}
+
+// The last day each alias was run on, over the whole history
+pub fn alias_last_used(history: &UsageHistory) -> HashMap<String, String> {
+    let mut last_used = HashMap::new();
+    // Days are sorted, later ones overwrite
+    for (day, usage) in &history.days {
+        for alias in usage.aliases.keys() {
+            last_used.insert(alias.clone(), day.clone());
+        }
+    }
+    last_used
+}
+
+fn bar(value: u64, max: u64, width: usize) -> String {
+    if max == 0 {
+        return String::new();
+    }
+    let filled = ((value as f64 / max as f64) * width as f64).round() as usize;
+    "█".repeat(filled.max(if value > 0 { 1 } else { 0 }))
+}
+
+pub fn print_report(report: &StatsReport) {
+    println!(
+        "{}",
+        format!("Usage from {} to {}: {} command(s) on {} active day(s)", report.from, report.to, report.total_commands, report.active_days)
+            .cyan()
+            .bold()
+    );
+    if report.total_commands == 0 {
+        println!("{}", "Nothing recorded in this period yet.".yellow());
+        return;
+    }
+    println!("Average per active day: {:.1}", report.average_per_active_day);
+
+    println!("\n{}", "Top tools".cyan());
+    let max_tool = report.top_tools.first().map(|t| t.count).unwrap_or(0);
+    let tool_width = report.top_tools.iter().map(|t| t.tool.chars().count()).max().unwrap_or(0);
+    for tool in &report.top_tools {
+        println!("  {:<width$} {:>6} {}", tool.tool, tool.count, bar(tool.count, max_tool, 30).green(), width = tool_width);
+    }
+
+    println!("\n{}", "Commands per day".cyan());
+    let max_day = report.days.iter().map(|d| d.commands as u64).max().unwrap_or(0);
+    for day in &report.days {
+        println!("  {} {:>5} {}", day.day, day.commands, bar(day.commands as u64, max_day, 30).green());
+    }
+
+    println!("\n{}", "Time of day".cyan());
+    let max_hour = report.hours.iter().copied().max().unwrap_or(0);
+    for (hour, count) in report.hours.iter().enumerate() {
+        println!("  {:02}:00 {:>6} {}", hour, count, bar(*count, max_hour, 30).green());
+    }
+
+    println!("\n{}", "Weekday".cyan());
+    let max_weekday = report.weekdays.iter().copied().max().unwrap_or(0);
+    for (name, count) in WEEKDAYS.iter().zip(report.weekdays.iter()) {
+        println!("  {} {:>6} {}", name, count, bar(*count, max_weekday, 30).green());
+    }
+
+    println!("\n{}", "New commands per week".cyan());
+    let max_week = report.new_commands_per_week.iter().map(|w| w.new_commands).max().unwrap_or(0);
+    for week in &report.new_commands_per_week {
+        println!(
+            "  {} {:>5} {} (total {})",
+            week.week,
+            week.new_commands,
+            // Padded before colouring, the escape codes would count towards the width otherwise
+            format!("{:<30}", bar(week.new_commands, max_week, 30)).green(),
+            week.cumulative
+        );
+    }
+
+    println!("\n{}", "Alias usage".cyan());
+    println!("  Runs through an alias:            {}", report.alias_runs);
+    println!("  Typed in full despite an alias:   {}", report.full_runs_with_alias);
+    println!("  Alias use rate:                   {:.0}%", report.alias_usage_ratio * 100.0);
+    println!("  Keystrokes saved in this period:  {}", report.keystrokes_saved);
+    println!("  Keystrokes saved so far:          {}", report.keystrokes_saved_all_time);
+
+    if !report.top_aliases.is_empty() {
+        println!("\n{}", "Top aliases".cyan());
+        let max_alias = report.top_aliases.first().map(|a| a.count).unwrap_or(0);
+        let alias_width = report.top_aliases.iter().map(|a| a.alias.chars().count()).max().unwrap_or(0);
+        for alias in &report.top_aliases {
+            println!("  {:<width$} {:>6} {}", alias.alias, alias.count, bar(alias.count, max_alias, 30).green(), width = alias_width);
+        }
+    }
+}
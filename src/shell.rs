@@ -21,7 +21,7 @@ impl ShellOpts {
         
         let alias_file_path = crate::database::persistence::load_config()
             .and_then(|cfg| cfg.alias_file_paths.first().cloned())
-            .unwrap_or_else(|| crate::database::persistence::get_default_alias_file_path());
+            .unwrap_or_else(crate::database::persistence::get_default_alias_file_path);
         
         Self {
             app_path,
@@ -45,7 +45,9 @@ fn render_bash(opts: &ShellOpts) -> String {
     script.push_str("# Alman shell integration for bash\n");
     script.push_str("# Add this to your ~/.bashrc\n\n");
     script.push_str(&format!("export ALMAN_DATA_DIR=\"{}\"\n", opts.data_dir));
-    script.push_str(&format!("export ALMAN_BIN=\"{}\"\n\n", opts.app_path));
+    script.push_str(&format!("export ALMAN_BIN=\"{}\"\n", opts.app_path));
+    script.push_str("# Tells alman which terminal a command ran in, for combo suggestions\n");
+    script.push_str("export ALMAN_SESSION=\"$$\"\n\n");
     
     script.push_str("alman_preexec() {\n");
     script.push_str("    if [ -n \"$1\" ]; then\n");
@@ -64,7 +66,7 @@ fn render_bash(opts: &ShellOpts) -> String {
     script.push_str("            fi\n");
     script.push_str("        done\n");
     script.push_str("    else\n");
-    script.push_str(&format!("        # Fallback to default alias file\n"));
+    script.push_str("        # Fallback to default alias file\n");
     script.push_str(&format!("        if [ -f \"{}\" ]; then\n", opts.alias_file_path));
     script.push_str(&format!("            source \"{}\"\n", opts.alias_file_path));
     script.push_str("        fi\n");
@@ -88,7 +90,9 @@ fn render_zsh(opts: &ShellOpts) -> String {
     script.push_str("# Alman shell integration for zsh\n");
     script.push_str("# Add this to your ~/.zshrc\n\n");
     script.push_str(&format!("export ALMAN_DATA_DIR=\"{}\"\n", opts.data_dir));
-    script.push_str(&format!("export ALMAN_BIN=\"{}\"\n\n", opts.app_path));
+    script.push_str(&format!("export ALMAN_BIN=\"{}\"\n", opts.app_path));
+    script.push_str("# Tells alman which terminal a command ran in, for combo suggestions\n");
+    script.push_str("export ALMAN_SESSION=\"$$\"\n\n");
     
     script.push_str("alman_preexec() {\n");
     script.push_str("    if [ -n \"$1\" ]; then\n");
@@ -107,7 +111,7 @@ fn render_zsh(opts: &ShellOpts) -> String {
     script.push_str("            fi\n");
     script.push_str("        done\n");
     script.push_str("    else\n");
-    script.push_str(&format!("        # Fallback to default alias file\n"));
+    script.push_str("        # Fallback to default alias file\n");
     script.push_str(&format!("        if [ -f \"{}\" ]; then\n", opts.alias_file_path));
     script.push_str(&format!("            source \"{}\"\n", opts.alias_file_path));
     script.push_str("        fi\n");
@@ -128,7 +132,9 @@ fn render_fish(opts: &ShellOpts) -> String {
     script.push_str("# Alman shell integration for fish\n");
     script.push_str("# Add this to your ~/.config/fish/config.fish\n\n");
     script.push_str(&format!("set -gx ALMAN_DATA_DIR \"{}\"\n", opts.data_dir));
-    script.push_str(&format!("set -gx ALMAN_BIN \"{}\"\n\n", opts.app_path));
+    script.push_str(&format!("set -gx ALMAN_BIN \"{}\"\n", opts.app_path));
+    script.push_str("# Tells alman which terminal a command ran in, for combo suggestions\n");
+    script.push_str("set -gx ALMAN_SESSION $fish_pid\n\n");
     
     script.push_str("function alman_preexec --on-event fish_preexec\n");
     script.push_str("    if test -n \"$argv[1]\"\n");
@@ -146,7 +152,7 @@ fn render_fish(opts: &ShellOpts) -> String {
     script.push_str("            end\n");
     script.push_str("        end\n");
     script.push_str("    else\n");
-    script.push_str(&format!("        # Fallback to default alias file\n"));
+    script.push_str("        # Fallback to default alias file\n");
     script.push_str(&format!("        if test -f \"{}\"\n", opts.alias_file_path));
     script.push_str(&format!("            source \"{}\"\n", opts.alias_file_path));
     script.push_str("        end\n");
@@ -164,7 +170,9 @@ fn render_posix(opts: &ShellOpts) -> String {
     script.push_str("# Alman shell integration for POSIX shells (ksh, dash, etc.)\n");
     script.push_str("# Add this to your ~/.profile or ~/.kshrc\n\n");
     script.push_str(&format!("export ALMAN_DATA_DIR=\"{}\"\n", opts.data_dir));
-    script.push_str(&format!("export ALMAN_BIN=\"{}\"\n\n", opts.app_path));
+    script.push_str(&format!("export ALMAN_BIN=\"{}\"\n", opts.app_path));
+    script.push_str("# Tells alman which terminal a command ran in, for combo suggestions\n");
+    script.push_str("export ALMAN_SESSION=\"$$\"\n\n");
     
     script.push_str("alman_preexec() {\n");
     script.push_str("    if [ -n \"$1\" ]; then\n");
@@ -183,16 +191,25 @@ fn render_posix(opts: &ShellOpts) -> String {
     script.push_str("            fi\n");
     script.push_str("        done\n");
     script.push_str("    else\n");
-    script.push_str(&format!("        # Fallback to default alias file\n"));
+    script.push_str("        # Fallback to default alias file\n");
     script.push_str(&format!("        if [ -f \"{}\" ]; then\n", opts.alias_file_path));
     script.push_str(&format!("            . \"{}\"\n", opts.alias_file_path));
     script.push_str("        fi\n");
     script.push_str("    fi\n");
     script.push_str("}\n\n");
     
-    script.push_str("# Note: POSIX shells don't have built-in preexec hooks\n");
-    script.push_str("# You may need to manually call alman_preexec in your PS1\n");
-    script.push_str("# Example: PS1='$(alman_preexec $?) $ '\n\n");
+    script.push_str("# Note: POSIX shells don't have built-in preexec hooks, so alman tails $HISTFILE instead.\n");
+    script.push_str("# Only new entries are read, and history rotation or truncation is detected.\n");
+    script.push_str("alman_sync_history() {\n");
+    script.push_str("    if [ -n \"$HISTFILE\" ] && [ -f \"$HISTFILE\" ]; then\n");
+    script.push_str(&format!("        {} sync-history \"$HISTFILE\" >/dev/null 2>&1\n", opts.app_path));
+    script.push_str("    fi\n");
+    script.push_str("}\n\n");
+    script.push_str("# To sync after every command, call it from your prompt, e.g. PS1='$(alman_sync_history)$ '\n");
+    script.push_str("# or run `alman sync-history --track` once so every alman invocation syncs it.\n\n");
+
+    script.push_str("# Sync history on shell startup\n");
+    script.push_str("alman_sync_history\n\n");
     
     script.push_str("# Source aliases on shell startup\n");
     script.push_str("alman_source_aliases\n");
@@ -1,29 +1,46 @@
-use crate::database::database_structs::{Command, Database};
+use crate::cli::cli_data::Operation;
+use crate::database::database_structs::{BlockKind, Command};
 use crate::ops::alias_suggestions::AliasSuggestion;
+use crate::ops::explain::Explanation;
+use crate::ops::ignored::IgnoredEntry;
+use crate::tui::batch::{BatchAction, BatchChange};
+use crate::tui::dashboard::Dashboard;
+use crate::tui::line_editor::LineEditor;
+use crate::tui::tabs::files::AliasFile;
+use crate::tui::tabs::settings::Setting;
+use crate::tui::tabs::{Tab, TabList};
 use ratatui::widgets::ListState;
+use std::collections::HashSet;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
 pub enum AppMode {
-    Main,
+    Main, // the tabs
     AddAliasStep1,
     AddAliasStep2,
     AddAliasConfirmation,
-    RemoveAliasStep1,
     RemoveAliasConfirmation,
-    ChangeAliasStep1,
     ChangeAliasStep2,
-    ListAliases,
+    EditAliasCommand,
+    BatchActions,
+    BatchConfirmation,
+    Dashboard,
 }
 
 #[derive(Debug)]
 pub struct App {
     pub mode: AppMode,
+    pub tab: Tab,
     pub input: String,
     pub cursor_position: usize,
-    pub list_state: ListState,
+    pub commands_tab: TabList,
+    pub aliases_tab: TabList,
+    pub files_tab: TabList,
+    pub ignored_tab: TabList,
+    pub settings_tab: TabList,
     pub commands: Vec<Command>,
     pub filtered_commands: Vec<Command>,
+    pub filtered_command_matches: Vec<Vec<usize>>, // matched chars of each filtered command, for highlighting
     pub alias_file_path: PathBuf,
     pub alias_file_paths: Vec<String>, // All tracked alias file paths
     pub should_quit: bool,
@@ -34,6 +51,7 @@ pub struct App {
     pub alias_input: String,
     pub alias_cursor_position: usize,
     pub alias_suggestions: Vec<AliasSuggestion>,
+    pub selected_command_daily_runs: f64, // for the savings shown next to alias suggestions
     pub alias_suggestions_state: ListState,
     pub confirmation_alias: Option<String>,
     pub confirmation_command: Option<String>,
@@ -47,32 +65,53 @@ pub struct App {
     pub change_new_alias_cursor_position: usize,
     pub change_alias_suggestions: Vec<AliasSuggestion>,
     pub change_alias_suggestions_state: ListState,
+    pub command_editor: LineEditor, // the alias's command being edited
     pub aliases: Vec<(String, String)>, // (alias, command) pairs
-    pub filtered_aliases: Vec<(String, String)>, // filtered aliases for remove
-    pub list_aliases_state: ListState, // for list aliases navigation
+    pub filtered_aliases: Vec<(String, String)>, // what the Aliases tab shows
+    pub filtered_alias_matches: Vec<(Vec<usize>, Vec<usize>)>, // matched chars in (alias, command)
+    pub alias_files: Vec<AliasFile>, // tracked alias files with what each one defines
+    pub filtered_files: Vec<(usize, Vec<usize>)>, // (index into alias_files, matched chars in its path)
     pub selected_command_details: Option<Command>, // for command details popup
-    pub command_details_selection: usize, // 0=Add Alias, 1=Delete Suggestion, 2=Back
+    pub command_details_selection: usize, // 0=Add Alias, 1=Delete Suggestion, 2=Snooze, 3=Back
     pub show_command_details_popup: bool,
+    pub command_explanation: Option<Explanation>, // score breakdown shown in the details popup
     pub config_changed: bool,
+    pub reload_commands: bool, // set when a setting changes the scores, the terminal loop reloads
+    pub ignored_entries: Vec<IgnoredEntry>, // deleted, snoozed and blocked, for the Ignored tab
+    pub filtered_ignored: Vec<(usize, Vec<usize>)>, // (index into ignored_entries, matched chars)
+    pub settings: Vec<Setting>,
+    pub filtered_settings: Vec<(usize, Vec<usize>)>, // (index into settings, matched chars in its name)
+    pub block_rule_kind: Option<BlockKind>, // set while a block rule is being typed into `input`
+    pub marked_commands: HashSet<String>, // command texts marked with Space in the Commands tab
+    pub marked_aliases: HashSet<String>, // alias names marked with Space in the Aliases tab
+    pub batch_actions: Vec<BatchAction>, // what can be done with the marked rows
+    pub batch_actions_state: ListState,
+    pub batch_changes: Vec<BatchChange>, // shown on the confirmation screen
+    pub batch_confirmation_selection: bool, // true for OK, false for Undo
+    pub pending_operations: Vec<Operation>, // a confirmed batch, run by the terminal loop
+    pub dashboard: Option<Dashboard>, // loaded by the handler when the dashboard opens
+    pub dashboard_idle_state: ListState, // for scrolling the idle aliases
 }
 
 impl App {
     pub fn new(alias_file_path: PathBuf, alias_file_paths: Vec<String>) -> Self {
-        let mut list_state = ListState::default();
-        list_state.select(Some(0));
         let mut alias_suggestions_state = ListState::default();
         alias_suggestions_state.select(Some(0));
         let mut change_alias_suggestions_state = ListState::default();
         change_alias_suggestions_state.select(Some(0));
-        let mut list_aliases_state = ListState::default();
-        list_aliases_state.select(Some(0));
         App {
             mode: AppMode::Main,
+            tab: Tab::Commands,
             input: String::new(),
             cursor_position: 0,
-            list_state,
+            commands_tab: TabList::default(),
+            aliases_tab: TabList::default(),
+            files_tab: TabList::default(),
+            ignored_tab: TabList::default(),
+            settings_tab: TabList::default(),
             commands: Vec::new(),
             filtered_commands: Vec::new(),
+            filtered_command_matches: Vec::new(),
             alias_file_path,
             alias_file_paths,
             should_quit: false,
@@ -83,6 +122,7 @@ impl App {
             alias_input: String::new(),
             alias_cursor_position: 0,
             alias_suggestions: Vec::new(),
+            selected_command_daily_runs: 0.0,
             alias_suggestions_state,
             confirmation_alias: None,
             confirmation_command: None,
@@ -96,45 +136,35 @@ impl App {
             change_new_alias_cursor_position: 0,
             change_alias_suggestions: Vec::new(),
             change_alias_suggestions_state,
+            command_editor: LineEditor::default(),
             aliases: Vec::new(),
             filtered_aliases: Vec::new(),
-            list_aliases_state,
+            filtered_alias_matches: Vec::new(),
+            alias_files: Vec::new(),
+            filtered_files: Vec::new(),
             selected_command_details: None,
             command_details_selection: 0,
             show_command_details_popup: false,
+            command_explanation: None,
             config_changed: false,
+            reload_commands: false,
+            ignored_entries: Vec::new(),
+            filtered_ignored: Vec::new(),
+            settings: Vec::new(),
+            filtered_settings: Vec::new(),
+            block_rule_kind: None,
+            marked_commands: HashSet::new(),
+            marked_aliases: HashSet::new(),
+            batch_actions: Vec::new(),
+            batch_actions_state: ListState::default(),
+            batch_changes: Vec::new(),
+            batch_confirmation_selection: true,
+            pending_operations: Vec::new(),
+            dashboard: None,
+            dashboard_idle_state: ListState::default(),
         }
     }
 
-    pub fn load_commands(&mut self, database: &mut Database) {
-        self.commands = database
-            .get_top_commands(Some(20))
-            .iter()
-            .map(|cmd| (*cmd).clone())
-            .collect();
-        self.filtered_commands = self.commands.clone();
-        // Reset list selection when commands are reloaded
-        self.list_state.select(None);
-    }
-
-    pub fn filter_commands(&mut self) {
-        if self.input.is_empty() {
-            self.filtered_commands = self.commands.clone();
-        } else {
-            self.filtered_commands = self
-                .commands
-                .iter()
-                .filter(|cmd| {
-                    cmd.command_text
-                        .to_lowercase()
-                        .contains(&self.input.to_lowercase())
-                })
-                .cloned()
-                .collect();
-        }
-        self.list_state.select(None);
-    }
-
     pub fn show_popup(&mut self, message: String) {
         self.popup_message = message;
         self.show_popup = true;
@@ -145,14 +175,6 @@ impl App {
         self.popup_message.clear();
     }
 
-    pub fn get_selected_command(&self) -> Option<&Command> {
-        if let Some(selected) = self.list_state.selected() {
-            self.filtered_commands.get(selected)
-        } else {
-            None
-        }
-    }
-
     pub fn clear_input(&mut self) {
         self.input.clear();
         self.cursor_position = 0;
@@ -173,11 +195,11 @@ impl App {
         self.change_new_alias_cursor_position = 0;
         self.change_alias_suggestions.clear();
         self.change_alias_suggestions_state.select(None);
-        self.aliases.clear();
-        self.filtered_aliases.clear();
-        self.list_aliases_state.select(None);
+        self.command_editor = LineEditor::default();
         self.selected_command_details = None;
         self.command_details_selection = 0;
+        self.command_explanation = None;
+        self.block_rule_kind = None;
     }
 
     pub fn set_mode(&mut self, mode: AppMode) {
@@ -193,26 +215,26 @@ impl App {
                 // Don't clear confirmation fields
                 self.mode = mode;
             }
-            AppMode::RemoveAliasStep1 => {
-                self.load_aliases();
-                self.mode = mode;
-            }
             AppMode::RemoveAliasConfirmation => {
                 // Don't clear remove confirmation fields
                 self.mode = mode;
             }
-            AppMode::ChangeAliasStep1 => {
+            AppMode::ChangeAliasStep2 | AppMode::EditAliasCommand => {
                 self.mode = mode;
             }
-            AppMode::ChangeAliasStep2 => {
+            AppMode::BatchActions | AppMode::BatchConfirmation => {
+                // Keep the marks
                 self.mode = mode;
             }
             AppMode::Main => {
-                // Force clear when returning to main menu
+                // Add's first step filters the commands with what is typed there, put the tab's filter back
+                let refilter = matches!(self.mode, AppMode::AddAliasStep1);
+                // Force clear when returning to the tabs
                 self.mode = mode;
                 self.clear_input();
-                // Reset list state to ensure clean display
-                self.list_state.select(None);
+                if refilter {
+                    self.filter_commands();
+                }
             }
             _ => {
                 self.mode = mode;
@@ -226,39 +248,13 @@ impl App {
             use crate::ops::alias_suggestions::AliasSuggester;
             let suggester = AliasSuggester::new(&self.alias_file_path.to_string_lossy());
             self.alias_suggestions = suggester.suggest_aliases(command);
-        }
-    }
-
-    pub fn load_aliases(&mut self) {
-        use crate::ops::alias_ops::get_aliases_from_multiple_files;
-        self.aliases = get_aliases_from_multiple_files(&self.alias_file_paths);
-        self.filtered_aliases = self.aliases.clone();
-        // Reset list selection when aliases are reloaded
-        self.list_state.select(None);
-    }
-
-    pub fn filter_aliases(&mut self) {
-        if self.input.is_empty() {
-            self.filtered_aliases = self.aliases.clone();
-        } else {
-            let filter = self.input.to_lowercase();
-            self.filtered_aliases = self
-                .aliases
+            let now = crate::database::scoring::now();
+            self.selected_command_daily_runs = self
+                .commands
                 .iter()
-                .filter(|(alias, command)| {
-                    alias.to_lowercase().contains(&filter)
-                        || command.to_lowercase().contains(&filter)
-                })
-                .cloned()
-                .collect();
-        }
-    }
-
-    pub fn get_selected_alias(&self) -> Option<&(String, String)> {
-        if let Some(selected) = self.list_state.selected() {
-            self.filtered_aliases.get(selected)
-        } else {
-            None
+                .find(|c| &c.command_text == command)
+                .map(|c| crate::ops::keystroke_savings::daily_runs(c, now))
+                .unwrap_or(0.0);
         }
     }
 
@@ -273,17 +269,18 @@ impl App {
         }
     }
 
-    pub fn load_aliases_for_listing(&mut self) {
-        use crate::ops::alias_ops::get_aliases_from_multiple_files;
-        self.aliases = get_aliases_from_multiple_files(&self.alias_file_paths);
-        self.list_aliases_state.select(None);
+    // Opens the command editor for the alias picked in the change flow
+    pub fn start_command_edit(&mut self) {
+        use crate::ops::change_alias::edit_history;
+        let command = self.change_old_command.clone().unwrap_or_default();
+        self.command_editor = LineEditor::new(&command, edit_history(&self.commands, &command));
+        self.set_mode(AppMode::EditAliasCommand);
+        self.status_message = "Edit the command (Enter to continue, ↑/↓ for recorded commands, Esc to cancel):".to_string();
     }
 
     pub fn format_last_access_time(&self, timestamp: i64) -> String {
         use chrono::{DateTime, TimeZone, Utc};
-        let dt: DateTime<Utc> = Utc.timestamp_opt(timestamp, 0).single().unwrap_or_else(|| Utc::now());
+        let dt: DateTime<Utc> = Utc.timestamp_opt(timestamp, 0).single().unwrap_or_else(Utc::now);
         dt.format("%m/%d/%Y %H:%M:%S").to_string()
     }
-
-
 }
@@ -0,0 +1,176 @@
+// Marking several rows (Space) and applying one action to all of them, with every change listed
+// on a single confirmation screen first
+use crate::cli::cli_data::Operation;
+use crate::ops::alias_ops::{get_aliases_from_multiple_files, get_aliases_with_files};
+use crate::ops::alias_suggestions::AliasSuggester;
+use crate::ops::normalize_command::alias_target;
+use crate::tui::app::{App, AppMode};
+use crate::tui::tabs::Tab;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BatchAction {
+    AddTopSuggestions,
+    DeleteSuggestions,
+    RemoveAliases,
+    MoveToFile(String),
+}
+
+impl BatchAction {
+    pub fn label(&self) -> String {
+        match self {
+            BatchAction::AddTopSuggestions => "Add the top suggested alias for each".to_string(),
+            BatchAction::DeleteSuggestions => "Delete from suggestions".to_string(),
+            BatchAction::RemoveAliases => "Remove aliases".to_string(),
+            BatchAction::MoveToFile(file) => format!("Move to {}", file),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum BatchChange {
+    Add { alias: String, command: String },
+    Remove { alias: String, command: String },
+    DeleteSuggestion { command: String },
+    Move { alias: String, from: String, to: String },
+    // Marked, but nothing will happen to it
+    Skip { text: String, reason: String },
+}
+
+impl BatchChange {
+    pub fn operation(&self) -> Option<Operation> {
+        match self {
+            BatchChange::Add { alias, command } => Some(Operation::Add { alias: alias.clone(), command: command.clone() }),
+            BatchChange::Remove { alias, .. } => Some(Operation::Remove { alias: alias.clone() }),
+            BatchChange::DeleteSuggestion { command } => Some(Operation::DeleteSuggestion { alias: command.clone() }),
+            BatchChange::Move { alias, to, .. } => Some(Operation::Move { to: PathBuf::from(to), aliases: vec![alias.clone()] }),
+            BatchChange::Skip { .. } => None,
+        }
+    }
+}
+
+impl App {
+    pub fn toggle_marked_command(&mut self) {
+        if let Some(cmd) = self.get_selected_command() {
+            let text = cmd.command_text.clone();
+            if !self.marked_commands.remove(&text) {
+                self.marked_commands.insert(text);
+            }
+            self.status_message = format!("{} marked (Enter for batch actions, Space to mark more)", self.marked_commands.len());
+        }
+    }
+
+    pub fn toggle_marked_alias(&mut self, selected: Option<usize>) {
+        if let Some((alias, _)) = selected.and_then(|i| self.filtered_aliases.get(i)) {
+            let alias = alias.clone();
+            if !self.marked_aliases.remove(&alias) {
+                self.marked_aliases.insert(alias);
+            }
+            self.status_message = format!("{} marked (Enter for batch actions, Space to mark more)", self.marked_aliases.len());
+        }
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.marked_commands.clear();
+        self.marked_aliases.clear();
+    }
+
+    // Opens the action menu for whatever is marked on the current tab
+    pub fn open_batch_actions(&mut self) {
+        self.batch_actions = if self.tab == Tab::Commands {
+            vec![BatchAction::AddTopSuggestions, BatchAction::DeleteSuggestions]
+        } else {
+            let mut actions = vec![BatchAction::RemoveAliases];
+            if self.alias_file_paths.len() > 1 {
+                actions.extend(self.alias_file_paths.iter().map(|file| BatchAction::MoveToFile(file.clone())));
+            }
+            actions
+        };
+        self.batch_actions_state.select(Some(0));
+        let count = self.marked_commands.len() + self.marked_aliases.len();
+        self.status_message = format!("Choose what to do with the {} marked rows (Enter to review, Esc to go back):", count);
+        self.set_mode(AppMode::BatchActions);
+    }
+
+    pub fn plan_batch(&mut self, action: &BatchAction) {
+        let mut changes = Vec::new();
+        match action {
+            BatchAction::AddTopSuggestions => {
+                let suggester = AliasSuggester::new(&self.alias_file_path.to_string_lossy());
+                // Two marked commands can have the same top suggestion, the second gets its next best
+                let mut taken: HashSet<String> = get_aliases_from_multiple_files(&self.alias_file_paths)
+                    .into_iter()
+                    .map(|(alias, _)| alias)
+                    .collect();
+                for command in self.marked_in_command_order() {
+                    let target = alias_target(&command);
+                    match suggester.suggest_aliases(&target).into_iter().find(|s| !taken.contains(&s.alias)) {
+                        Some(suggestion) => {
+                            taken.insert(suggestion.alias.clone());
+                            changes.push(BatchChange::Add { alias: suggestion.alias, command: suggestion.command });
+                        }
+                        None => changes.push(BatchChange::Skip { text: target, reason: "no free alias name".to_string() }),
+                    }
+                }
+            }
+            BatchAction::DeleteSuggestions => {
+                changes.extend(self.marked_in_command_order().into_iter().map(|command| BatchChange::DeleteSuggestion { command }));
+            }
+            BatchAction::RemoveAliases => {
+                for (alias, command) in self.marked_in_alias_order() {
+                    changes.push(BatchChange::Remove { alias, command });
+                }
+            }
+            BatchAction::MoveToFile(to) => {
+                let with_files = get_aliases_with_files(&self.alias_file_paths);
+                for (alias, _) in self.marked_in_alias_order() {
+                    match with_files.iter().find(|(a, _, _)| *a == alias) {
+                        Some((_, _, from)) if from == to => {
+                            changes.push(BatchChange::Skip { text: alias, reason: "already in that file".to_string() });
+                        }
+                        Some((_, _, from)) => changes.push(BatchChange::Move { alias, from: from.clone(), to: to.clone() }),
+                        None => changes.push(BatchChange::Skip { text: alias, reason: "not found".to_string() }),
+                    }
+                }
+            }
+        }
+        self.batch_changes = changes;
+        self.batch_confirmation_selection = true;
+        let applied = self.batch_changes.iter().filter(|c| c.operation().is_some()).count();
+        self.status_message = format!("Apply {} change(s)? (OK/Undo)", applied);
+        self.set_mode(AppMode::BatchConfirmation);
+    }
+
+    // Queued for the terminal loop to run one after another
+    pub fn apply_batch(&mut self) {
+        self.pending_operations = self.batch_changes.iter().filter_map(BatchChange::operation).collect();
+        self.batch_changes.clear();
+        self.clear_marks();
+        self.set_mode(AppMode::Main);
+    }
+
+    pub fn cancel_batch(&mut self) {
+        self.batch_changes.clear();
+        self.clear_marks();
+        self.set_mode(AppMode::Main);
+        self.status_message = "Batch cancelled.".to_string();
+    }
+
+    fn marked_in_command_order(&self) -> Vec<String> {
+        self.commands
+            .iter()
+            .map(|cmd| cmd.command_text.clone())
+            .filter(|text| self.marked_commands.contains(text))
+            .collect()
+    }
+
+    fn marked_in_alias_order(&self) -> Vec<(String, String)> {
+        let mut seen = HashSet::new();
+        self.aliases
+            .iter()
+            .filter(|(alias, _)| self.marked_aliases.contains(alias) && seen.insert(alias.clone()))
+            .cloned()
+            .collect()
+    }
+}
@@ -0,0 +1,76 @@
+// The statistics dashboard (`s` in the main menu): the `alman stats` report plus what only
+// makes sense next to the suggestions, how their scores are spread and which aliases are idle
+use crate::database::database_structs::{Database, DeletedCommands};
+use crate::database::persistence::{get_usage_history_path, load_usage_history};
+use crate::ops::alias_ops::get_aliases_from_multiple_files;
+use crate::ops::usage_stats::{alias_last_used, build_report, StatsReport};
+use crate::tui::app::{App, AppMode};
+use chrono::{Duration, Local};
+use std::collections::HashSet;
+
+pub const DASHBOARD_DAYS: usize = 30;
+const SCORE_BUCKETS: usize = 10;
+
+#[derive(Debug)]
+pub struct Dashboard {
+    pub report: StatsReport,
+    // (lower bound, number of suggestions), lowest scores first
+    pub score_histogram: Vec<(i32, u64)>,
+    pub idle_aliases: Vec<IdleAlias>,
+}
+
+#[derive(Debug)]
+pub struct IdleAlias {
+    pub alias: String,
+    pub command: String,
+    pub last_used: Option<String>, // YYYY-MM-DD, None if no run was ever recorded
+}
+
+impl App {
+    pub fn load_dashboard(&mut self, database: &Database, deleted_commands: &DeletedCommands, days: usize) {
+        let history = match load_usage_history(&get_usage_history_path()) {
+            Ok(history) => history,
+            Err(e) => {
+                self.set_mode(AppMode::Main);
+                self.status_message = format!("Failed to load usage history: {}", e);
+                return;
+            }
+        };
+        let report = build_report(&history, days);
+
+        let scorer = crate::database::scoring::configured_scorer();
+        let scores: Vec<i32> = database
+            .get_top_commands(Some(usize::MAX), scorer.as_ref(), deleted_commands)
+            .iter()
+            .map(|cmd| cmd.score)
+            .collect();
+
+        // Not run since the report's first day; the oldest first, never run before those
+        let since = (Local::now().date_naive() - Duration::days(days.max(1) as i64 - 1)).format("%Y-%m-%d").to_string();
+        let last_used = alias_last_used(&history);
+        let mut seen = HashSet::new();
+        let mut idle_aliases: Vec<IdleAlias> = get_aliases_from_multiple_files(&self.alias_file_paths)
+            .into_iter()
+            .rev() // the last definition of a name is the one in use
+            .filter(|(alias, _)| seen.insert(alias.clone()))
+            .map(|(alias, command)| IdleAlias { last_used: last_used.get(&alias).cloned(), alias, command })
+            .filter(|idle| idle.last_used.as_ref().is_none_or(|day| *day < since))
+            .collect();
+        idle_aliases.sort_by(|a, b| a.last_used.cmp(&b.last_used).then_with(|| a.alias.cmp(&b.alias)));
+
+        self.dashboard = Some(Dashboard { report, score_histogram: score_histogram(&scores), idle_aliases });
+        self.dashboard_idle_state.select(None);
+    }
+}
+
+// Equal width buckets from 0 to the highest score
+fn score_histogram(scores: &[i32]) -> Vec<(i32, u64)> {
+    let max = scores.iter().copied().max().unwrap_or(0).max(0);
+    let width = (max / SCORE_BUCKETS as i32 + 1).max(1);
+    let mut buckets: Vec<(i32, u64)> = (0..SCORE_BUCKETS as i32).map(|i| (i * width, 0)).collect();
+    for score in scores {
+        let i = (score.max(&0) / width) as usize;
+        buckets[i.min(SCORE_BUCKETS - 1)].1 += 1;
+    }
+    buckets
+}
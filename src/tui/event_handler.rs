@@ -1,9 +1,12 @@
-use crate::cli::cli_data::Operation;
+use crate::cli::cli_data::{IgnoredAction, Operation};
+use crate::ops::normalize_command::alias_target;
 use crate::tui::app::{App, AppMode};
-use ratatui::crossterm::event::KeyCode;
+use crate::tui::line_editor::EditOutcome;
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
 
 impl App {
-    pub fn handle_key_event(&mut self, key: KeyCode) -> Option<Operation> {
+    pub fn handle_key_event(&mut self, event: KeyEvent) -> Option<Operation> {
+        let key = event.code;
 
         if self.show_popup {
             match key {
@@ -24,7 +27,7 @@ impl App {
                     return None;
                 }
                 KeyCode::Right => {
-                    if self.command_details_selection < 2 {
+                    if self.command_details_selection < 3 {
                         self.command_details_selection += 1;
                     }
                     return None;
@@ -33,8 +36,9 @@ impl App {
                     match self.command_details_selection {
                         0 => { // Add Alias
                             if let Some(cmd) = &self.selected_command_details {
-                                self.selected_command = Some(cmd.command_text.clone());
-                                self.input = cmd.command_text.clone();
+                                // templates are aliased by their stable prefix
+                                self.selected_command = Some(alias_target(&cmd.command_text));
+                                self.input = alias_target(&cmd.command_text);
                                 self.cursor_position = self.input.len();
                                 self.set_mode(AppMode::AddAliasStep2);
                                 self.generate_alias_suggestions();
@@ -48,7 +52,17 @@ impl App {
                             }
                             self.show_command_details_popup = false;
                         }
-                        2 => { // Back
+                        2 => { // Snooze
+                            if let Some(cmd) = self.selected_command_details.take() {
+                                self.show_command_details_popup = false;
+                                self.command_details_selection = 0;
+                                return Some(Operation::Ignored {
+                                    action: IgnoredAction::Snooze { duration: "7d".to_string(), command: vec![cmd.command_text] },
+                                });
+                            }
+                            self.show_command_details_popup = false;
+                        }
+                        3 => { // Back
                             self.show_command_details_popup = false;
                             self.selected_command_details = None;
                             self.command_details_selection = 0;
@@ -70,122 +84,16 @@ impl App {
         }
 
         match self.mode {
-            AppMode::Main => self.handle_main_mode(key),
+            AppMode::Main => self.handle_tabs(key),
             AppMode::AddAliasStep1 => self.handle_add_alias_step1(key),
             AppMode::AddAliasStep2 => self.handle_add_alias_step2(key),
             AppMode::AddAliasConfirmation => self.handle_add_alias_confirmation(key),
-            AppMode::RemoveAliasStep1 => self.handle_remove_alias_step1(key),
             AppMode::RemoveAliasConfirmation => self.handle_remove_alias_confirmation(key),
-            AppMode::ChangeAliasStep1 => self.handle_change_alias_step1(key),
             AppMode::ChangeAliasStep2 => self.handle_change_alias_step2(key),
-            AppMode::ListAliases => self.handle_list_aliases(key),
-
-        }
-    }
-
-    fn handle_main_mode(&mut self, key: KeyCode) -> Option<Operation> {
-        match key {
-            KeyCode::Char('q') => {
-                self.should_quit = true;
-                None
-            }
-            KeyCode::Char('a') => {
-                self.set_mode(AppMode::AddAliasStep1);
-                self.status_message = "Select a command to create an alias for (use ↑↓ to navigate, Enter to select):".to_string();
-                None
-            }
-            KeyCode::Char('r') => {
-                self.set_mode(AppMode::RemoveAliasStep1);
-                self.status_message = "Select an alias to remove (type to filter, ↑↓ to navigate):".to_string();
-                None
-            }
-            KeyCode::Char('c') => {
-                self.set_mode(AppMode::ChangeAliasStep1);
-                self.status_message = "Select an alias to change (type to filter, ↑↓ to navigate):".to_string();
-                self.load_aliases();
-                self.filter_aliases();
-                None
-            }
-            KeyCode::Char('l') => {
-                self.set_mode(AppMode::ListAliases);
-                self.status_message = "Listing aliases (use ↑↓ to navigate, Esc to return):".to_string();
-                self.load_aliases_for_listing();
-                None
-            }
-            KeyCode::Up => {
-                if !self.filtered_commands.is_empty() {
-                    let selected = match self.list_state.selected() {
-                        Some(i) => {
-                            if i > 0 {
-                                i - 1
-                            } else {
-                                i
-                            }
-                        }
-                        None => 0,
-                    };
-                    self.list_state.select(Some(selected));
-                }
-                None
-            }
-            KeyCode::Down => {
-                if !self.filtered_commands.is_empty() {
-                    let selected = match self.list_state.selected() {
-                        Some(i) => {
-                            if i < self.filtered_commands.len() - 1 {
-                                i + 1
-                            } else {
-                                i
-                            }
-                        }
-                        None => 0,
-                    };
-                    self.list_state.select(Some(selected));
-                }
-                None
-            }
-            KeyCode::Enter => {
-                if let Some(cmd) = self.get_selected_command() {
-                    self.selected_command_details = Some(cmd.clone());
-                    self.command_details_selection = 0;
-                    self.show_command_details_popup = true;
-                }
-                None
-            }
-            KeyCode::Char('/') => {
-                self.clear_input();
-                self.status_message = "Type to filter commands... (Esc to clear)".to_string();
-                None
-            }
-            KeyCode::Char(c) => {
-                self.input.push(c);
-                self.cursor_position = self.input.len();
-                self.filter_commands();
-                self.status_message = format!("Filtering: '{}' (Esc to clear)", self.input);
-                None
-            }
-            KeyCode::Backspace => {
-                if !self.input.is_empty() {
-                    self.input.pop();
-                    self.cursor_position = self.input.len();
-                    self.filter_commands();
-                    if self.input.is_empty() {
-                        self.status_message = "Filter cleared".to_string();
-                    } else {
-                        self.status_message = format!("Filtering: '{}'", self.input);
-                    }
-                }
-                None
-            }
-            KeyCode::Esc => {
-                if !self.input.is_empty() {
-                    self.clear_input();
-                    self.filter_commands();
-                    self.status_message = "Filter cleared".to_string();
-                }
-                None
-            }
-            _ => None,
+            AppMode::EditAliasCommand => self.handle_edit_alias_command(event),
+            AppMode::BatchActions => self.handle_batch_actions(key),
+            AppMode::BatchConfirmation => self.handle_batch_confirmation(key),
+            AppMode::Dashboard => self.handle_dashboard(key),
         }
     }
 
@@ -193,7 +101,7 @@ impl App {
         match key {
             KeyCode::Enter => {
                 let command_text = if let Some(cmd) = self.get_selected_command() {
-                    cmd.command_text.clone()
+                    alias_target(&cmd.command_text)
                 } else if !self.input.trim().is_empty() {
                     self.input.trim().to_string()
                 } else {
@@ -212,14 +120,14 @@ impl App {
             KeyCode::Char(c) => {
                 self.input.insert(self.cursor_position, c);
                 self.cursor_position += 1;
-                self.filter_commands();
+                self.filter_commands_by(&self.input.clone());
                 None
             }
             KeyCode::Backspace => {
                 if self.cursor_position > 0 {
                     self.input.remove(self.cursor_position - 1);
                     self.cursor_position -= 1;
-                    self.filter_commands();
+                    self.filter_commands_by(&self.input.clone());
                 }
                 None
             }
@@ -235,45 +143,16 @@ impl App {
                 }
                 None
             }
-            KeyCode::Up => {
-                if !self.filtered_commands.is_empty() {
-                    let selected = match self.list_state.selected() {
-                        Some(i) => {
-                            if i > 0 {
-                                i - 1
-                            } else {
-                                i
-                            }
-                        }
-                        None => 0,
-                    };
-                    self.list_state.select(Some(selected));
-                    // Autofill the input with the selected command
-                    if let Some(cmd) = self.get_selected_command() {
-                        self.input = cmd.command_text.clone();
-                        self.cursor_position = self.input.len();
-                    }
+            KeyCode::Up | KeyCode::Down => {
+                if key == KeyCode::Up {
+                    self.commands_tab.select_previous(self.filtered_commands.len());
+                } else {
+                    self.select_next_command();
                 }
-                None
-            }
-            KeyCode::Down => {
-                if !self.filtered_commands.is_empty() {
-                    let selected = match self.list_state.selected() {
-                        Some(i) => {
-                            if i < self.filtered_commands.len() - 1 {
-                                i + 1
-                            } else {
-                                i
-                            }
-                        }
-                        None => 0,
-                    };
-                    self.list_state.select(Some(selected));
-                    // Autofill the input with the selected command
-                    if let Some(cmd) = self.get_selected_command() {
-                        self.input = cmd.command_text.clone();
-                        self.cursor_position = self.input.len();
-                    }
+                // Autofill the input with the selected command
+                if let Some(cmd) = self.get_selected_command() {
+                    self.input = cmd.command_text.clone();
+                    self.cursor_position = self.input.len();
                 }
                 None
             }
@@ -375,7 +254,7 @@ impl App {
                 None
             }
             KeyCode::Tab => {
-                if let Some(suggestion) = self.alias_suggestions.get(0) {
+                if let Some(suggestion) = self.alias_suggestions.first() {
                     self.alias_input = suggestion.alias.clone();
                     self.alias_cursor_position = self.alias_input.len();
                 }
@@ -412,10 +291,12 @@ impl App {
                             self.status_message = format!("Changed alias: {} = {} → {} = {}", 
                                 old_alias_str, command_str, new_alias_str, command_str);
                             self.set_mode(AppMode::Main);
-                            Some(Operation::Change { 
-                                old_alias: old_alias_str, 
-                                new_alias: new_alias_str,
-                                command: command_str
+                            Some(Operation::Change {
+                                old_alias: old_alias_str,
+                                new_alias: Some(new_alias_str),
+                                new_command: None,
+                                command: Some(command_str),
+                                edit: false,
                             })
                         } else {
                             // This is an add operation
@@ -465,101 +346,6 @@ impl App {
         }
     }
 
-    fn handle_remove_alias_step1(&mut self, key: KeyCode) -> Option<Operation> {
-        match key {
-            KeyCode::Enter => {
-                let (alias_text, command_text) = if let Some((alias, command)) = self.get_selected_alias() {
-                    (alias.clone(), command.clone())
-                } else if !self.input.trim().is_empty() {
-                    (self.input.trim().to_string(), String::new())
-                } else {
-                    self.status_message = "Please select an alias or type one".to_string();
-                    return None;
-                };
-                self.remove_confirmation_alias = Some(alias_text);
-                self.remove_confirmation_command = Some(command_text);
-                self.remove_confirmation_selection = true; // OK selected by default
-                self.status_message = format!("Confirm removing alias: {} (OK/Undo)", self.remove_confirmation_alias.as_deref().unwrap_or(""));
-                self.set_mode(AppMode::RemoveAliasConfirmation);
-                None
-            }
-            KeyCode::Char(c) => {
-                self.input.insert(self.cursor_position, c);
-                self.cursor_position += 1;
-                self.filter_aliases();
-                None
-            }
-            KeyCode::Backspace => {
-                if self.cursor_position > 0 {
-                    self.input.remove(self.cursor_position - 1);
-                    self.cursor_position -= 1;
-                    self.filter_aliases();
-                }
-                None
-            }
-            KeyCode::Left => {
-                if self.cursor_position > 0 {
-                    self.cursor_position -= 1;
-                }
-                None
-            }
-            KeyCode::Right => {
-                if self.cursor_position < self.input.len() {
-                    self.cursor_position += 1;
-                }
-                None
-            }
-            KeyCode::Up => {
-                if !self.filtered_aliases.is_empty() {
-                    let selected = match self.list_state.selected() {
-                        Some(i) => {
-                            if i > 0 {
-                                i - 1
-                            } else {
-                                i
-                            }
-                        }
-                        None => 0,
-                    };
-                    self.list_state.select(Some(selected));
-                    // Autofill the input with the selected alias
-                    if let Some((alias, _)) = self.get_selected_alias() {
-                        self.input = alias.clone();
-                        self.cursor_position = self.input.len();
-                    }
-                }
-                None
-            }
-            KeyCode::Down => {
-                if !self.filtered_aliases.is_empty() {
-                    let selected = match self.list_state.selected() {
-                        Some(i) => {
-                            if i < self.filtered_aliases.len() - 1 {
-                                i + 1
-                            } else {
-                                i
-                            }
-                        }
-                        None => 0,
-                    };
-                    self.list_state.select(Some(selected));
-                    // Autofill the input with the selected alias
-                    if let Some((alias, _)) = self.get_selected_alias() {
-                        self.input = alias.clone();
-                        self.cursor_position = self.input.len();
-                    }
-                }
-                None
-            }
-            KeyCode::Esc => {
-                self.set_mode(AppMode::Main);
-                self.status_message = "Remove alias cancelled.".to_string();
-                None
-            }
-            _ => None,
-        }
-    }
-
     fn handle_remove_alias_confirmation(&mut self, key: KeyCode) -> Option<Operation> {
         match key {
             KeyCode::Left => {
@@ -603,105 +389,6 @@ impl App {
         }
     }
 
-    fn handle_change_alias_step1(&mut self, key: KeyCode) -> Option<Operation> {
-        match key {
-            KeyCode::Enter => {
-                if let Some((alias, command)) = self.get_selected_alias() {
-                    let alias_clone = alias.clone();
-                    let command_clone = command.clone();
-                    self.change_old_alias = Some(alias_clone);
-                    self.change_old_command = Some(command_clone);
-                    self.change_new_alias.clear();
-                    self.change_new_alias_cursor_position = 0;
-                    self.set_mode(AppMode::ChangeAliasStep2);
-                    self.generate_change_alias_suggestions();
-                    self.status_message = "Enter new alias name:".to_string();
-                } else if !self.input.trim().is_empty() {
-                    // Try to find alias by name
-                    let search_alias = self.input.trim();
-                    if let Some((alias, command)) = self.aliases.iter().find(|(a, _)| a == search_alias) {
-                        self.change_old_alias = Some(alias.clone());
-                        self.change_old_command = Some(command.clone());
-                        self.change_new_alias.clear();
-                        self.change_new_alias_cursor_position = 0;
-                        self.set_mode(AppMode::ChangeAliasStep2);
-                        self.generate_change_alias_suggestions();
-                        self.status_message = "Enter new alias name:".to_string();
-                    } else {
-                        self.status_message = "Alias not found".to_string();
-                    }
-                } else {
-                    self.status_message = "Please select an alias or type one".to_string();
-                }
-                None
-            }
-            KeyCode::Char(c) => {
-                self.input.insert(self.cursor_position, c);
-                self.cursor_position += 1;
-                self.filter_aliases();
-                None
-            }
-            KeyCode::Backspace => {
-                if self.cursor_position > 0 {
-                    self.input.remove(self.cursor_position - 1);
-                    self.cursor_position -= 1;
-                    self.filter_aliases();
-                }
-                None
-            }
-            KeyCode::Left => {
-                if self.cursor_position > 0 {
-                    self.cursor_position -= 1;
-                }
-                None
-            }
-            KeyCode::Right => {
-                if self.cursor_position < self.input.len() {
-                    self.cursor_position += 1;
-                }
-                None
-            }
-            KeyCode::Up => {
-                if !self.filtered_aliases.is_empty() {
-                    let selected = match self.list_state.selected() {
-                        Some(i) => {
-                            if i > 0 {
-                                i - 1
-                            } else {
-                                i
-                            }
-                        }
-                        None => 0,
-                    };
-                    self.list_state.select(Some(selected));
-                }
-                None
-            }
-            KeyCode::Down => {
-                if !self.filtered_aliases.is_empty() {
-                    let selected = match self.list_state.selected() {
-                        Some(i) => {
-                            if i < self.filtered_aliases.len() - 1 {
-                                i + 1
-                            } else {
-                                i
-                            }
-                        }
-                        None => 0,
-                    };
-                    self.list_state.select(Some(selected));
-                }
-                None
-            }
-            KeyCode::Esc => {
-                self.set_mode(AppMode::Main);
-                self.status_message = "Change alias cancelled.".to_string();
-                None
-            }
-            _ => None,
-        }
-    }
-
     fn handle_change_alias_step2(&mut self, key: KeyCode) -> Option<Operation> {
         match key {
             KeyCode::Enter => {
@@ -711,14 +398,10 @@ impl App {
                     return None;
                 }
                 
-                if let (Some(old_alias), Some(old_command)) = (&self.change_old_alias, &self.change_old_command) {
-                    // Store the old and new alias for confirmation
-                    self.confirmation_alias = Some(new_alias.clone());
-                    self.confirmation_command = Some(old_command.clone());
-                    self.confirmation_selection = true; // Default to OK selected
-                    self.status_message = format!("Confirm changing alias: {} = {} → {} = {} (OK/Undo)", 
-                        old_alias, old_command, new_alias, old_command);
-                    self.set_mode(AppMode::AddAliasConfirmation);
+                if self.change_old_alias.is_some() {
+                    // On to the command, the confirmation comes after it
+                    self.change_new_alias = new_alias;
+                    self.start_command_edit();
                     None
                 } else {
                     self.status_message = "No alias selected for change".to_string();
@@ -792,7 +475,7 @@ impl App {
                 None
             }
             KeyCode::Tab => {
-                if let Some(suggestion) = self.change_alias_suggestions.get(0) {
+                if let Some(suggestion) = self.change_alias_suggestions.first() {
                     self.change_new_alias = suggestion.alias.clone();
                     self.change_new_alias_cursor_position = self.change_new_alias.len();
                 }
@@ -807,52 +490,119 @@ impl App {
         }
     }
 
-    fn handle_list_aliases(&mut self, key: KeyCode) -> Option<Operation> {
+    fn handle_edit_alias_command(&mut self, event: KeyEvent) -> Option<Operation> {
+        use crate::ops::change_alias::check_command;
+        match self.command_editor.handle(event) {
+            EditOutcome::Editing => None,
+            EditOutcome::Cancel => {
+                self.set_mode(AppMode::Main);
+                self.status_message = "Change alias cancelled.".to_string();
+                None
+            }
+            EditOutcome::Submit => {
+                let (Some(old_alias), Some(old_command)) = (self.change_old_alias.clone(), self.change_old_command.clone()) else {
+                    self.status_message = "No alias selected for change".to_string();
+                    return None;
+                };
+                let command = self.command_editor.text.trim().to_string();
+                let new_alias = self.change_new_alias.trim().to_string();
+                if command == old_command && new_alias == old_alias {
+                    self.status_message = "Nothing changed (Esc to cancel)".to_string();
+                    return None;
+                }
+                if command != old_command {
+                    if let Err(e) = check_command(&command) {
+                        self.status_message = format!("Can't use this command: {}", e);
+                        return None;
+                    }
+                }
+                self.confirmation_alias = Some(new_alias.clone());
+                self.confirmation_command = Some(command.clone());
+                self.confirmation_selection = true; // Default to OK selected
+                self.status_message = format!("Confirm changing alias: {} = {} → {} = {} (OK/Undo)",
+                    old_alias, old_command, new_alias, command);
+                self.set_mode(AppMode::AddAliasConfirmation);
+                None
+            }
+        }
+    }
+
+    fn handle_dashboard(&mut self, key: KeyCode) -> Option<Operation> {
+        let idle = self.dashboard.as_ref().map(|d| d.idle_aliases.len()).unwrap_or(0);
         match key {
             KeyCode::Up => {
-                if !self.aliases.is_empty() {
-                    let selected = match self.list_aliases_state.selected() {
-                        Some(i) => {
-                            if i > 0 {
-                                i - 1
-                            } else {
-                                i
-                            }
-                        }
-                        None => 0,
-                    };
-                    self.list_aliases_state.select(Some(selected));
-                }
+                let selected = self.dashboard_idle_state.selected().map(|i| i.saturating_sub(1));
+                self.dashboard_idle_state.select(selected.or(if idle > 0 { Some(0) } else { None }));
                 None
             }
             KeyCode::Down => {
-                if !self.aliases.is_empty() {
-                    let selected = match self.list_aliases_state.selected() {
-                        Some(i) => {
-                            if i < self.aliases.len() - 1 {
-                                i + 1
-                            } else {
-                                i
-                            }
-                        }
-                        None => 0,
-                    };
-                    self.list_aliases_state.select(Some(selected));
+                if idle > 0 {
+                    let next = self.dashboard_idle_state.selected().map(|i| (i + 1).min(idle - 1)).unwrap_or(0);
+                    self.dashboard_idle_state.select(Some(next));
                 }
                 None
             }
-            KeyCode::Enter => {
-                // Show details of selected alias
-                if let Some(selected) = self.list_aliases_state.selected() {
-                    if let Some((alias, command)) = self.aliases.get(selected) {
-                        self.show_popup(format!("Alias: {} = {}", alias, command));
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.dashboard = None;
+                self.set_mode(AppMode::Main);
+                self.status_message = "Returned to main menu.".to_string();
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn handle_batch_actions(&mut self, key: KeyCode) -> Option<Operation> {
+        match key {
+            KeyCode::Up => {
+                if let Some(i) = self.batch_actions_state.selected() {
+                    self.batch_actions_state.select(Some(i.saturating_sub(1)));
+                }
+                None
+            }
+            KeyCode::Down => {
+                if let Some(i) = self.batch_actions_state.selected() {
+                    if i + 1 < self.batch_actions.len() {
+                        self.batch_actions_state.select(Some(i + 1));
                     }
                 }
                 None
             }
+            KeyCode::Enter => {
+                let action = self.batch_actions_state.selected().and_then(|i| self.batch_actions.get(i)).cloned()?;
+                self.plan_batch(&action);
+                None
+            }
             KeyCode::Esc => {
+                // Back to the list with the marks still in place
                 self.set_mode(AppMode::Main);
-                self.status_message = "Returned to main menu.".to_string();
+                self.status_message = "Space to mark or unmark, Enter for batch actions".to_string();
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn handle_batch_confirmation(&mut self, key: KeyCode) -> Option<Operation> {
+        match key {
+            KeyCode::Left => {
+                self.batch_confirmation_selection = true; // OK
+                None
+            }
+            KeyCode::Right => {
+                self.batch_confirmation_selection = false; // Undo
+                None
+            }
+            KeyCode::Enter => {
+                if self.batch_confirmation_selection {
+                    self.apply_batch();
+                } else {
+                    self.cancel_batch();
+                }
+                None
+            }
+            KeyCode::Esc => {
+                self.cancel_batch();
                 None
             }
             _ => None,
@@ -0,0 +1,160 @@
+// A one-line editor with readline-ish keys, for editing an alias's command in the TUI and in
+// `alman change`. Words are runs of non-whitespace.
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditOutcome {
+    Editing,
+    Submit,
+    Cancel,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LineEditor {
+    pub text: String,
+    pub cursor: usize, // byte offset into text
+    // Older entries first, Up walks backwards from the end
+    history: Vec<String>,
+    history_index: Option<usize>,
+    // What was typed before walking into the history
+    draft: String,
+}
+
+impl LineEditor {
+    pub fn new(text: &str, history: Vec<String>) -> Self {
+        Self { text: text.to_string(), cursor: text.len(), history, history_index: None, draft: String::new() }
+    }
+
+    // Chars before the cursor, where the terminal cursor goes
+    pub fn cursor_column(&self) -> usize {
+        self.text[..self.cursor].chars().count()
+    }
+
+    pub fn handle(&mut self, key: KeyEvent) -> EditOutcome {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        match key.code {
+            KeyCode::Enter => return EditOutcome::Submit,
+            KeyCode::Esc => return EditOutcome::Cancel,
+            KeyCode::Char('c') if ctrl => return EditOutcome::Cancel,
+            KeyCode::Char('a') if ctrl => self.cursor = 0,
+            KeyCode::Char('e') if ctrl => self.cursor = self.text.len(),
+            KeyCode::Char('b') if ctrl => self.cursor = self.prev_char(),
+            KeyCode::Char('f') if ctrl => self.cursor = self.next_char(),
+            KeyCode::Char('b') if alt => self.cursor = self.word_start(),
+            KeyCode::Char('f') if alt => self.cursor = self.word_end(),
+            KeyCode::Char('w') if ctrl => self.delete_to(self.word_start()),
+            KeyCode::Char('d') if alt => self.delete_to(self.word_end()),
+            KeyCode::Char('u') if ctrl => self.delete_to(0),
+            KeyCode::Char('k') if ctrl => self.delete_to(self.text.len()),
+            KeyCode::Char(_) if ctrl || alt => {}
+            KeyCode::Char(c) => {
+                self.text.insert(self.cursor, c);
+                self.cursor += c.len_utf8();
+            }
+            KeyCode::Backspace if alt || ctrl => self.delete_to(self.word_start()),
+            KeyCode::Backspace => self.delete_to(self.prev_char()),
+            KeyCode::Delete => self.delete_to(self.next_char()),
+            KeyCode::Left if ctrl || alt => self.cursor = self.word_start(),
+            KeyCode::Right if ctrl || alt => self.cursor = self.word_end(),
+            KeyCode::Left => self.cursor = self.prev_char(),
+            KeyCode::Right => self.cursor = self.next_char(),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.text.len(),
+            KeyCode::Up => self.history_back(),
+            KeyCode::Down => self.history_forward(),
+            _ => {}
+        }
+        EditOutcome::Editing
+    }
+
+    fn prev_char(&self) -> usize {
+        self.text[..self.cursor].chars().next_back().map(|c| self.cursor - c.len_utf8()).unwrap_or(0)
+    }
+
+    fn next_char(&self) -> usize {
+        self.text[self.cursor..].chars().next().map(|c| self.cursor + c.len_utf8()).unwrap_or(self.cursor)
+    }
+
+    // Start of the word before the cursor, skipping whitespace right before it
+    fn word_start(&self) -> usize {
+        let before = self.text[..self.cursor].trim_end();
+        before.rfind(char::is_whitespace).map(|i| i + 1).unwrap_or(0)
+    }
+
+    // End of the word after the cursor, skipping whitespace right after it
+    fn word_end(&self) -> usize {
+        let after = &self.text[self.cursor..];
+        let skipped = after.len() - after.trim_start().len();
+        let word = &after[skipped..];
+        self.cursor + skipped + word.find(char::is_whitespace).unwrap_or(word.len())
+    }
+
+    // Deletes between the cursor and `to`, either side of it
+    fn delete_to(&mut self, to: usize) {
+        let (start, end) = if to < self.cursor { (to, self.cursor) } else { (self.cursor, to) };
+        self.text.replace_range(start..end, "");
+        self.cursor = start;
+    }
+
+    fn history_back(&mut self) {
+        let index = match self.history_index {
+            Some(0) => return,
+            Some(i) => i - 1,
+            None if self.history.is_empty() => return,
+            None => {
+                self.draft = self.text.clone();
+                self.history.len() - 1
+            }
+        };
+        self.history_index = Some(index);
+        self.text = self.history[index].clone();
+        self.cursor = self.text.len();
+    }
+
+    fn history_forward(&mut self) {
+        let Some(i) = self.history_index else { return };
+        if i + 1 < self.history.len() {
+            self.history_index = Some(i + 1);
+            self.text = self.history[i + 1].clone();
+        } else {
+            self.history_index = None;
+            self.text = std::mem::take(&mut self.draft);
+        }
+        self.cursor = self.text.len();
+    }
+}
+
+// Runs the editor on the terminal outside the TUI, for `alman change`. None if cancelled.
+pub fn read_line(prompt: &str, editor: &mut LineEditor) -> std::io::Result<Option<String>> {
+    use ratatui::crossterm::{
+        cursor::MoveToColumn,
+        event::{self, Event, KeyEventKind},
+        execute,
+        terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
+    };
+    use std::io::Write;
+
+    let mut stdout = std::io::stdout();
+    enable_raw_mode()?;
+    let result = loop {
+        let drawn = execute!(stdout, MoveToColumn(0), Clear(ClearType::CurrentLine))
+            .and_then(|_| write!(stdout, "{}{}", prompt, editor.text))
+            .and_then(|_| execute!(stdout, MoveToColumn((prompt.chars().count() + editor.cursor_column()) as u16)));
+        if let Err(e) = drawn {
+            break Err(e);
+        }
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => match editor.handle(key) {
+                EditOutcome::Editing => {}
+                EditOutcome::Submit => break Ok(Some(editor.text.clone())),
+                EditOutcome::Cancel => break Ok(None),
+            },
+            Ok(_) => {}
+            Err(e) => break Err(e),
+        }
+    };
+    disable_raw_mode()?;
+    println!();
+    result
+}
@@ -1,5 +1,9 @@
 pub mod app;
+pub mod batch;
+pub mod dashboard;
 pub mod event_handler;
+pub mod line_editor;
+pub mod tabs;
 pub mod terminal;
 pub mod ui;
 
@@ -0,0 +1,119 @@
+// The Aliases tab: every alias in the tracked files, where they get removed, renamed and edited
+use crate::cli::cli_data::Operation;
+use crate::ops::fuzzy::fuzzy_match_terms;
+use crate::tui::app::{App, AppMode};
+use ratatui::crossterm::event::KeyCode;
+
+impl App {
+    pub fn load_aliases(&mut self) {
+        use crate::ops::alias_ops::get_aliases_from_multiple_files;
+        self.aliases = get_aliases_from_multiple_files(&self.alias_file_paths);
+        let aliases = &self.aliases;
+        self.marked_aliases.retain(|marked| aliases.iter().any(|(alias, _)| alias == marked));
+        self.filter_aliases();
+    }
+
+    pub fn filter_aliases(&mut self) {
+        let mut matched: Vec<_> = self
+            .aliases
+            .iter()
+            .filter_map(|pair| {
+                let (score, positions) = fuzzy_match_terms(&self.aliases_tab.filter, &[&pair.0, &pair.1])?;
+                Some((score, pair, positions))
+            })
+            .collect();
+        matched.sort_by_key(|(score, _, _)| std::cmp::Reverse(*score));
+        self.filtered_alias_matches = matched
+            .iter()
+            .map(|(_, _, positions)| (positions[0].clone(), positions[1].clone()))
+            .collect();
+        self.filtered_aliases = matched.into_iter().map(|(_, pair, _)| pair.clone()).collect();
+        self.aliases_tab.clamp(self.filtered_aliases.len());
+    }
+
+    pub fn get_selected_alias(&self) -> Option<&(String, String)> {
+        self.aliases_tab.selected().and_then(|i| self.filtered_aliases.get(i))
+    }
+
+    fn selected_alias_or_hint(&mut self) -> Option<(String, String)> {
+        let selected = self.get_selected_alias().cloned();
+        if selected.is_none() {
+            self.status_message = "Select an alias first (↑↓ to navigate)".to_string();
+        }
+        selected
+    }
+
+    pub(super) fn handle_aliases_tab(&mut self, key: KeyCode) -> Option<Operation> {
+        match key {
+            KeyCode::Up => {
+                self.aliases_tab.select_previous(self.filtered_aliases.len());
+                None
+            }
+            KeyCode::Down => {
+                self.aliases_tab.select_next(self.filtered_aliases.len());
+                None
+            }
+            KeyCode::Char(' ') => {
+                self.toggle_marked_alias(self.aliases_tab.selected());
+                self.aliases_tab.select_next(self.filtered_aliases.len());
+                None
+            }
+            KeyCode::Enter if !self.marked_aliases.is_empty() => {
+                self.open_batch_actions();
+                None
+            }
+            KeyCode::Enter => {
+                if let Some((alias, command)) = self.get_selected_alias() {
+                    self.show_popup(format!("Alias: {} = {}", alias, command));
+                }
+                None
+            }
+            KeyCode::Char('r') => {
+                let (alias, command) = self.selected_alias_or_hint()?;
+                self.remove_confirmation_alias = Some(alias.clone());
+                self.remove_confirmation_command = Some(command);
+                self.remove_confirmation_selection = true; // OK selected by default
+                self.status_message = format!("Confirm removing alias: {} (OK/Undo)", alias);
+                self.set_mode(AppMode::RemoveAliasConfirmation);
+                None
+            }
+            KeyCode::Char('c') => {
+                let (alias, command) = self.selected_alias_or_hint()?;
+                self.change_old_alias = Some(alias.clone());
+                self.change_old_command = Some(command);
+                // Enter right away keeps the name
+                self.change_new_alias_cursor_position = alias.len();
+                self.change_new_alias = alias;
+                self.set_mode(AppMode::ChangeAliasStep2);
+                self.generate_change_alias_suggestions();
+                self.status_message = "Enter new alias name (Enter keeps it):".to_string();
+                None
+            }
+            KeyCode::Char('e') => {
+                let (alias, command) = self.selected_alias_or_hint()?;
+                self.change_old_alias = Some(alias.clone());
+                self.change_old_command = Some(command);
+                self.change_new_alias = alias;
+                self.start_command_edit();
+                None
+            }
+            KeyCode::Backspace => {
+                self.edit_kept_filter(key);
+                None
+            }
+            KeyCode::Esc => {
+                // The filter goes first, then the marks
+                if !self.edit_kept_filter(key) && !self.marked_aliases.is_empty() {
+                    self.clear_marks();
+                    self.status_message = "Marks cleared".to_string();
+                }
+                None
+            }
+            KeyCode::Char(c) => {
+                self.type_into_filter(c);
+                None
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,120 @@
+// The Commands tab: the top suggestions from the database, where aliases get added
+use crate::cli::cli_data::{Operation, OutputFormat};
+use crate::database::database_structs::{Command, Database, DeletedCommands};
+use crate::ops::fuzzy::fuzzy_match_terms;
+use crate::ops::normalize_command::alias_target;
+use crate::tui::app::{App, AppMode};
+use ratatui::crossterm::event::KeyCode;
+
+// How much a command's own score can add to its fuzzy match score when ranking the filtered
+// list, enough to break near ties without burying a better match
+const COMMAND_SCORE_WEIGHT: i64 = 10;
+
+impl App {
+    pub fn load_commands(&mut self, database: &mut Database, deleted_commands: &DeletedCommands) {
+        let scorer = crate::database::scoring::configured_scorer();
+        self.commands = database.get_top_commands(Some(20), scorer.as_ref(), deleted_commands);
+        let commands = &self.commands;
+        self.marked_commands.retain(|text| commands.iter().any(|cmd| &cmd.command_text == text));
+        self.filter_commands();
+    }
+
+    pub fn filter_commands(&mut self) {
+        let query = self.commands_tab.filter.clone();
+        self.filter_commands_by(&query);
+    }
+
+    // Add's first step filters with what is typed there instead of the tab's filter
+    pub fn filter_commands_by(&mut self, query: &str) {
+        let max_score = self.commands.iter().map(|cmd| cmd.score).max().unwrap_or(0).max(1) as i64;
+        let mut matched: Vec<(i64, &Command, Vec<usize>)> = self
+            .commands
+            .iter()
+            .filter_map(|cmd| {
+                let (score, mut positions) = fuzzy_match_terms(query, &[&cmd.command_text])?;
+                let rank = score as i64 + cmd.score.max(0) as i64 * COMMAND_SCORE_WEIGHT / max_score;
+                Some((rank, cmd, positions.remove(0)))
+            })
+            .collect();
+        // Stable, so an empty filter keeps the score order
+        matched.sort_by_key(|(rank, _, _)| std::cmp::Reverse(*rank));
+        self.filtered_command_matches = matched.iter().map(|(_, _, positions)| positions.clone()).collect();
+        self.filtered_commands = matched.into_iter().map(|(_, cmd, _)| cmd.clone()).collect();
+        self.commands_tab.state.select(None);
+    }
+
+    pub fn get_selected_command(&self) -> Option<&Command> {
+        self.commands_tab.selected().and_then(|i| self.filtered_commands.get(i))
+    }
+
+    pub fn select_next_command(&mut self) {
+        self.commands_tab.select_next(self.filtered_commands.len());
+    }
+
+    pub(super) fn handle_commands_tab(&mut self, key: KeyCode) -> Option<Operation> {
+        match key {
+            KeyCode::Up => {
+                self.commands_tab.select_previous(self.filtered_commands.len());
+                None
+            }
+            KeyCode::Down => {
+                self.select_next_command();
+                None
+            }
+            KeyCode::Char(' ') => {
+                self.toggle_marked_command();
+                self.select_next_command();
+                None
+            }
+            KeyCode::Char('a') => {
+                match self.get_selected_command() {
+                    Some(cmd) => {
+                        // templates are aliased by their stable prefix
+                        let command_text = alias_target(&cmd.command_text);
+                        self.selected_command = Some(command_text);
+                        self.set_mode(AppMode::AddAliasStep2);
+                        self.generate_alias_suggestions();
+                        self.status_message = "Enter alias name for the selected command:".to_string();
+                    }
+                    None => {
+                        self.set_mode(AppMode::AddAliasStep1);
+                        self.status_message = "Select a command to create an alias for, or type one (↑↓ to navigate, Enter to select):".to_string();
+                    }
+                }
+                None
+            }
+            KeyCode::Enter => {
+                if !self.marked_commands.is_empty() {
+                    self.open_batch_actions();
+                    return None;
+                }
+                if let Some(cmd) = self.get_selected_command() {
+                    let command_text = cmd.command_text.clone();
+                    self.selected_command_details = Some(cmd.clone());
+                    self.command_details_selection = 0;
+                    self.show_command_details_popup = true;
+                    self.command_explanation = None;
+                    return Some(Operation::Explain { command: vec![command_text], format: OutputFormat::Table });
+                }
+                None
+            }
+            KeyCode::Backspace => {
+                self.edit_kept_filter(key);
+                None
+            }
+            KeyCode::Esc => {
+                // The filter goes first, then the marks
+                if !self.edit_kept_filter(key) && !self.marked_commands.is_empty() {
+                    self.clear_marks();
+                    self.status_message = "Marks cleared".to_string();
+                }
+                None
+            }
+            KeyCode::Char(c) => {
+                self.type_into_filter(c);
+                None
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,108 @@
+// The Files tab: the tracked alias files, what each one defines and which one new aliases go to
+use crate::cli::cli_data::Operation;
+use crate::database::persistence::{load_config, save_config};
+use crate::ops::alias_ops::{get_alias_definitions, AliasDefinition};
+use crate::ops::fuzzy::fuzzy_match_terms;
+use crate::tui::app::App;
+use ratatui::crossterm::event::KeyCode;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub struct AliasFile {
+    pub path: String,
+    pub exists: bool,
+    // In file order, each with whether a later definition of the name wins over it
+    pub definitions: Vec<(AliasDefinition, bool)>,
+}
+
+impl App {
+    pub fn load_files(&mut self) {
+        let definitions = get_alias_definitions(&self.alias_file_paths);
+        self.alias_files = self
+            .alias_file_paths
+            .iter()
+            .map(|path| AliasFile {
+                path: path.clone(),
+                exists: std::path::Path::new(path).exists(),
+                definitions: definitions
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, d)| &d.file == path)
+                    .map(|(i, d)| (d.clone(), definitions[i + 1..].iter().any(|later| later.alias == d.alias)))
+                    .collect(),
+            })
+            .collect();
+        self.filter_files();
+    }
+
+    pub fn filter_files(&mut self) {
+        self.filtered_files = self
+            .alias_files
+            .iter()
+            .enumerate()
+            .filter_map(|(i, file)| {
+                let (_, mut positions) = fuzzy_match_terms(&self.files_tab.filter, &[&file.path])?;
+                Some((i, positions.remove(0)))
+            })
+            .collect();
+        self.files_tab.clamp(self.filtered_files.len());
+        if self.files_tab.selected().is_none() && !self.filtered_files.is_empty() {
+            self.files_tab.state.select(Some(0));
+        }
+    }
+
+    pub fn get_selected_file(&self) -> Option<&AliasFile> {
+        let (i, _) = self.files_tab.selected().and_then(|i| self.filtered_files.get(i))?;
+        self.alias_files.get(*i)
+    }
+
+    pub(super) fn handle_files_tab(&mut self, key: KeyCode) -> Option<Operation> {
+        match key {
+            KeyCode::Up => {
+                self.files_tab.select_previous(self.filtered_files.len());
+                None
+            }
+            KeyCode::Down => {
+                self.files_tab.select_next(self.filtered_files.len());
+                None
+            }
+            KeyCode::Char('d') => {
+                let path = self.get_selected_file()?.path.clone();
+                self.make_default_file(&path);
+                None
+            }
+            KeyCode::Backspace | KeyCode::Esc => {
+                self.edit_kept_filter(key);
+                None
+            }
+            KeyCode::Char(c) => {
+                self.type_into_filter(c);
+                None
+            }
+            _ => None,
+        }
+    }
+
+    // New aliases go to the first tracked file, like starting `alman -a <file>` does
+    fn make_default_file(&mut self, path: &str) {
+        let Some(pos) = self.alias_file_paths.iter().position(|p| p == path) else { return };
+        if pos == 0 {
+            self.status_message = format!("{} is already the default", path);
+            return;
+        }
+        let file = self.alias_file_paths.remove(pos);
+        self.alias_file_paths.insert(0, file);
+        self.alias_file_path = PathBuf::from(path);
+
+        let mut config = load_config().unwrap_or_default();
+        config.alias_file_paths = self.alias_file_paths.clone();
+        self.status_message = match save_config(&config) {
+            Ok(()) => format!("New aliases now go to {}", path),
+            Err(e) => format!("Failed to save config: {}", e),
+        };
+        self.config_changed = true;
+        self.load_files();
+        let moved = self.filtered_files.iter().position(|(i, _)| *i == 0);
+        self.files_tab.state.select(moved);
+    }
+}
@@ -0,0 +1,112 @@
+// The Ignored tab: deleted, snoozed and blocked suggestions, and the block rules themselves
+use crate::cli::cli_data::{IgnoredAction, Operation};
+use crate::database::database_structs::{BlockKind, DeletedCommands};
+use crate::ops::fuzzy::fuzzy_match_terms;
+use crate::tui::app::App;
+use crate::tui::tabs::Tab;
+use ratatui::crossterm::event::KeyCode;
+
+impl App {
+    pub fn load_ignored(&mut self, deleted_commands: &DeletedCommands) {
+        let now = crate::database::scoring::now();
+        let aliases = crate::ops::alias_ops::get_aliases_from_multiple_files(&self.alias_file_paths);
+        self.ignored_entries = crate::ops::ignored::ignored_entries(deleted_commands, &aliases, now);
+        self.filter_ignored();
+    }
+
+    pub fn filter_ignored(&mut self) {
+        self.filtered_ignored = self
+            .ignored_entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| {
+                let (_, mut positions) = fuzzy_match_terms(&self.ignored_tab.filter, &[&entry.text])?;
+                Some((i, positions.remove(0)))
+            })
+            .collect();
+        self.ignored_tab.clamp(self.filtered_ignored.len());
+        if self.ignored_tab.selected().is_none() && !self.filtered_ignored.is_empty() {
+            self.ignored_tab.state.select(Some(0));
+        }
+    }
+
+    pub(super) fn handle_ignored_tab(&mut self, key: KeyCode) -> Option<Operation> {
+        if let Some(kind) = self.block_rule_kind {
+            return self.handle_block_rule_input(key, kind);
+        }
+        match key {
+            KeyCode::Up => {
+                self.ignored_tab.select_previous(self.filtered_ignored.len());
+                None
+            }
+            KeyCode::Down => {
+                self.ignored_tab.select_next(self.filtered_ignored.len());
+                None
+            }
+            KeyCode::Enter | KeyCode::Char('u') => {
+                let (i, _) = self.ignored_tab.selected().and_then(|i| self.filtered_ignored.get(i))?;
+                let entry = self.ignored_entries.get(*i)?;
+                Some(Operation::Ignored { action: IgnoredAction::Restore { entry: vec![entry.text.clone()] } })
+            }
+            KeyCode::Char('b') => {
+                self.input.clear();
+                self.cursor_position = 0;
+                self.block_rule_kind = Some(BlockKind::Glob);
+                self.status_message = "Type a pattern to block (Tab switches glob/regex, Enter to save, Esc to cancel):".to_string();
+                None
+            }
+            KeyCode::Backspace | KeyCode::Esc => {
+                self.edit_kept_filter(key);
+                None
+            }
+            KeyCode::Char(c) => {
+                self.type_into_filter(c);
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn handle_block_rule_input(&mut self, key: KeyCode, kind: BlockKind) -> Option<Operation> {
+        match key {
+            KeyCode::Char(c) => {
+                self.input.insert(self.cursor_position, c);
+                self.cursor_position += c.len_utf8();
+                None
+            }
+            KeyCode::Backspace => {
+                if let Some(c) = self.input[..self.cursor_position].chars().next_back() {
+                    self.cursor_position -= c.len_utf8();
+                    self.input.remove(self.cursor_position);
+                }
+                None
+            }
+            KeyCode::Tab => {
+                self.block_rule_kind = Some(match kind {
+                    BlockKind::Glob => BlockKind::Regex,
+                    BlockKind::Regex => BlockKind::Glob,
+                });
+                None
+            }
+            KeyCode::Enter => {
+                let pattern = self.input.trim().to_string();
+                if pattern.is_empty() {
+                    self.status_message = "Please type a pattern".to_string();
+                    return None;
+                }
+                self.input.clear();
+                self.cursor_position = 0;
+                self.block_rule_kind = None;
+                Some(Operation::Ignored { action: IgnoredAction::Block { pattern, regex: kind == BlockKind::Regex } })
+            }
+            KeyCode::Esc => {
+                self.input.clear();
+                self.cursor_position = 0;
+                self.block_rule_kind = None;
+                self.status_message = Tab::Ignored.hint().to_string();
+                None
+            }
+            _ => None,
+        }
+    }
+}
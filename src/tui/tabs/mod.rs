@@ -0,0 +1,280 @@
+// The tabs of the main screen. Every tab keeps its own list position and filter, and handles
+// its own keys in its module here; what works on every tab is handled first, in this file.
+pub mod aliases;
+pub mod commands;
+pub mod files;
+pub mod ignored;
+pub mod settings;
+
+use crate::cli::cli_data::Operation;
+use crate::tui::app::{App, AppMode};
+use crate::tui::dashboard::DASHBOARD_DAYS;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::widgets::ListState;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    Commands,
+    Aliases,
+    Files,
+    Ignored,
+    Settings,
+}
+
+impl Tab {
+    pub const ALL: [Tab; 5] = [Tab::Commands, Tab::Aliases, Tab::Files, Tab::Ignored, Tab::Settings];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Tab::Commands => "Commands",
+            Tab::Aliases => "Aliases",
+            Tab::Files => "Files",
+            Tab::Ignored => "Ignored",
+            Tab::Settings => "Settings",
+        }
+    }
+
+    pub fn index(self) -> usize {
+        Self::ALL.iter().position(|tab| *tab == self).unwrap_or(0)
+    }
+
+    fn next(self) -> Tab {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    fn previous(self) -> Tab {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    // Shown in the status bar when the tab is opened
+    fn hint(self) -> &'static str {
+        match self {
+            Tab::Commands => "Suggestions from your history (Enter for details, a to add an alias, Space to mark):",
+            Tab::Aliases => "Your aliases (r to remove, c to change, e to edit the command, Space to mark):",
+            Tab::Files => "Tracked alias files (d to make the selected one the default for new aliases):",
+            Tab::Ignored => "Ignored suggestions (Enter to restore, b to add a block rule):",
+            Tab::Settings => "Settings from ~/.alman/config.json (Enter or ←/→ to change):",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TabList {
+    pub state: ListState,
+    pub filter: String,
+    pub filtering: bool, // keys go to the filter instead of the tab
+}
+
+impl TabList {
+    pub fn selected(&self) -> Option<usize> {
+        self.state.selected()
+    }
+
+    pub fn select_previous(&mut self, len: usize) {
+        if len > 0 {
+            self.state.select(Some(self.state.selected().map(|i| i.saturating_sub(1)).unwrap_or(0)));
+        }
+    }
+
+    pub fn select_next(&mut self, len: usize) {
+        if len > 0 {
+            self.state.select(Some(self.state.selected().map(|i| (i + 1).min(len - 1)).unwrap_or(0)));
+        }
+    }
+
+    // Keeps the selection inside a list that was just rebuilt
+    pub fn clamp(&mut self, len: usize) {
+        let selected = match self.state.selected() {
+            _ if len == 0 => None,
+            Some(i) => Some(i.min(len - 1)),
+            None => None,
+        };
+        self.state.select(selected);
+    }
+}
+
+impl App {
+    pub fn tab_list(&self) -> &TabList {
+        match self.tab {
+            Tab::Commands => &self.commands_tab,
+            Tab::Aliases => &self.aliases_tab,
+            Tab::Files => &self.files_tab,
+            Tab::Ignored => &self.ignored_tab,
+            Tab::Settings => &self.settings_tab,
+        }
+    }
+
+    fn tab_list_mut(&mut self) -> &mut TabList {
+        match self.tab {
+            Tab::Commands => &mut self.commands_tab,
+            Tab::Aliases => &mut self.aliases_tab,
+            Tab::Files => &mut self.files_tab,
+            Tab::Ignored => &mut self.ignored_tab,
+            Tab::Settings => &mut self.settings_tab,
+        }
+    }
+
+    pub fn handle_tabs(&mut self, key: KeyCode) -> Option<Operation> {
+        if self.tab_list().filtering {
+            self.handle_filter_key(key);
+            return None;
+        }
+        // A block rule being typed takes every key
+        if self.tab == Tab::Ignored && self.block_rule_kind.is_some() {
+            return self.handle_ignored_tab(key);
+        }
+        match key {
+            KeyCode::Tab => self.switch_tab(self.tab.next()),
+            KeyCode::BackTab => self.switch_tab(self.tab.previous()),
+            KeyCode::Char(c @ '1'..='5') => self.switch_tab(Tab::ALL[c as usize - '1' as usize]),
+            KeyCode::Char('q') => {
+                self.should_quit = true;
+                None
+            }
+            KeyCode::Char('/') => {
+                self.tab_list_mut().filtering = true;
+                self.status_message = "Type to filter (Enter to keep it, Esc to clear it)".to_string();
+                None
+            }
+            KeyCode::Char('s') => {
+                self.set_mode(AppMode::Dashboard);
+                self.status_message = format!("Statistics for the last {} days (↑↓ to scroll idle aliases, Esc to return):", DASHBOARD_DAYS);
+                // The report is loaded by the handler
+                Some(Operation::Stats { days: DASHBOARD_DAYS, json: false, format: crate::cli::cli_data::OutputFormat::Table })
+            }
+            _ => match self.tab {
+                Tab::Commands => self.handle_commands_tab(key),
+                Tab::Aliases => self.handle_aliases_tab(key),
+                Tab::Files => self.handle_files_tab(key),
+                Tab::Ignored => self.handle_ignored_tab(key),
+                Tab::Settings => self.handle_settings_tab(key),
+            },
+        }
+    }
+
+    // After an alias was added, removed, changed or moved
+    pub fn reload_alias_tabs(&mut self) {
+        match self.tab {
+            Tab::Aliases => self.load_aliases(),
+            Tab::Files => self.load_files(),
+            _ => {}
+        }
+    }
+
+    pub fn switch_tab(&mut self, tab: Tab) -> Option<Operation> {
+        self.tab = tab;
+        self.status_message = tab.hint().to_string();
+        match tab {
+            Tab::Commands => None,
+            Tab::Aliases => {
+                self.load_aliases();
+                None
+            }
+            Tab::Files => {
+                self.load_files();
+                None
+            }
+            // The list itself is loaded by the handler
+            Tab::Ignored => Some(Operation::Ignored {
+                action: crate::cli::cli_data::IgnoredAction::List { format: crate::cli::cli_data::OutputFormat::Table },
+            }),
+            Tab::Settings => {
+                self.load_settings();
+                None
+            }
+        }
+    }
+
+    // A printable key nothing on the tab uses starts the filter, like typing into fzf
+    fn type_into_filter(&mut self, c: char) {
+        let list = self.tab_list_mut();
+        list.filtering = true;
+        list.filter.push(c);
+        self.status_message = format!("Filtering: '{}' (Enter to keep it, Esc to clear it)", list.filter);
+        self.apply_filter();
+    }
+
+    // Backspace and Esc on a tab with a filter kept from earlier. True if there was one.
+    fn edit_kept_filter(&mut self, key: KeyCode) -> bool {
+        let list = self.tab_list_mut();
+        if list.filter.is_empty() {
+            return false;
+        }
+        match key {
+            KeyCode::Backspace => {
+                list.filter.pop();
+            }
+            KeyCode::Esc => list.filter.clear(),
+            _ => return false,
+        }
+        self.status_message = if list.filter.is_empty() {
+            "Filter cleared".to_string()
+        } else {
+            format!("Filtering: '{}' (Esc to clear)", list.filter)
+        };
+        self.apply_filter();
+        true
+    }
+
+    fn handle_filter_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Up | KeyCode::Down => {
+                self.select_in_tab(key == KeyCode::Down);
+                return;
+            }
+            KeyCode::Enter => {
+                let list = self.tab_list_mut();
+                list.filtering = false;
+                let filter = list.filter.clone();
+                self.status_message = format!("Filtering: '{}' (Esc to clear)", filter);
+                return;
+            }
+            _ => {}
+        }
+        let list = self.tab_list_mut();
+        match key {
+            KeyCode::Char(c) => list.filter.push(c),
+            KeyCode::Backspace => {
+                list.filter.pop();
+            }
+            KeyCode::Esc => {
+                list.filter.clear();
+                list.filtering = false;
+            }
+            _ => return,
+        }
+        let filter = list.filter.clone();
+        self.status_message = if filter.is_empty() {
+            "Filter cleared".to_string()
+        } else {
+            format!("Filtering: '{}' (Enter to keep it, Esc to clear it)", filter)
+        };
+        self.apply_filter();
+    }
+
+    fn apply_filter(&mut self) {
+        match self.tab {
+            Tab::Commands => self.filter_commands(),
+            Tab::Aliases => self.filter_aliases(),
+            Tab::Files => self.filter_files(),
+            Tab::Ignored => self.filter_ignored(),
+            Tab::Settings => self.filter_settings(),
+        }
+    }
+
+    fn select_in_tab(&mut self, down: bool) {
+        let len = match self.tab {
+            Tab::Commands => self.filtered_commands.len(),
+            Tab::Aliases => self.filtered_aliases.len(),
+            Tab::Files => self.filtered_files.len(),
+            Tab::Ignored => self.filtered_ignored.len(),
+            Tab::Settings => self.filtered_settings.len(),
+        };
+        let list = self.tab_list_mut();
+        if down {
+            list.select_next(len);
+        } else {
+            list.select_previous(len);
+        }
+    }
+}
@@ -0,0 +1,166 @@
+// The Settings tab: what ~/.alman/config.json holds. The scorer can be changed here, the rest
+// is shown for reference and edited where it lives (the Files tab, the config file).
+use crate::cli::cli_data::Operation;
+use crate::database::persistence::{get_config_path, load_config, save_config, AppConfig};
+use crate::database::scoring::{scorer_for, ScoringMethod};
+use crate::ops::fuzzy::fuzzy_match_terms;
+use crate::tui::app::App;
+use ratatui::crossterm::event::KeyCode;
+
+const HALF_LIFE_STEP_HOURS: f64 = 12.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingKind {
+    ScoringMethod,
+    HalfLife,
+    ReadOnly,
+}
+
+#[derive(Debug, Clone)]
+pub struct Setting {
+    pub name: &'static str,
+    pub value: String,
+    pub about: &'static str,
+    pub kind: SettingKind,
+}
+
+impl App {
+    pub fn load_settings(&mut self) {
+        let config = load_config().unwrap_or_default();
+        let shells = if config.target_shells.is_empty() {
+            format!("$SHELL ({})", std::env::var("SHELL").unwrap_or_default())
+        } else {
+            config.target_shells.join(", ")
+        };
+        let history_files = if config.history_file_paths.is_empty() {
+            "none".to_string()
+        } else {
+            config.history_file_paths.join(", ")
+        };
+        self.settings = vec![
+            Setting {
+                name: "Scoring method",
+                value: scorer_for(&config.scoring).name().to_string(),
+                about: "bucket: frequency and length by four recency buckets | decay: the same, fading smoothly | keystrokes: characters an alias would save",
+                kind: SettingKind::ScoringMethod,
+            },
+            Setting {
+                name: "Half-life",
+                value: format!("{} hours", config.scoring.half_life_hours),
+                about: "How fast old runs stop counting, for the decay and keystrokes methods",
+                kind: SettingKind::HalfLife,
+            },
+            Setting {
+                name: "Default alias file",
+                value: self.alias_file_paths.first().cloned().unwrap_or_default(),
+                about: "Where new aliases are written, picked in the Files tab",
+                kind: SettingKind::ReadOnly,
+            },
+            Setting {
+                name: "Target shells",
+                value: shells,
+                about: "Shells the alias files are sourced by, suggestions never shadow their builtins",
+                kind: SettingKind::ReadOnly,
+            },
+            Setting {
+                name: "History files",
+                value: history_files,
+                about: "Read for new commands every time alman runs, for shells without a hook",
+                kind: SettingKind::ReadOnly,
+            },
+            Setting {
+                name: "Suggestion templates",
+                value: config.suggestion_templates.len().to_string(),
+                about: "Naming patterns of your own, see Suggestion Templates in the README",
+                kind: SettingKind::ReadOnly,
+            },
+            Setting {
+                name: "Config file",
+                value: get_config_path(),
+                about: "Everything that can't be changed here is edited in this file",
+                kind: SettingKind::ReadOnly,
+            },
+        ];
+        self.filter_settings();
+    }
+
+    pub fn filter_settings(&mut self) {
+        self.filtered_settings = self
+            .settings
+            .iter()
+            .enumerate()
+            .filter_map(|(i, setting)| {
+                let (_, mut positions) = fuzzy_match_terms(&self.settings_tab.filter, &[setting.name])?;
+                Some((i, positions.remove(0)))
+            })
+            .collect();
+        self.settings_tab.clamp(self.filtered_settings.len());
+        if self.settings_tab.selected().is_none() && !self.filtered_settings.is_empty() {
+            self.settings_tab.state.select(Some(0));
+        }
+    }
+
+    pub fn get_selected_setting(&self) -> Option<&Setting> {
+        let (i, _) = self.settings_tab.selected().and_then(|i| self.filtered_settings.get(i))?;
+        self.settings.get(*i)
+    }
+
+    pub(super) fn handle_settings_tab(&mut self, key: KeyCode) -> Option<Operation> {
+        match key {
+            KeyCode::Up => {
+                self.settings_tab.select_previous(self.filtered_settings.len());
+                None
+            }
+            KeyCode::Down => {
+                self.settings_tab.select_next(self.filtered_settings.len());
+                None
+            }
+            KeyCode::Enter | KeyCode::Right | KeyCode::Left => {
+                let forward = key != KeyCode::Left;
+                match self.get_selected_setting()?.kind {
+                    SettingKind::ScoringMethod => self.update_config(|config| {
+                        config.scoring.method = match (config.scoring.method, forward) {
+                            (ScoringMethod::Bucket, true) | (ScoringMethod::Keystrokes, false) => ScoringMethod::Decay,
+                            (ScoringMethod::Decay, true) | (ScoringMethod::Bucket, false) => ScoringMethod::Keystrokes,
+                            (ScoringMethod::Keystrokes, true) | (ScoringMethod::Decay, false) => ScoringMethod::Bucket,
+                        };
+                        format!("Scoring method set to {}", scorer_for(&config.scoring).name())
+                    }),
+                    SettingKind::HalfLife => self.update_config(|config| {
+                        let step = if forward { HALF_LIFE_STEP_HOURS } else { -HALF_LIFE_STEP_HOURS };
+                        config.scoring.half_life_hours = (config.scoring.half_life_hours + step).max(HALF_LIFE_STEP_HOURS);
+                        format!("Half-life set to {} hours", config.scoring.half_life_hours)
+                    }),
+                    SettingKind::ReadOnly => {
+                        self.status_message = "This one is changed in the config file (or the Files tab)".to_string();
+                    }
+                }
+                None
+            }
+            KeyCode::Backspace | KeyCode::Esc => {
+                self.edit_kept_filter(key);
+                None
+            }
+            KeyCode::Char(c) => {
+                self.type_into_filter(c);
+                None
+            }
+            _ => None,
+        }
+    }
+
+    // Saves the scoring change and has the suggestions ranked again
+    fn update_config(&mut self, change: impl FnOnce(&mut AppConfig) -> String) {
+        let mut config = load_config().unwrap_or_else(|| AppConfig {
+            alias_file_paths: self.alias_file_paths.clone(),
+            ..Default::default()
+        });
+        let message = change(&mut config);
+        self.status_message = match save_config(&config) {
+            Ok(()) => message,
+            Err(e) => format!("Failed to save config: {}", e),
+        };
+        self.reload_commands = true;
+        self.load_settings();
+    }
+}
@@ -1,9 +1,10 @@
 use crate::cli::cli_data::Operation;
 use crate::database::database_structs::{Database, DeletedCommands};
 use crate::database::persistence::{
-    ensure_data_directory, get_database_path, get_deleted_commands_path, load_database,
+    ensure_data_directory, get_database_path, get_deleted_commands_path, load_config, load_database,
     load_deleted_commands, save_database, save_deleted_commands,
 };
+use crate::ops::history_sync::sync_history_files;
 use crate::ops::{add_alias , remove_alias, delete_suggestion};
 use crate::tui::app::App;
 use crate::tui::ui::render_ui;
@@ -47,6 +48,20 @@ pub fn run_tui(alias_file_path: PathBuf, alias_file_paths: Vec<String>) -> Resul
         }
     };
 
+    // Pick up anything new in tracked history files
+    if let Some(config) = load_config() {
+        if !config.history_file_paths.is_empty() {
+            let synced = sync_history_files(&config.history_file_paths, &mut database, &deleted_commands);
+            if synced.count == 0 {
+                synced.save();
+            } else if let Err(e) = save_database(&database, &db_path) {
+                eprintln!("Failed to save database: {}", e);
+            } else {
+                synced.save();
+            }
+        }
+    }
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -76,7 +91,7 @@ pub fn run_tui(alias_file_path: PathBuf, alias_file_paths: Vec<String>) -> Resul
 
     // Create app and load initial data
     let mut app = App::new(alias_file_path.clone(), alias_file_paths);
-    app.load_commands(&mut database);
+    app.load_commands(&mut database, &deleted_commands);
 
     // Run the app
     let res = run_app(
@@ -123,9 +138,22 @@ fn run_app<B: Backend>(
 
         if let Event::Key(key) = event::read()? {
             if key.kind == KeyEventKind::Press {
-                if let Some(operation) = app.handle_key_event(key.code) {
+                if let Some(operation) = app.handle_key_event(key) {
                     handle_operation(operation, app, database, deleted_commands, db_path, deleted_commands_path);
                 }
+                // A setting changed how commands are scored
+                if std::mem::take(&mut app.reload_commands) {
+                    app.load_commands(database, deleted_commands);
+                }
+                // A confirmed batch
+                let batch = std::mem::take(&mut app.pending_operations);
+                if !batch.is_empty() {
+                    let count = batch.len();
+                    for operation in batch {
+                        handle_operation(operation, app, database, deleted_commands, db_path, deleted_commands_path);
+                    }
+                    app.status_message = format!("Applied {} change(s)", count);
+                }
             }
         }
 
@@ -159,7 +187,8 @@ fn handle_operation(
             if let Err(e) = save_deleted_commands(deleted_commands, deleted_commands_path) {
                 eprintln!("Failed to save deleted commands: {}", e);
             }
-            app.load_commands(database);
+            app.load_commands(database, deleted_commands);
+            app.reload_alias_tabs();
             app.config_changed = true;
         }
         Operation::Remove { alias } => {
@@ -173,46 +202,108 @@ fn handle_operation(
             if let Err(e) = save_deleted_commands(deleted_commands, deleted_commands_path) {
                 eprintln!("Failed to save deleted commands: {}", e);
             }
+            app.reload_alias_tabs();
             app.config_changed = true;
         }
-        Operation::Change { old_alias, new_alias, command } => {
-            use crate::ops::alias_ops::remove_alias_from_multiple_files;
-            use crate::ops::alias_ops::add_alias_to_multiple_files;
-            // First remove the old alias
-            remove_alias_from_multiple_files(&app.alias_file_paths, old_alias.as_str());
-            if let Some(first_path) = app.alias_file_paths.first() {
-                remove_alias::remove_alias(deleted_commands, first_path, old_alias.as_str());
-            }
-            // Then add the new alias with the provided command
-            add_alias_to_multiple_files(&app.alias_file_paths, new_alias.as_str(), command.as_str());
-            if let Some(first_path) = app.alias_file_paths.first() {
-                add_alias::add_alias(database, deleted_commands, first_path, new_alias.as_str(), command.as_str());
-            }
-            app.status_message = format!("Changed alias: {} -> {}", old_alias, new_alias);
-            // Save after changing alias
-            if let Err(e) = save_database(database, db_path) {
-                eprintln!("Failed to save database: {}", e);
+        Operation::Change { old_alias, new_alias, command, .. } => {
+            use crate::ops::change_alias::change_alias;
+            match change_alias(database, deleted_commands, &app.alias_file_paths, &old_alias, new_alias.as_deref(), command.as_deref()) {
+                Ok(outcome) => {
+                    app.status_message = match outcome.warning {
+                        Some(warning) => format!("Changed alias: {} -> {} = {} (warning: {})", outcome.old_alias, outcome.alias, outcome.command, warning),
+                        None => format!("Changed alias: {} -> {} = {}", outcome.old_alias, outcome.alias, outcome.command),
+                    };
+                    // Save after changing alias
+                    if let Err(e) = save_database(database, db_path) {
+                        eprintln!("Failed to save database: {}", e);
+                    }
+                    if let Err(e) = save_deleted_commands(deleted_commands, deleted_commands_path) {
+                        eprintln!("Failed to save deleted commands: {}", e);
+                    }
+                    app.load_commands(database, deleted_commands);
+                    app.reload_alias_tabs();
+                    app.config_changed = true;
+                }
+                Err(e) => app.status_message = format!("Failed to change alias: {}", e),
             }
-            if let Err(e) = save_deleted_commands(deleted_commands, deleted_commands_path) {
-                eprintln!("Failed to save deleted commands: {}", e);
+        }
+        Operation::Move { to, aliases } => {
+            use crate::ops::alias_ops::move_alias_to_file;
+            let to = to.to_string_lossy().to_string();
+            for alias in &aliases {
+                move_alias_to_file(&app.alias_file_paths, alias, &to);
             }
+            app.status_message = format!("Moved {} to {}", aliases.join(", "), to);
+            app.reload_alias_tabs();
             app.config_changed = true;
         }
-        Operation::List => {
-            // List operation is now handled in the TUI as a separate mode
+        Operation::List { .. } => {
+            // Listing is the Aliases tab
             app.status_message = "List operation handled in TUI mode".to_string();
         }
         Operation::DeleteSuggestion { alias } => {
-            delete_suggestion::delete_suggestion(&alias, database, deleted_commands);
+            delete_suggestion::delete_suggestion(&alias, deleted_commands);
             app.status_message = format!("Deleted suggestions for: {}", alias);
-            app.load_commands(database);
+            app.load_commands(database, deleted_commands);
             // Save after deleting suggestion
-            if let Err(e) = save_database(database, db_path) {
-                eprintln!("Failed to save database: {}", e);
+            if let Err(e) = save_deleted_commands(deleted_commands, deleted_commands_path) {
+                eprintln!("Failed to save deleted commands: {}", e);
+            }
+        }
+        Operation::Ignored { action } => {
+            use crate::cli::cli_data::IgnoredAction;
+            use crate::database::database_structs::BlockKind;
+            use crate::ops::ignored::{add_block_rule, format_time, parse_duration, prune_expired, restore, snooze};
+            let now = crate::database::scoring::now();
+            prune_expired(deleted_commands, now);
+            match action {
+                IgnoredAction::List { .. } => {}
+                IgnoredAction::Restore { entry } => {
+                    let text = entry.join(" ");
+                    let aliases = crate::ops::alias_ops::get_aliases_from_multiple_files(&app.alias_file_paths);
+                    app.status_message = match restore(deleted_commands, &aliases, &text) {
+                        Ok(_) => format!("Restored: {}", text),
+                        Err(e) => e,
+                    };
+                }
+                IgnoredAction::Block { pattern, regex } => {
+                    let kind = if regex { BlockKind::Regex } else { BlockKind::Glob };
+                    app.status_message = match add_block_rule(deleted_commands, &pattern, kind) {
+                        Ok(()) => format!("Blocked suggestions matching '{}'", pattern),
+                        Err(e) => format!("Invalid pattern '{}': {}", pattern, e),
+                    };
+                }
+                IgnoredAction::Snooze { duration, command } => {
+                    let text = command.join(" ");
+                    app.status_message = match parse_duration(&duration) {
+                        Ok(seconds) => {
+                            snooze(deleted_commands, &text, now + seconds);
+                            format!("Snoozed '{}' until {}", text, format_time(now + seconds))
+                        }
+                        Err(e) => e,
+                    };
+                }
             }
             if let Err(e) = save_deleted_commands(deleted_commands, deleted_commands_path) {
                 eprintln!("Failed to save deleted commands: {}", e);
             }
+            app.load_ignored(deleted_commands);
+            app.load_commands(database, deleted_commands);
+        }
+        Operation::Explain { command, .. } => {
+            // Filled into the command details popup
+            use crate::ops::explain::explain_command;
+            let alias_file_path = app.alias_file_path.to_string_lossy().to_string();
+            app.command_explanation = explain_command(&command.join(" "), database, deleted_commands, &alias_file_path).ok();
+        }
+        Operation::Stats { days, .. } => {
+            app.load_dashboard(database, deleted_commands, days);
+        }
+        Operation::Search { .. } | Operation::Which { .. } | Operation::Expand { .. } => {
+            app.status_message = "Search, which and expand not available in TUI mode".to_string();
+        }
+        Operation::Accept { .. } => {
+            app.status_message = "Accept not available in TUI mode".to_string();
         }
         Operation::GetSuggestions { .. } => {
             // Get suggestions is not available in TUI mode
@@ -221,6 +312,13 @@ fn handle_operation(
         Operation::Tui => {
             // Already in TUI mode, do nothing
         }
+        Operation::SyncHistory { .. } => {
+            // History files are synced when the TUI starts
+            app.status_message = "Sync history not available in TUI mode".to_string();
+        }
+        Operation::Import { .. } => {
+            app.status_message = "Import not available in TUI mode".to_string();
+        }
         Operation::Init { .. } => {
             // Init is not available in TUI mode
             app.status_message = "Init command not available in TUI mode".to_string();
@@ -0,0 +1,50 @@
+use crate::tui::app::App;
+use crate::tui::ui::{highlight_spans, mark_span, render_controls, render_filter, tab_layout};
+use ratatui::{
+    Frame,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+};
+
+pub fn render(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let chunks = tab_layout(area);
+    render_filter(f, &app.aliases_tab, chunks[0]);
+
+    if !app.filtered_aliases.is_empty() {
+        let aliases: Vec<ListItem> = app
+            .filtered_aliases
+            .iter()
+            .zip(&app.filtered_alias_matches)
+            .map(|((alias, command), (alias_positions, command_positions))| {
+                alias_item(alias, command, alias_positions, command_positions, app.marked_aliases.contains(alias))
+            })
+            .collect();
+
+        let aliases_list = List::new(aliases)
+            .block(Block::default().borders(Borders::ALL).title(format!("Aliases ({})", app.aliases.len())))
+            .highlight_style(Style::default().bg(Color::DarkGray))
+            .highlight_symbol(">> ");
+
+        f.render_stateful_widget(aliases_list, chunks[1], &mut app.aliases_tab.state.clone());
+    } else {
+        let empty_message = Paragraph::new(if app.aliases.is_empty() { "No aliases found" } else { "No aliases match the filter" })
+            .style(Style::default().fg(Color::Yellow))
+            .block(Block::default().borders(Borders::ALL).title("Aliases"));
+        f.render_widget(empty_message, chunks[1]);
+    }
+
+    render_controls(
+        f,
+        "↑/↓ - Navigate | r - Remove | c - Change | e - Edit command | Space - Mark | Enter - Show/Batch actions",
+        chunks[2],
+    );
+}
+
+fn alias_item<'a>(alias: &'a str, command: &'a str, alias_positions: &[usize], command_positions: &[usize], marked: bool) -> ListItem<'a> {
+    let mut spans = vec![mark_span(marked)];
+    spans.extend(highlight_spans(alias, alias_positions, Style::default().fg(Color::Magenta)));
+    spans.push(Span::styled(" = ", Style::default().fg(Color::Magenta)));
+    spans.extend(highlight_spans(command, command_positions, Style::default().fg(Color::Blue)));
+    ListItem::new(Line::from(spans))
+}
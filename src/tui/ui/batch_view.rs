@@ -0,0 +1,116 @@
+use crate::tui::app::{App, AppMode};
+use crate::tui::batch::BatchChange;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+};
+
+pub fn render(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    match app.mode {
+        AppMode::BatchConfirmation => render_confirmation(f, app, area),
+        _ => render_actions(f, app, area),
+    }
+}
+
+fn render_actions(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // What is marked
+            Constraint::Min(0),    // Actions
+            Constraint::Length(3), // Controls
+        ])
+        .split(area);
+
+    let marked: Vec<&str> = if app.marked_commands.is_empty() {
+        app.marked_aliases.iter().map(|a| a.as_str()).collect()
+    } else {
+        app.marked_commands.iter().map(|c| c.as_str()).collect()
+    };
+    let summary = Paragraph::new(marked.join(", "))
+        .style(Style::default().fg(Color::Cyan))
+        .block(Block::default().borders(Borders::ALL).title(format!("{} Marked", marked.len())));
+    f.render_widget(summary, chunks[0]);
+
+    let actions: Vec<ListItem> = app
+        .batch_actions
+        .iter()
+        .map(|action| ListItem::new(Span::styled(action.label(), Style::default().fg(Color::Blue))))
+        .collect();
+    let actions_list = List::new(actions)
+        .block(Block::default().borders(Borders::ALL).title("Batch Actions"))
+        .highlight_style(Style::default().bg(Color::DarkGray))
+        .highlight_symbol(">> ");
+    f.render_stateful_widget(actions_list, chunks[1], &mut app.batch_actions_state.clone());
+
+    let controls = Paragraph::new("↑/↓ - Navigate | Enter - Review changes | Esc - Back to the marked list")
+        .style(Style::default().fg(Color::Green))
+        .block(Block::default().borders(Borders::ALL).title("Controls"));
+    f.render_widget(controls, chunks[2]);
+}
+
+fn render_confirmation(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Every change
+            Constraint::Length(3), // Buttons
+        ])
+        .split(area);
+
+    let changes: Vec<ListItem> = app
+        .batch_changes
+        .iter()
+        .map(|change| {
+            let line = match change {
+                BatchChange::Add { alias, command } => Line::from(vec![
+                    Span::styled("+ add     ", Style::default().fg(Color::Green)),
+                    Span::styled(alias.as_str(), Style::default().fg(Color::Magenta)),
+                    Span::raw(" = "),
+                    Span::styled(command.as_str(), Style::default().fg(Color::Blue)),
+                ]),
+                BatchChange::Remove { alias, command } => Line::from(vec![
+                    Span::styled("- remove  ", Style::default().fg(Color::Red)),
+                    Span::styled(alias.as_str(), Style::default().fg(Color::Magenta)),
+                    Span::raw(" = "),
+                    Span::styled(command.as_str(), Style::default().fg(Color::Blue)),
+                ]),
+                BatchChange::DeleteSuggestion { command } => Line::from(vec![
+                    Span::styled("- delete  ", Style::default().fg(Color::Red)),
+                    Span::styled(command.as_str(), Style::default().fg(Color::Blue)),
+                ]),
+                BatchChange::Move { alias, from, to } => Line::from(vec![
+                    Span::styled("→ move    ", Style::default().fg(Color::Cyan)),
+                    Span::styled(alias.as_str(), Style::default().fg(Color::Magenta)),
+                    Span::raw(format!("  {} → {}", from, to)),
+                ]),
+                BatchChange::Skip { text, reason } => Line::from(Span::styled(
+                    format!("  skip    {} ({})", text, reason),
+                    Style::default().fg(Color::DarkGray),
+                )),
+            };
+            ListItem::new(line)
+        })
+        .collect();
+    let changes_list = List::new(changes)
+        .block(Block::default().borders(Borders::ALL).title(format!("{} Change(s)", app.batch_changes.len())));
+    f.render_widget(changes_list, chunks[0]);
+
+    let ok_text = if app.batch_confirmation_selection {
+        Span::styled(" [OK] ", Style::default().fg(Color::White).bg(Color::Blue))
+    } else {
+        Span::styled(" [OK] ", Style::default().fg(Color::Blue))
+    };
+    let undo_text = if !app.batch_confirmation_selection {
+        Span::styled(" [Undo] ", Style::default().fg(Color::White).bg(Color::Red))
+    } else {
+        Span::styled(" [Undo] ", Style::default().fg(Color::Red))
+    };
+    let buttons = Paragraph::new(Line::from(vec![ok_text, undo_text]))
+        .block(Block::default().borders(Borders::ALL).title("Confirm"))
+        .alignment(Alignment::Center);
+    f.render_widget(buttons, chunks[1]);
+}
@@ -0,0 +1,50 @@
+use crate::tui::app::App;
+use crate::tui::ui::{highlight_spans, mark_span, render_controls, render_filter, tab_layout};
+use ratatui::{
+    Frame,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+};
+
+pub fn render(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let chunks = tab_layout(area);
+    render_filter(f, &app.commands_tab, chunks[0]);
+
+    if app.filtered_commands.is_empty() {
+        let empty_message = Paragraph::new(if app.commands.is_empty() { "No suggestions yet" } else { "No commands match the filter" })
+            .style(Style::default().fg(Color::Yellow))
+            .block(Block::default().borders(Borders::ALL).title("Commands"));
+        f.render_widget(empty_message, chunks[1]);
+    } else {
+        let commands: Vec<ListItem> = app
+            .filtered_commands
+            .iter()
+            .zip(&app.filtered_command_matches)
+            .map(|(cmd, positions)| {
+                let mut spans = vec![
+                    mark_span(app.marked_commands.contains(&cmd.command_text)),
+                    Span::styled(
+                        format!("Score: {} | ", cmd.score),
+                        Style::default().fg(Color::Green),
+                    ),
+                ];
+                spans.extend(highlight_spans(&cmd.command_text, positions, Style::default().fg(Color::Blue)));
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let commands_list = List::new(commands)
+            .block(Block::default().borders(Borders::ALL).title("Commands"))
+            .highlight_style(Style::default().bg(Color::DarkGray))
+            .highlight_symbol(">> ");
+
+        f.render_stateful_widget(commands_list, chunks[1], &mut app.commands_tab.state.clone());
+    }
+
+    render_controls(
+        f,
+        "↑/↓ - Navigate | Enter - Details | a - Add alias | Space - Mark | s - Statistics | Tab/1-5 - Switch tab | q - Quit",
+        chunks[2],
+    );
+}
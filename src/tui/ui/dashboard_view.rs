@@ -0,0 +1,168 @@
+use crate::tui::app::App;
+use crate::tui::dashboard::Dashboard;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, List, ListItem, Paragraph, Sparkline},
+};
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(6), // Commands per day
+            Constraint::Fill(1),   // Top tools and aliases
+            Constraint::Fill(1),   // Scores and idle aliases
+            Constraint::Length(3), // Controls
+        ])
+        .split(area);
+
+    let controls = Paragraph::new("↑/↓ - Scroll idle aliases | Esc - Back to main menu")
+        .style(Style::default().fg(Color::Green))
+        .block(Block::default().borders(Borders::ALL).title("Controls"));
+    f.render_widget(controls, chunks[3]);
+
+    let Some(dashboard) = &app.dashboard else {
+        let loading = Paragraph::new("Loading...")
+            .style(Style::default().fg(Color::Yellow))
+            .block(Block::default().borders(Borders::ALL).title("Statistics"));
+        f.render_widget(loading, chunks[0]);
+        return;
+    };
+
+    render_commands_per_day(f, dashboard, chunks[0]);
+
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+    let tools: Vec<(String, u64)> = dashboard.report.top_tools.iter().map(|t| (t.tool.clone(), t.count)).collect();
+    render_top(f, "Top Tools", &tools, "Nothing recorded in this period yet", Color::Green, top[0]);
+    let aliases: Vec<(String, u64)> = dashboard.report.top_aliases.iter().map(|a| (a.alias.clone(), a.count)).collect();
+    // bash hands the hook the expanded line, so alias runs only show up in zsh and fish
+    render_top(f, "Top Aliases", &aliases, "No alias runs recorded in this period", Color::Magenta, top[1]);
+
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[2]);
+    render_score_histogram(f, dashboard, bottom[0]);
+    render_idle_aliases(f, app, dashboard, bottom[1]);
+}
+
+fn render_commands_per_day(f: &mut Frame, dashboard: &Dashboard, area: Rect) {
+    let report = &dashboard.report;
+    let title = format!(
+        "Commands per Day, {} to {}: {} total, {:.1} per active day",
+        report.from, report.to, report.total_commands, report.average_per_active_day
+    );
+    // One column per day looks lost in a wide terminal, so each day gets an equal share
+    let per_day = (area.width.saturating_sub(2) as usize / report.days.len().max(1)).max(1);
+    let data: Vec<u64> = report
+        .days
+        .iter()
+        .flat_map(|day| std::iter::repeat_n(day.commands as u64, per_day))
+        .collect();
+    let sparkline = Sparkline::default()
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .data(&data)
+        .style(Style::default().fg(Color::Cyan));
+    f.render_widget(sparkline, area);
+}
+
+fn render_top(f: &mut Frame, title: &str, counts: &[(String, u64)], empty: &str, color: Color, area: Rect) {
+    let block = Block::default().borders(Borders::ALL).title(title.to_string());
+    if counts.is_empty() {
+        f.render_widget(Paragraph::new(empty.to_string()).style(Style::default().fg(Color::Yellow)).block(block), area);
+        return;
+    }
+    let bars: Vec<Bar> = counts
+        .iter()
+        .map(|(name, count)| {
+            Bar::default()
+                .label(Line::from(name.as_str()))
+                .value(*count)
+                .text_value(count.to_string())
+                .style(Style::default().fg(color))
+                .value_style(Style::default().fg(Color::Black).bg(color))
+        })
+        .collect();
+    let chart = BarChart::default()
+        .block(block)
+        .direction(Direction::Horizontal)
+        .bar_width(1)
+        .bar_gap(0)
+        .data(BarGroup::default().bars(&bars));
+    f.render_widget(chart, area);
+}
+
+fn render_score_histogram(f: &mut Frame, dashboard: &Dashboard, area: Rect) {
+    let total: u64 = dashboard.score_histogram.iter().map(|(_, count)| count).sum();
+    let block = Block::default().borders(Borders::ALL).title(format!("Suggestion Scores ({} suggestions)", total));
+    if total == 0 {
+        f.render_widget(Paragraph::new("No suggestions yet").style(Style::default().fg(Color::Yellow)).block(block), area);
+        return;
+    }
+    let bars: Vec<Bar> = dashboard
+        .score_histogram
+        .iter()
+        .map(|(from, count)| {
+            Bar::default()
+                .label(Line::from(short_number(*from)))
+                .value(*count)
+                .style(Style::default().fg(Color::Blue))
+                .value_style(Style::default().fg(Color::White).bg(Color::Blue))
+        })
+        .collect();
+    let bar_width = (area.width.saturating_sub(2) / bars.len().max(1) as u16).saturating_sub(1).max(1);
+    let chart = BarChart::default()
+        .block(block)
+        .bar_width(bar_width)
+        .bar_gap(1)
+        .data(BarGroup::default().bars(&bars));
+    f.render_widget(chart, area);
+}
+
+fn render_idle_aliases(f: &mut Frame, app: &App, dashboard: &Dashboard, area: Rect) {
+    let title = format!("Not Run Since {} ({})", dashboard.report.from, dashboard.idle_aliases.len());
+    if dashboard.idle_aliases.is_empty() {
+        let message = Paragraph::new("Every alias was run in this period")
+            .style(Style::default().fg(Color::Green))
+            .block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(message, area);
+        return;
+    }
+    let alias_width = dashboard.idle_aliases.iter().map(|a| a.alias.chars().count()).max().unwrap_or(0);
+    let items: Vec<ListItem> = dashboard
+        .idle_aliases
+        .iter()
+        .map(|idle| {
+            let last_used = match &idle.last_used {
+                Some(day) => format!("last run {}  ", day),
+                None => "no runs recorded  ".to_string(),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:<width$}  ", idle.alias, width = alias_width), Style::default().fg(Color::Magenta)),
+                Span::styled(last_used, Style::default().fg(Color::DarkGray)),
+                Span::styled(idle.command.as_str(), Style::default().fg(Color::Blue)),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().bg(Color::DarkGray))
+        .highlight_symbol(">> ");
+    f.render_stateful_widget(list, area, &mut app.dashboard_idle_state.clone());
+}
+
+// Histogram labels have to fit under narrow bars
+fn short_number(n: i32) -> String {
+    match n {
+        n if n >= 1_000_000 => format!("{}M", n / 1_000_000),
+        n if n >= 10_000 => format!("{}k", n / 1_000),
+        n if n >= 1_000 => format!("{:.1}k", n as f64 / 1_000.0),
+        n => n.to_string(),
+    }
+}
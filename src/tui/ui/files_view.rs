@@ -0,0 +1,85 @@
+use crate::tui::app::App;
+use crate::tui::ui::{highlight_spans, render_controls, render_filter, tab_layout};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+};
+
+pub fn render(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let chunks = tab_layout(area);
+    render_filter(f, &app.files_tab, chunks[0]);
+
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+
+    // Tracked files, the default one first
+    if !app.filtered_files.is_empty() {
+        let files: Vec<ListItem> = app
+            .filtered_files
+            .iter()
+            .map(|(i, positions)| {
+                let file = &app.alias_files[*i];
+                let mut spans = highlight_spans(&file.path, positions, Style::default().fg(Color::Blue));
+                if *i == 0 {
+                    spans.push(Span::styled(" (default)", Style::default().fg(Color::Cyan)));
+                }
+                if file.exists {
+                    spans.push(Span::styled(
+                        format!(" {} aliases", file.definitions.len()),
+                        Style::default().fg(Color::Green),
+                    ));
+                } else {
+                    spans.push(Span::styled(" missing", Style::default().fg(Color::Red)));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let files_list = List::new(files)
+            .block(Block::default().borders(Borders::ALL).title(format!("Alias Files ({})", app.alias_files.len())))
+            .highlight_style(Style::default().bg(Color::DarkGray))
+            .highlight_symbol(">> ");
+
+        f.render_stateful_widget(files_list, panes[0], &mut app.files_tab.state.clone());
+    } else {
+        let empty_message = Paragraph::new("No files match the filter")
+            .style(Style::default().fg(Color::Yellow))
+            .block(Block::default().borders(Borders::ALL).title("Alias Files"));
+        f.render_widget(empty_message, panes[0]);
+    }
+
+    // What the selected file defines, greyed out where another file wins
+    let definitions: Vec<ListItem> = app
+        .get_selected_file()
+        .map(|file| {
+            file.definitions
+                .iter()
+                .map(|(definition, shadowed)| {
+                    let (alias_style, command_style) = if *shadowed {
+                        (Style::default().fg(Color::DarkGray), Style::default().fg(Color::DarkGray))
+                    } else {
+                        (Style::default().fg(Color::Magenta), Style::default().fg(Color::Blue))
+                    };
+                    let mut spans = vec![
+                        Span::styled(definition.alias.as_str(), alias_style),
+                        Span::styled(" = ", alias_style),
+                        Span::styled(definition.command.as_str(), command_style),
+                    ];
+                    if *shadowed {
+                        spans.push(Span::styled(" (overridden)", Style::default().fg(Color::DarkGray)));
+                    }
+                    ListItem::new(Line::from(spans))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let definitions_list = List::new(definitions).block(Block::default().borders(Borders::ALL).title("Defined Here"));
+    f.render_widget(definitions_list, panes[1]);
+
+    render_controls(f, "↑/↓ - Navigate | d - Make default for new aliases | / - Filter | Tab/1-5 - Switch tab", chunks[2]);
+}
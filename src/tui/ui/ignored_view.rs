@@ -0,0 +1,70 @@
+use crate::database::database_structs::BlockKind;
+use crate::ops::ignored::IgnoredKind;
+use crate::tui::app::App;
+use crate::tui::ui::{highlight_spans, render_controls, render_filter, tab_layout};
+use ratatui::{
+    Frame,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+};
+
+pub fn render(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let chunks = tab_layout(area);
+
+    // The block rule being typed takes the filter's place
+    if let Some(kind) = app.block_rule_kind {
+        let title = match kind {
+            BlockKind::Glob => "New Block Rule (glob, Tab for regex)",
+            BlockKind::Regex => "New Block Rule (regex, Tab for glob)",
+        };
+        let input = Paragraph::new(app.input.as_str())
+            .style(Style::default().fg(Color::Yellow))
+            .block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(input, chunks[0]);
+        f.set_cursor_position((
+            chunks[0].x + app.input[..app.cursor_position].chars().count() as u16 + 1,
+            chunks[0].y + 1,
+        ));
+    } else {
+        render_filter(f, &app.ignored_tab, chunks[0]);
+    }
+
+    // Ignored list
+    if !app.filtered_ignored.is_empty() {
+        let label_width = app.ignored_entries.iter().map(|e| e.label().len()).max().unwrap_or(0);
+        let entries: Vec<ListItem> = app
+            .filtered_ignored
+            .iter()
+            .map(|(i, positions)| {
+                let entry = &app.ignored_entries[*i];
+                let color = match entry.kind {
+                    IgnoredKind::Deleted => Color::Red,
+                    IgnoredKind::Aliased(_) => Color::Green,
+                    IgnoredKind::Blocked(_) => Color::Magenta,
+                    IgnoredKind::Snoozed(_) => Color::Cyan,
+                };
+                let mut spans = vec![Span::styled(
+                    format!("{:<width$}  ", entry.label(), width = label_width),
+                    Style::default().fg(color),
+                )];
+                spans.extend(highlight_spans(&entry.text, positions, Style::default().fg(Color::Blue)));
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let entries_list = List::new(entries)
+            .block(Block::default().borders(Borders::ALL).title("Ignored Suggestions"))
+            .highlight_style(Style::default().bg(Color::DarkGray))
+            .highlight_symbol(">> ");
+
+        f.render_stateful_widget(entries_list, chunks[1], &mut app.ignored_tab.state.clone());
+    } else {
+        let empty_message = Paragraph::new(if app.ignored_entries.is_empty() { "Nothing is ignored" } else { "Nothing ignored matches the filter" })
+            .style(Style::default().fg(Color::Yellow))
+            .block(Block::default().borders(Borders::ALL).title("Ignored Suggestions"));
+        f.render_widget(empty_message, chunks[1]);
+    }
+
+    render_controls(f, "↑/↓ - Navigate | Enter/u - Restore | b - Block pattern | / - Filter | Tab/1-5 - Switch tab", chunks[2]);
+}
@@ -1,4 +1,5 @@
 use crate::tui::app::{App, AppMode};
+use crate::tui::ui::highlight_spans;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Alignment},
@@ -12,11 +13,9 @@ pub fn render(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
         AppMode::AddAliasStep1 => render_add_alias_step1(f, app, area),
         AppMode::AddAliasStep2 => render_add_alias_step2(f, app, area),
         AppMode::AddAliasConfirmation => render_add_alias_confirmation(f, app, area),
-        AppMode::RemoveAliasStep1 => render_remove_alias_step1(f, app, area),
         AppMode::RemoveAliasConfirmation => render_remove_alias_confirmation(f, app, area),
-        AppMode::ChangeAliasStep1 => render_change_alias_step1(f, app, area),
         AppMode::ChangeAliasStep2 => render_change_alias_step2(f, app, area),
-        AppMode::ListAliases => render_list_aliases(f, app, area),
+        AppMode::EditAliasCommand => render_edit_alias_command(f, app, area),
 
         _ => render_default_input(f, app, area),
     }
@@ -53,9 +52,10 @@ fn render_add_alias_step1(f: &mut Frame, app: &App, area: ratatui::layout::Rect)
         let commands: Vec<ListItem> = app
             .filtered_commands
             .iter()
-            .map(|cmd| ListItem::new(
-                ratatui::text::Span::styled(cmd.command_text.as_str(), ratatui::style::Style::default().fg(ratatui::style::Color::Blue))
-            ))
+            .zip(&app.filtered_command_matches)
+            .map(|(cmd, positions)| ListItem::new(Line::from(
+                highlight_spans(&cmd.command_text, positions, Style::default().fg(Color::Blue))
+            )))
             .collect();
 
         let commands_list = List::new(commands)
@@ -63,7 +63,7 @@ fn render_add_alias_step1(f: &mut Frame, app: &App, area: ratatui::layout::Rect)
             .highlight_style(Style::default().bg(Color::DarkGray))
             .highlight_symbol(">> ");
 
-        f.render_stateful_widget(commands_list, chunks[2], &mut app.list_state.clone());
+        f.render_stateful_widget(commands_list, chunks[2], &mut app.commands_tab.state.clone());
     }
 }
 
@@ -108,13 +108,16 @@ fn render_add_alias_step2(f: &mut Frame, app: &App, area: ratatui::layout::Rect)
             .iter()
             .map(|suggestion| {
                 use ratatui::text::{Span, Line};
+                use crate::ops::keystroke_savings::saved_per_run;
+                let daily_savings = saved_per_run(&suggestion.command, &suggestion.alias) as f64 * app.selected_command_daily_runs;
                 ListItem::new(Line::from(vec![
                     Span::styled(&suggestion.alias, ratatui::style::Style::default().fg(ratatui::style::Color::Magenta)),
                     Span::raw(" = "),
                     Span::styled(&suggestion.command, ratatui::style::Style::default().fg(ratatui::style::Color::Blue)),
                     Span::raw(" ("),
                     Span::styled(&suggestion.reason, ratatui::style::Style::default().fg(ratatui::style::Color::Green)),
-                    Span::raw(")"),
+                    Span::raw(") "),
+                    Span::styled(format!("~{:.0} keys/day", daily_savings), ratatui::style::Style::default().fg(ratatui::style::Color::Yellow)),
                 ]))
             })
             .collect();
@@ -141,10 +144,16 @@ fn render_add_alias_confirmation(f: &mut Frame, app: &App, area: ratatui::layout
     // Confirmation message
     let alias = app.confirmation_alias.as_deref().unwrap_or("unknown");
     let command = app.confirmation_command.as_deref().unwrap_or("unknown");
-    let message = format!("Alias added: {} = {}", alias, command);
+    let (message, title) = match (&app.change_old_alias, &app.change_old_command) {
+        (Some(old_alias), Some(old_command)) => (
+            format!("{} = {}  →  {} = {}", old_alias, old_command, alias, command),
+            "Change Alias",
+        ),
+        _ => (format!("Alias added: {} = {}", alias, command), "Alias Added"),
+    };
     let confirmation_message = Paragraph::new(message)
         .style(Style::default().fg(Color::Green))
-        .block(Block::default().borders(Borders::ALL).title("Alias Added"))
+        .block(Block::default().borders(Borders::ALL).title(title))
         .alignment(Alignment::Center);
     f.render_widget(confirmation_message, chunks[0]);
 
@@ -167,60 +176,6 @@ fn render_add_alias_confirmation(f: &mut Frame, app: &App, area: ratatui::layout
     f.render_widget(buttons, chunks[2]);
 }
 
-fn render_remove_alias_step1(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Alias input
-            Constraint::Length(1), // Spacer
-            Constraint::Min(0),    // Alias list
-        ])
-        .split(area);
-
-    // Alias input field
-    let input = Paragraph::new(app.input.as_str())
-        .style(Style::default().fg(Color::Yellow))
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Filter aliases to remove (type to filter, ↑↓ to navigate)"),
-        );
-    f.render_widget(input, chunks[0]);
-
-    // Set cursor position for alias input
-    f.set_cursor_position((
-        chunks[0].x + app.cursor_position as u16 + 1,
-        chunks[0].y + 1,
-    ));
-
-    // Alias list
-    if !app.filtered_aliases.is_empty() {
-        let aliases: Vec<ListItem> = app
-            .filtered_aliases
-            .iter()
-            .map(|(alias, command)| {
-                ListItem::new(Line::from(vec![
-                    Span::styled(
-                        format!("{} = ", alias),
-                        Style::default().fg(Color::Magenta),
-                    ),
-                    Span::styled(
-                        command,
-                        Style::default().fg(Color::Blue),
-                    ),
-                ]))
-            })
-            .collect();
-
-        let aliases_list = List::new(aliases)
-            .block(Block::default().borders(Borders::ALL).title("Aliases"))
-            .highlight_style(Style::default().bg(Color::DarkGray))
-            .highlight_symbol(">> ");
-
-        f.render_stateful_widget(aliases_list, chunks[2], &mut app.list_state.clone());
-    }
-}
-
 fn render_remove_alias_confirmation(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -264,60 +219,6 @@ fn render_remove_alias_confirmation(f: &mut Frame, app: &App, area: ratatui::lay
     f.render_widget(buttons, chunks[2]);
 }
 
-fn render_change_alias_step1(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Alias input
-            Constraint::Length(1), // Spacer
-            Constraint::Min(0),    // Alias list
-        ])
-        .split(area);
-
-    // Alias input field
-    let input = Paragraph::new(app.input.as_str())
-        .style(Style::default().fg(Color::Yellow))
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Filter aliases to change (type to filter, ↑↓ to navigate)"),
-        );
-    f.render_widget(input, chunks[0]);
-
-    // Set cursor position for alias input
-    f.set_cursor_position((
-        chunks[0].x + app.cursor_position as u16 + 1,
-        chunks[0].y + 1,
-    ));
-
-    // Alias list
-    if !app.filtered_aliases.is_empty() {
-        let aliases: Vec<ListItem> = app
-            .filtered_aliases
-            .iter()
-            .map(|(alias, command)| {
-                ListItem::new(Line::from(vec![
-                    Span::styled(
-                        format!("{} = ", alias),
-                        Style::default().fg(Color::Magenta),
-                    ),
-                    Span::styled(
-                        command,
-                        Style::default().fg(Color::Blue),
-                    ),
-                ]))
-            })
-            .collect();
-
-        let aliases_list = List::new(aliases)
-            .block(Block::default().borders(Borders::ALL).title("Aliases"))
-            .highlight_style(Style::default().bg(Color::DarkGray))
-            .highlight_symbol(">> ");
-
-        f.render_stateful_widget(aliases_list, chunks[2], &mut app.list_state.clone());
-    }
-}
-
 fn render_change_alias_step2(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -388,6 +289,47 @@ fn render_change_alias_step2(f: &mut Frame, app: &App, area: ratatui::layout::Re
     }
 }
 
+fn render_edit_alias_command(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Selected alias display
+            Constraint::Length(3), // Command editor
+            Constraint::Length(1), // Spacer
+            Constraint::Min(0),    // Keys
+        ])
+        .split(area);
+
+    let old_alias = app.change_old_alias.as_deref().unwrap_or("No alias selected");
+    let old_command = app.change_old_command.as_deref().unwrap_or("");
+    let alias_display = Paragraph::new(format!("Selected alias: {} = {}", old_alias, old_command))
+        .style(Style::default().fg(Color::Green))
+        .block(Block::default().borders(Borders::ALL).title("Selected Alias"));
+    f.render_widget(alias_display, chunks[0]);
+
+    let editor = Paragraph::new(app.command_editor.text.as_str())
+        .style(Style::default().fg(Color::Yellow))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Command for {}", app.change_new_alias)),
+        );
+    f.render_widget(editor, chunks[1]);
+    f.set_cursor_position((
+        chunks[1].x + app.command_editor.cursor_column() as u16 + 1,
+        chunks[1].y + 1,
+    ));
+
+    let keys = Paragraph::new(vec![
+        Line::from("←/→ - Move | Ctrl+←/→ or Alt+b/f - Move by word | Home/End or Ctrl+a/e - Start/end"),
+        Line::from("Ctrl+w or Alt+Backspace - Delete word | Alt+d - Delete next word | Ctrl+u/k - Delete to start/end"),
+        Line::from("↑/↓ - Recorded commands | Enter - Check and confirm | Esc - Cancel"),
+    ])
+    .style(Style::default().fg(Color::DarkGray))
+    .block(Block::default().borders(Borders::ALL).title("Keys"));
+    f.render_widget(keys, chunks[3]);
+}
+
 fn render_default_input(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -412,9 +354,10 @@ fn render_default_input(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
         let commands: Vec<ListItem> = app
             .filtered_commands
             .iter()
-            .map(|cmd| ListItem::new(
-                ratatui::text::Span::styled(cmd.command_text.as_str(), ratatui::style::Style::default().fg(ratatui::style::Color::Blue))
-            ))
+            .zip(&app.filtered_command_matches)
+            .map(|(cmd, positions)| ListItem::new(Line::from(
+                highlight_spans(&cmd.command_text, positions, Style::default().fg(Color::Blue))
+            )))
             .collect();
 
         let commands_list = List::new(commands)
@@ -425,60 +368,3 @@ fn render_default_input(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
         f.render_widget(commands_list, chunks[1]);
     }
 }
-
-fn render_list_aliases(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Min(0),    // Alias list
-            Constraint::Length(3), // Controls
-        ])
-        .split(area);
-
-    // Header
-    let header = Paragraph::new("Aliases")
-        .style(Style::default().fg(Color::Cyan))
-        .block(Block::default().borders(Borders::ALL).title("Alias List"));
-    f.render_widget(header, chunks[0]);
-
-    // Alias list
-    if !app.aliases.is_empty() {
-        let aliases: Vec<ListItem> = app
-            .aliases
-            .iter()
-            .map(|(alias, command)| {
-                ListItem::new(Line::from(vec![
-                    Span::styled(
-                        format!("{} = ", alias),
-                        Style::default().fg(Color::Magenta),
-                    ),
-                    Span::styled(
-                        command,
-                        Style::default().fg(Color::Blue),
-                    ),
-                ]))
-            })
-            .collect();
-
-        let aliases_list = List::new(aliases)
-            .block(Block::default().borders(Borders::ALL).title("Aliases"))
-            .highlight_style(Style::default().bg(Color::DarkGray))
-            .highlight_symbol(">> ");
-
-        f.render_stateful_widget(aliases_list, chunks[1], &mut app.list_aliases_state.clone());
-    } else {
-        let empty_message = Paragraph::new("No aliases found")
-            .style(Style::default().fg(Color::Yellow))
-            .block(Block::default().borders(Borders::ALL).title("Aliases"));
-        f.render_widget(empty_message, chunks[1]);
-    }
-
-    // Controls
-    let controls = Paragraph::new("↑/↓ - Navigate | Enter - Select | Esc - Back to main menu")
-        .style(Style::default().fg(Color::Green))
-        .block(Block::default().borders(Borders::ALL).title("Controls"));
-    f.render_widget(controls, chunks[2]);
-}
-
-
@@ -1,13 +1,21 @@
+pub mod aliases_view;
+pub mod batch_view;
+pub mod commands_view;
+pub mod dashboard_view;
+pub mod files_view;
+pub mod ignored_view;
 pub mod input_view;
-pub mod main_view;
 pub mod popup;
+pub mod settings_view;
 
 use crate::tui::app::{App, AppMode};
+use crate::tui::tabs::{Tab, TabList};
 use ratatui::{
     Frame,
-    layout::{Alignment, Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Paragraph},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Tabs},
 };
 
 pub fn render_ui(f: &mut Frame, app: &App) {
@@ -21,15 +29,13 @@ pub fn render_ui(f: &mut Frame, app: &App) {
         ])
         .split(f.area());
 
-    // Header
-    let header = Paragraph::new("Alman TUI")
-        .style(
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        )
-        .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::ALL));
+    // Header, the tabs with their number keys
+    let titles = Tab::ALL.iter().enumerate().map(|(i, tab)| format!("{} {}", i + 1, tab.title()));
+    let header = Tabs::new(titles)
+        .select(app.tab.index())
+        .style(Style::default().fg(Color::DarkGray))
+        .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .block(Block::default().borders(Borders::ALL).title("Alman TUI"));
     f.render_widget(header, chunks[0]);
 
     // Main content based on mode or popup
@@ -37,9 +43,15 @@ pub fn render_ui(f: &mut Frame, app: &App) {
         crate::tui::ui::popup::render_command_details_popup(f, app);
     } else {
         match app.mode {
-            AppMode::Main => main_view::render(f, app, chunks[1]),
-            AppMode::AddAliasStep1 | AppMode::AddAliasStep2 | AppMode::AddAliasConfirmation => input_view::render(f, app, chunks[1]),
-            AppMode::RemoveAliasStep1 | AppMode::RemoveAliasConfirmation => input_view::render(f, app, chunks[1]),
+            AppMode::Main => match app.tab {
+                Tab::Commands => commands_view::render(f, app, chunks[1]),
+                Tab::Aliases => aliases_view::render(f, app, chunks[1]),
+                Tab::Files => files_view::render(f, app, chunks[1]),
+                Tab::Ignored => ignored_view::render(f, app, chunks[1]),
+                Tab::Settings => settings_view::render(f, app, chunks[1]),
+            },
+            AppMode::BatchActions | AppMode::BatchConfirmation => batch_view::render(f, app, chunks[1]),
+            AppMode::Dashboard => dashboard_view::render(f, app, chunks[1]),
             _ => input_view::render(f, app, chunks[1]),
         }
     }
@@ -55,3 +67,72 @@ pub fn render_ui(f: &mut Frame, app: &App) {
         popup::render(f, app);
     }
 }
+
+// The tab's filter, with the cursor in it while it is being typed
+pub fn render_filter(f: &mut Frame, list: &TabList, area: Rect) {
+    let (title, style) = if list.filtering {
+        ("Filter (Enter to keep it, Esc to clear it)", Style::default().fg(Color::Yellow))
+    } else if list.filter.is_empty() {
+        ("Filter (/ or just type)", Style::default().fg(Color::DarkGray))
+    } else {
+        ("Filter (/ to edit, Esc to clear)", Style::default().fg(Color::Yellow))
+    };
+    let filter = Paragraph::new(list.filter.as_str())
+        .style(style)
+        .block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(filter, area);
+    if list.filtering {
+        f.set_cursor_position((area.x + list.filter.chars().count() as u16 + 1, area.y + 1));
+    }
+}
+
+// The keys of a tab, in the box at its bottom
+pub fn render_controls(f: &mut Frame, keys: &str, area: Rect) {
+    let controls = Paragraph::new(Line::from(keys.to_string()))
+        .style(Style::default().fg(Color::Green))
+        .block(Block::default().borders(Borders::ALL).title("Controls"));
+    f.render_widget(controls, area);
+}
+
+// Filter, list and controls, top to bottom
+pub fn tab_layout(area: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Filter
+            Constraint::Min(0),    // List
+            Constraint::Length(3), // Controls
+        ])
+        .split(area)
+}
+
+// Shown in front of rows marked for a batch action
+pub fn mark_span(marked: bool) -> Span<'static> {
+    if marked {
+        Span::styled("● ", Style::default().fg(Color::Cyan))
+    } else {
+        Span::raw("  ")
+    }
+}
+
+// `text` in `style`, with the chars at `positions` (what a filter matched) picked out
+pub fn highlight_spans<'a>(text: &'a str, positions: &[usize], style: Style) -> Vec<Span<'a>> {
+    let matched_style = style.fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let mut spans = Vec::new();
+    let mut run_start = 0;
+    let mut run_matched = false;
+    for (i, (byte, _)) in text.char_indices().enumerate() {
+        let matched = positions.contains(&i);
+        if matched != run_matched {
+            if byte > run_start {
+                spans.push(Span::styled(&text[run_start..byte], if run_matched { matched_style } else { style }));
+            }
+            run_start = byte;
+            run_matched = matched;
+        }
+    }
+    if run_start < text.len() {
+        spans.push(Span::styled(&text[run_start..], if run_matched { matched_style } else { style }));
+    }
+    spans
+}
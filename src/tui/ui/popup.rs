@@ -22,7 +22,7 @@ pub fn render(f: &mut Frame, app: &App) {
 }
 
 pub fn render_command_details_popup(f: &mut Frame, app: &App) {
-    let area = centered_rect(60, 30, f.area());
+    let area = centered_rect(70, 80, f.area());
     f.render_widget(Clear, area);
 
     if let Some(cmd) = &app.selected_command_details {
@@ -30,6 +30,7 @@ pub fn render_command_details_popup(f: &mut Frame, app: &App) {
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(7), // Details
+                Constraint::Min(5),    // Why this score
                 Constraint::Length(3), // Buttons
             ])
             .split(area);
@@ -58,14 +59,21 @@ pub fn render_command_details_popup(f: &mut Frame, app: &App) {
             .style(Style::default().fg(Color::White));
         f.render_widget(details, chunks[0]);
 
+        let explanation = Paragraph::new(explanation_lines(app))
+            .block(Block::default().borders(Borders::ALL).title("Why This Score"))
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: false });
+        f.render_widget(explanation, chunks[1]);
+
         let button_chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
-                Constraint::Percentage(33),
-                Constraint::Percentage(33),
-                Constraint::Percentage(34),
+                Constraint::Percentage(25),
+                Constraint::Percentage(25),
+                Constraint::Percentage(25),
+                Constraint::Percentage(25),
             ])
-            .split(chunks[1]);
+            .split(chunks[2]);
 
         let add_alias_style = if app.command_details_selection == 0 {
             Style::default().fg(Color::White).bg(Color::DarkGray)
@@ -77,7 +85,12 @@ pub fn render_command_details_popup(f: &mut Frame, app: &App) {
         } else {
             Style::default().fg(Color::Red)
         };
-        let back_style = if app.command_details_selection == 2 {
+        let snooze_style = if app.command_details_selection == 2 {
+            Style::default().fg(Color::White).bg(Color::DarkGray)
+        } else {
+            Style::default().fg(Color::Magenta)
+        };
+        let back_style = if app.command_details_selection == 3 {
             Style::default().fg(Color::White).bg(Color::DarkGray)
         } else {
             Style::default().fg(Color::Yellow)
@@ -93,11 +106,75 @@ pub fn render_command_details_popup(f: &mut Frame, app: &App) {
             .block(Block::default().borders(Borders::ALL));
         f.render_widget(delete_btn, button_chunks[1]);
 
+        let snooze_btn = Paragraph::new("Snooze 7 Days")
+            .style(snooze_style)
+            .block(Block::default().borders(Borders::ALL));
+        f.render_widget(snooze_btn, button_chunks[2]);
+
         let back_btn = Paragraph::new("Back")
             .style(back_style)
             .block(Block::default().borders(Borders::ALL));
-        f.render_widget(back_btn, button_chunks[2]);
+        f.render_widget(back_btn, button_chunks[3]);
+    }
+}
+
+fn explanation_lines(app: &App) -> Vec<Line<'_>> {
+    let explanation = match &app.command_explanation {
+        Some(explanation) => explanation,
+        None => return vec![Line::from(Span::styled("No explanation available.", Style::default().fg(Color::DarkGray)))],
+    };
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled("Rank: ", Style::default().fg(Color::Cyan)),
+        Span::raw(explanation.rank_text()),
+    ])];
+    for component in &explanation.components {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {}: ", component.label), Style::default().fg(Color::Cyan)),
+            Span::raw(component.value.clone()),
+        ]));
+    }
+    if explanation.command.decays > 0 {
+        lines.push(Line::from(format!("  Frequency halved {} time(s) by score resets", explanation.command.decays)));
+    }
+
+    let counts = |commands: &[(String, i32)]| {
+        commands.iter().map(|(text, freq)| format!("{} ({})", text, freq)).collect::<Vec<_>>().join(", ")
+    };
+    if !explanation.prefixes.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled("Prefixes: ", Style::default().fg(Color::Cyan)),
+            Span::raw(counts(&explanation.prefixes)),
+        ]));
+    }
+    if !explanation.extensions.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled("Extended by: ", Style::default().fg(Color::Cyan)),
+            Span::raw(counts(&explanation.extensions)),
+        ]));
+    }
+    if let Some(template) = &explanation.template {
+        lines.push(Line::from(vec![
+            Span::styled("Also counted as: ", Style::default().fg(Color::Cyan)),
+            Span::raw(template.clone()),
+        ]));
+    }
+
+    if !explanation.suggestions.is_empty() {
+        lines.push(Line::from(Span::styled("Alias suggestions:", Style::default().fg(Color::Cyan))));
+    }
+    for ranked in explanation.suggestions.iter().take(5) {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<8}", ranked.suggestion.alias), Style::default().fg(Color::Green)),
+            Span::raw(format!(
+                "{:>3} = {}, ~{:.0} keys/day",
+                ranked.priority.total(),
+                ranked.priority.describe(),
+                ranked.savings.daily_keystrokes
+            )),
+        ]));
     }
+    lines
 }
 
 fn centered_rect(
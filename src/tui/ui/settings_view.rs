@@ -0,0 +1,49 @@
+use crate::tui::app::App;
+use crate::tui::tabs::settings::SettingKind;
+use crate::tui::ui::{highlight_spans, render_controls, render_filter, tab_layout};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+
+pub fn render(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let chunks = tab_layout(area);
+    render_filter(f, &app.settings_tab, chunks[0]);
+
+    let panes = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(4)])
+        .split(chunks[1]);
+
+    let name_width = app.settings.iter().map(|s| s.name.len()).max().unwrap_or(0);
+    let settings: Vec<ListItem> = app
+        .filtered_settings
+        .iter()
+        .map(|(i, positions)| {
+            let setting = &app.settings[*i];
+            let mut spans = highlight_spans(setting.name, positions, Style::default().fg(Color::Magenta));
+            spans.push(Span::raw(" ".repeat(name_width - setting.name.len() + 2)));
+            // Values that can be changed here stand out from the read-only ones
+            let value_color = if setting.kind == SettingKind::ReadOnly { Color::Blue } else { Color::Green };
+            spans.push(Span::styled(setting.value.as_str(), Style::default().fg(value_color)));
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let settings_list = List::new(settings)
+        .block(Block::default().borders(Borders::ALL).title("Settings"))
+        .highlight_style(Style::default().bg(Color::DarkGray))
+        .highlight_symbol(">> ");
+    f.render_stateful_widget(settings_list, panes[0], &mut app.settings_tab.state.clone());
+
+    let about = Paragraph::new(app.get_selected_setting().map(|s| s.about).unwrap_or(""))
+        .style(Style::default().fg(Color::Gray))
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL).title("About"));
+    f.render_widget(about, panes[1]);
+
+    render_controls(f, "↑/↓ - Navigate | Enter/→ - Next value | ← - Previous value | / - Filter | Tab/1-5 - Switch tab", chunks[2]);
+}